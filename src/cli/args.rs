@@ -1,49 +1,158 @@
 //! Argument parsing for CLI
 
+use crate::cli::input::{dedup_usernames, load_usernames};
+use crate::core::variants::{VariantOptions, PLACEHOLDER};
 use crate::data::site_info::SiteType;
-use clap::Parser;
+use crate::utils::error::Result;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
 #[command(name = "sleuth")]
 #[command(about = "A Rust implementation of sherlock")]
 #[command(version)]
+#[command(subcommand_negates_reqs = true, args_conflicts_with_subcommands = true)]
 pub struct Args {
-    /// Username to search for
-    #[arg(required = true)]
-    pub username: String,
+    /// Usernames to search for
+    #[arg(value_name = "USERNAME", required_unless_present = "input")]
+    pub usernames: Vec<String>,
+
+    /// Read more usernames from a file, or from stdin with `-` (one per line)
+    #[arg(long = "input", short = 'i', value_name = "PATH")]
+    pub input: Option<PathBuf>,
+
+    /// Read `--input` as CSV and take usernames from this column of the header row
+    #[arg(long = "input-column", value_name = "NAME", requires = "input")]
+    pub input_column: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
 
     /// Filter by site type (dev, social, nsfw, professional, gaming, forum, other)
     /// Can be specified multiple times to include multiple types
-    #[arg(long = "type", short = 't', value_name = "TYPE")]
+    #[arg(long = "type", short = 't', value_name = "TYPE", global = true)]
     pub site_types: Vec<String>,
 
     /// Filter by specific site names
     /// Can be specified multiple times
-    #[arg(long = "site", short = 's', value_name = "SITE")]
+    #[arg(long = "site", short = 's', value_name = "SITE", global = true)]
     pub sites: Vec<String>,
 
     /// Output format: text, json, csv
-    #[arg(long = "format", short = 'f', default_value = "text")]
+    #[arg(long = "format", short = 'f', default_value = "text", global = true)]
     pub output_format: String,
 
-    /// Timeout in seconds for each request
-    #[arg(long = "timeout", default_value_t = 10)]
+    /// Timeout in seconds for each request (sites may declare a longer one)
+    #[arg(long = "timeout", default_value_t = 10, global = true)]
     pub timeout: u64,
 
     /// Number of retries for failed requests
     #[arg(long = "retries", default_value_t = 3)]
     pub retries: u32,
 
+    /// Overall time limit in seconds for the whole search
+    /// Sites that have not answered by then are reported as timed out
+    #[arg(long = "deadline", value_name = "SECS")]
+    pub deadline: Option<u64>,
+
+    /// Maximum number of requests in flight at once
+    #[arg(long = "concurrency", default_value_t = 32, value_name = "N")]
+    pub concurrency: usize,
+
+    /// Maximum number of requests in flight to the same host
+    #[arg(long = "per-host", default_value_t = 4, value_name = "N")]
+    pub per_host: usize,
+
     /// Verify found results with browser headless rendering
     ///
-    /// By default, sleuth uses fast HTTP HEAD requests for all sites. When --verify is enabled,
+    /// By default, sleuth sends each site's HTTP probe (usually a fast HEAD). When --verify is enabled,
     /// a second pass is performed: sites that were found in the first pass and require browser
-    /// rendering (e.g., JavaScript-heavy sites like OnlyFans, Reddit) are verified using a
+    /// rendering (e.g., JavaScript-heavy sites like OnlyFans) are verified using a
     /// headless browser to ensure accuracy and eliminate false positives.
     ///
     /// This is slower but more accurate, especially for sites that render content dynamically.
     #[arg(long = "verify")]
     pub verify: bool,
+
+    /// Check every hit against a random, unclaimed control username and report
+    /// it as found only if the responses differ (catches sites that answer 200
+    /// for users that do not exist, at the cost of one more request per hit)
+    #[arg(long = "calibrate")]
+    pub calibrate: bool,
+
+    /// Pivot on usernames and links found in profiles, up to N hops away (0 disables)
+    #[arg(long = "pivot-depth", default_value_t = 0, value_name = "N")]
+    pub pivot_depth: usize,
+
+    /// Maximum number of discovered usernames to scan when pivoting
+    #[arg(long = "pivot-budget", default_value_t = 10, value_name = "N")]
+    pub pivot_budget: usize,
+
+    /// Also search variants of each username: separators, suffixes, leetspeak
+    /// A `{?}` in a username is filled with `_`, `-` and `.` even without this flag
+    #[arg(long = "variants", conflicts_with = "pivot_depth")]
+    pub variants: bool,
+
+    /// Full name to build variants from (e.g. "Jane Doe"); implies --variants
+    #[arg(
+        long = "full-name",
+        value_name = "NAME",
+        conflicts_with = "pivot_depth"
+    )]
+    pub full_name: Option<String>,
+
+    /// Birth year to add as a variant suffix; implies --variants
+    #[arg(
+        long = "birth-year",
+        value_name = "YEAR",
+        conflicts_with = "pivot_depth"
+    )]
+    pub birth_year: Option<u16>,
+
+    /// Maximum number of variants searched per username
+    #[arg(long = "max-variants", default_value_t = 25, value_name = "N")]
+    pub max_variants: usize,
+
+    /// Load additional site definitions from a JSON or TOML manifest
+    /// Can be specified multiple times
+    #[arg(long = "sites-file", value_name = "PATH", global = true)]
+    pub sites_files: Vec<PathBuf>,
+
+    /// Import sites from a sherlock `data.json` database
+    #[arg(long = "sherlock-data", value_name = "PATH", global = true)]
+    pub sherlock_data: Option<PathBuf>,
+
+    /// Import sites from a WhatsMyName `wmn-data.json` list
+    #[arg(long = "wmn-data", value_name = "PATH", global = true)]
+    pub wmn_data: Option<PathBuf>,
+}
+
+/// Subcommands
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Work with the site checkers
+    Sites {
+        #[command(subcommand)]
+        command: SitesCommand,
+    },
+}
+
+/// `sleuth sites` subcommands
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum SitesCommand {
+    /// Check every site against its known claimed and unclaimed usernames
+    ///
+    /// Reports checkers that give wrong answers; use `--format json` for a
+    /// machine-readable summary. Exits with status 1 if any checker fails.
+    Test {
+        /// Replay responses recorded in this fixture file instead of going online
+        #[arg(long = "fixtures", value_name = "PATH", conflicts_with = "record")]
+        fixtures: Option<PathBuf>,
+
+        /// Record the live responses into this fixture file
+        #[arg(long = "record", value_name = "PATH")]
+        record: Option<PathBuf>,
+    },
 }
 
 impl Args {
@@ -64,6 +173,40 @@ impl Args {
     pub fn has_site_filter(&self) -> bool {
         !self.sites.is_empty()
     }
+
+    /// Usernames given as arguments, then those read from `--input`, without repeats
+    pub fn load_usernames(&self) -> Result<Vec<String>> {
+        let mut usernames = self.usernames.clone();
+        if let Some(path) = &self.input {
+            usernames.extend(load_usernames(path, self.input_column.as_deref())?);
+        }
+        dedup_usernames(&mut usernames);
+        Ok(usernames)
+    }
+
+    /// Whether `usernames` (from [`Args::load_usernames`]) are seeds to expand
+    /// into variants
+    pub fn wants_variants(&self, usernames: &[String]) -> bool {
+        self.variants
+            || self.full_name.is_some()
+            || self.birth_year.is_some()
+            || usernames.iter().any(|u| u.contains(PLACEHOLDER))
+    }
+
+    /// How to expand usernames into variants
+    pub fn variant_options(&self) -> VariantOptions {
+        VariantOptions {
+            max_variants: self.max_variants,
+            full_name: self.full_name.clone(),
+            birth_year: self.birth_year,
+            ..VariantOptions::default()
+        }
+    }
+
+    /// Whether several usernames may be searched, so output is grouped per username
+    pub fn is_batch(&self) -> bool {
+        self.usernames.len() > 1 || self.input.is_some()
+    }
 }
 
 #[cfg(test)]
@@ -73,13 +216,29 @@ mod tests {
     #[test]
     fn test_args_parsed_site_types() {
         let args = Args {
-            username: "test".to_string(),
+            usernames: vec!["test".to_string()],
+            input: None,
+            input_column: None,
             site_types: vec!["dev".to_string(), "social".to_string()],
             sites: vec![],
             output_format: "text".to_string(),
             timeout: 10,
             retries: 3,
+            deadline: None,
+            concurrency: 32,
+            per_host: 4,
             verify: false,
+            calibrate: false,
+            pivot_depth: 0,
+            pivot_budget: 10,
+            variants: false,
+            full_name: None,
+            birth_year: None,
+            max_variants: 25,
+            sites_files: vec![],
+            sherlock_data: None,
+            wmn_data: None,
+            command: None,
         };
         let types = args.parsed_site_types();
         assert_eq!(types.len(), 2);
@@ -90,13 +249,29 @@ mod tests {
     #[test]
     fn test_args_parsed_site_types_invalid() {
         let args = Args {
-            username: "test".to_string(),
+            usernames: vec!["test".to_string()],
+            input: None,
+            input_column: None,
             site_types: vec!["dev".to_string(), "invalid".to_string()],
             sites: vec![],
             output_format: "text".to_string(),
             timeout: 10,
             retries: 3,
+            deadline: None,
+            concurrency: 32,
+            per_host: 4,
             verify: false,
+            calibrate: false,
+            pivot_depth: 0,
+            pivot_budget: 10,
+            variants: false,
+            full_name: None,
+            birth_year: None,
+            max_variants: 25,
+            sites_files: vec![],
+            sherlock_data: None,
+            wmn_data: None,
+            command: None,
         };
         let types = args.parsed_site_types();
         assert_eq!(types.len(), 1);
@@ -106,24 +281,56 @@ mod tests {
     #[test]
     fn test_args_has_type_filter() {
         let args = Args {
-            username: "test".to_string(),
+            usernames: vec!["test".to_string()],
+            input: None,
+            input_column: None,
             site_types: vec!["dev".to_string()],
             sites: vec![],
             output_format: "text".to_string(),
             timeout: 10,
             retries: 3,
+            deadline: None,
+            concurrency: 32,
+            per_host: 4,
             verify: false,
+            calibrate: false,
+            pivot_depth: 0,
+            pivot_budget: 10,
+            variants: false,
+            full_name: None,
+            birth_year: None,
+            max_variants: 25,
+            sites_files: vec![],
+            sherlock_data: None,
+            wmn_data: None,
+            command: None,
         };
         assert!(args.has_type_filter());
 
         let args_no_filter = Args {
-            username: "test".to_string(),
+            usernames: vec!["test".to_string()],
+            input: None,
+            input_column: None,
             site_types: vec![],
             sites: vec![],
             output_format: "text".to_string(),
             timeout: 10,
             retries: 3,
+            deadline: None,
+            concurrency: 32,
+            per_host: 4,
             verify: false,
+            calibrate: false,
+            pivot_depth: 0,
+            pivot_budget: 10,
+            variants: false,
+            full_name: None,
+            birth_year: None,
+            max_variants: 25,
+            sites_files: vec![],
+            sherlock_data: None,
+            wmn_data: None,
+            command: None,
         };
         assert!(!args_no_filter.has_type_filter());
     }
@@ -131,25 +338,194 @@ mod tests {
     #[test]
     fn test_args_has_site_filter() {
         let args = Args {
-            username: "test".to_string(),
+            usernames: vec!["test".to_string()],
+            input: None,
+            input_column: None,
             site_types: vec![],
             sites: vec!["github".to_string()],
             output_format: "text".to_string(),
             timeout: 10,
             retries: 3,
+            deadline: None,
+            concurrency: 32,
+            per_host: 4,
             verify: false,
+            calibrate: false,
+            pivot_depth: 0,
+            pivot_budget: 10,
+            variants: false,
+            full_name: None,
+            birth_year: None,
+            max_variants: 25,
+            sites_files: vec![],
+            sherlock_data: None,
+            wmn_data: None,
+            command: None,
         };
         assert!(args.has_site_filter());
 
         let args_no_filter = Args {
-            username: "test".to_string(),
+            usernames: vec!["test".to_string()],
+            input: None,
+            input_column: None,
             site_types: vec![],
             sites: vec![],
             output_format: "text".to_string(),
             timeout: 10,
             retries: 3,
+            deadline: None,
+            concurrency: 32,
+            per_host: 4,
             verify: false,
+            calibrate: false,
+            pivot_depth: 0,
+            pivot_budget: 10,
+            variants: false,
+            full_name: None,
+            birth_year: None,
+            max_variants: 25,
+            sites_files: vec![],
+            sherlock_data: None,
+            wmn_data: None,
+            command: None,
         };
         assert!(!args_no_filter.has_site_filter());
     }
+
+    #[test]
+    fn test_args_parse_search() {
+        let args = Args::try_parse_from(["sleuth", "blue", "--site", "GitHub"]).unwrap();
+        assert_eq!(args.usernames, vec!["blue"]);
+        assert!(!args.is_batch());
+        assert!(args.command.is_none());
+        assert!(args.deadline.is_none());
+        assert!(Args::try_parse_from(["sleuth"]).is_err());
+
+        let args =
+            Args::try_parse_from(["sleuth", "blue", "--timeout", "5", "--deadline", "60"]).unwrap();
+        assert_eq!(args.timeout, 5);
+        assert_eq!(args.deadline, Some(60));
+        assert_eq!(args.concurrency, 32);
+
+        let args =
+            Args::try_parse_from(["sleuth", "blue", "--concurrency", "8", "--per-host", "2"])
+                .unwrap();
+        assert_eq!(args.concurrency, 8);
+        assert_eq!(args.per_host, 2);
+        assert_eq!(args.pivot_depth, 0);
+        assert!(!args.calibrate);
+        assert!(
+            Args::try_parse_from(["sleuth", "blue", "--calibrate"])
+                .unwrap()
+                .calibrate
+        );
+
+        let args = Args::try_parse_from(["sleuth", "blue", "--pivot-depth", "2"]).unwrap();
+        assert_eq!(args.pivot_depth, 2);
+        assert_eq!(args.pivot_budget, 10);
+    }
+
+    #[test]
+    fn test_args_parse_batch() {
+        let args = Args::try_parse_from(["sleuth", "blue", "navy", "Blue"]).unwrap();
+        assert!(args.is_batch());
+        assert_eq!(args.load_usernames().unwrap(), vec!["blue", "navy"]);
+
+        let args = Args::try_parse_from(["sleuth", "--input", "-"]).unwrap();
+        assert!(args.usernames.is_empty());
+        assert_eq!(args.input, Some(PathBuf::from("-")));
+        assert!(args.is_batch());
+
+        let path =
+            std::env::temp_dir().join(format!("sleuth-usernames-{}.csv", std::process::id()));
+        std::fs::write(&path, "handle,site\nnavy,x\nsky,y\n").unwrap();
+        let args = Args::try_parse_from([
+            "sleuth",
+            "blue",
+            "--input",
+            path.to_str().unwrap(),
+            "--input-column",
+            "handle",
+        ])
+        .unwrap();
+        assert_eq!(args.load_usernames().unwrap(), vec!["blue", "navy", "sky"]);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(Args::try_parse_from(["sleuth", "blue", "--input-column", "handle"]).is_err());
+    }
+
+    #[test]
+    fn test_args_parse_variants() {
+        let args = Args::try_parse_from(["sleuth", "blue"]).unwrap();
+        assert!(!args.wants_variants(&args.load_usernames().unwrap()));
+
+        let args = Args::try_parse_from(["sleuth", "jane{?}doe"]).unwrap();
+        assert!(args.wants_variants(&args.load_usernames().unwrap()));
+
+        // Templates read from --input are seeds too
+        let path =
+            std::env::temp_dir().join(format!("sleuth-templates-{}.txt", std::process::id()));
+        std::fs::write(&path, "blue\njane{?}doe\n").unwrap();
+        let args = Args::try_parse_from(["sleuth", "--input", path.to_str().unwrap()]).unwrap();
+        let usernames = args.load_usernames().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(args.wants_variants(&usernames));
+
+        let args = Args::try_parse_from([
+            "sleuth",
+            "jd",
+            "--full-name",
+            "Jane Doe",
+            "--birth-year",
+            "1990",
+            "--max-variants",
+            "5",
+        ])
+        .unwrap();
+        assert!(args.wants_variants(&args.load_usernames().unwrap()));
+        let options = args.variant_options();
+        assert_eq!(options.full_name.as_deref(), Some("Jane Doe"));
+        assert_eq!(options.birth_year, Some(1990));
+        assert_eq!(options.max_variants, 5);
+
+        assert!(
+            Args::try_parse_from(["sleuth", "jd", "--variants", "--pivot-depth", "1"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_args_parse_sites_test() {
+        let args = Args::try_parse_from([
+            "sleuth",
+            "sites",
+            "test",
+            "--fixtures",
+            "fixtures.json",
+            "--format",
+            "json",
+        ])
+        .unwrap();
+        assert!(args.usernames.is_empty());
+        assert_eq!(args.output_format, "json");
+        assert_eq!(
+            args.command,
+            Some(Command::Sites {
+                command: SitesCommand::Test {
+                    fixtures: Some(PathBuf::from("fixtures.json")),
+                    record: None,
+                }
+            })
+        );
+
+        assert!(Args::try_parse_from([
+            "sleuth",
+            "sites",
+            "test",
+            "--fixtures",
+            "a.json",
+            "--record",
+            "b.json"
+        ])
+        .is_err());
+    }
 }
@@ -0,0 +1,125 @@
+//! Username lists for batch searches
+
+use crate::utils::error::{Result, SleuthError};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+/// Read usernames from a file, or from stdin if `path` is `-`
+///
+/// See [`read_usernames`] for the format.
+pub fn load_usernames(path: &Path, column: Option<&str>) -> Result<Vec<String>> {
+    if path == Path::new("-") {
+        read_usernames(io::stdin().lock(), column)
+    } else {
+        read_usernames(BufReader::new(File::open(path)?), column)
+    }
+}
+
+/// Read usernames, one per line, or from the `column` of a CSV with a header row
+///
+/// Blank lines and lines starting with `#` are skipped.
+pub fn read_usernames(reader: impl BufRead, column: Option<&str>) -> Result<Vec<String>> {
+    let mut lines = reader.lines().filter(|line| {
+        line.as_ref().map_or(true, |l| {
+            !l.trim().is_empty() && !l.trim_start().starts_with('#')
+        })
+    });
+
+    let index = match column {
+        Some(column) => {
+            let header = lines.next().transpose()?.unwrap_or_default();
+            let index = csv_fields(&header)
+                .iter()
+                .position(|name| name.eq_ignore_ascii_case(column));
+            Some(index.ok_or_else(|| {
+                SleuthError::Validation(format!("no column '{}' in the CSV header", column))
+            })?)
+        }
+        None => None,
+    };
+
+    let mut usernames = Vec::new();
+    for line in lines {
+        let line = line?;
+        let username = match index {
+            Some(index) => csv_fields(&line).into_iter().nth(index).unwrap_or_default(),
+            None => line.trim().to_string(),
+        };
+        if !username.is_empty() {
+            usernames.push(username);
+        }
+    }
+    Ok(usernames)
+}
+
+/// Drop repeated usernames (case-insensitively), keeping the first occurrence
+pub fn dedup_usernames(usernames: &mut Vec<String>) {
+    let mut seen = std::collections::HashSet::new();
+    usernames.retain(|username| seen.insert(username.to_lowercase()));
+}
+
+/// Fields of a CSV line, trimmed, with `"quoted, fields"` and `""` escapes
+fn csv_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field).trim().to_string()),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_usernames_lines() {
+        let input = "blue\n\n  navy  \n# comment\nsky\r\n";
+        let usernames = read_usernames(input.as_bytes(), None).unwrap();
+        assert_eq!(usernames, vec!["blue", "navy", "sky"]);
+    }
+
+    #[test]
+    fn test_read_usernames_csv_column() {
+        let input = "id,Username,note\n1,blue,\"likes, commas\"\n2,\"navy\",\n3,,empty\n";
+        let usernames = read_usernames(input.as_bytes(), Some("username")).unwrap();
+        assert_eq!(usernames, vec!["blue", "navy"]);
+
+        let err = read_usernames(input.as_bytes(), Some("handle")).unwrap_err();
+        assert!(matches!(err, SleuthError::Validation(_)));
+    }
+
+    #[test]
+    fn test_csv_fields() {
+        assert_eq!(
+            csv_fields(r#"a, "b, c" ,"say ""hi""""#),
+            vec!["a", "b, c", r#"say "hi""#]
+        );
+        assert_eq!(csv_fields(""), vec![""]);
+    }
+
+    #[test]
+    fn test_dedup_usernames() {
+        let mut usernames = vec!["Blue".to_string(), "navy".to_string(), "blue".to_string()];
+        dedup_usernames(&mut usernames);
+        assert_eq!(usernames, vec!["Blue", "navy"]);
+    }
+
+    #[test]
+    fn test_load_usernames_missing_file() {
+        let err = load_usernames(Path::new("/nonexistent/usernames.txt"), None).unwrap_err();
+        assert!(matches!(err, SleuthError::Io(_)));
+    }
+}
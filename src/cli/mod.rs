@@ -1,10 +1,14 @@
 //! Command-line interface module
 
 pub mod args;
+pub mod input;
 pub mod output;
 
-pub use args::Args;
-pub use output::print_results;
+pub use args::{Args, Command, SitesCommand};
+pub use output::{
+    print_pivot_report, print_pivot_reports, print_results, print_self_test, print_variant_report,
+    print_variant_reports, BatchPrinter, ResultPrinter,
+};
 
 #[cfg(test)]
 mod tests {
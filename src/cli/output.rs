@@ -1,6 +1,11 @@
 //! Output formatting for CLI
 
-use crate::core::result::SearchResult;
+use crate::core::pivot::{Discovery, PivotReport};
+use crate::core::result::{Outcome, SearchResult, UsernameResults};
+use crate::core::scanner::merge_result;
+use crate::core::self_test::{CheckStatus, SelfTestSummary, UsernameCheck};
+use crate::core::variants::{VariantKind, VariantReport};
+use crate::sites::profile::ProfileInfo;
 use colored::*;
 use serde_json;
 
@@ -13,32 +18,313 @@ pub fn print_results(results: &[SearchResult], format: &str) {
 }
 
 fn print_text(results: &[SearchResult]) {
-    let found: Vec<&SearchResult> = results.iter().filter(|r| r.exists).collect();
-    let not_found: Vec<&SearchResult> = results.iter().filter(|r| !r.exists).collect();
+    for outcome in Outcome::ALL {
+        let matching: Vec<&SearchResult> =
+            results.iter().filter(|r| r.outcome == outcome).collect();
+        if matching.is_empty() {
+            continue;
+        }
 
-    if !found.is_empty() {
-        println!("\n{}", "Found:".green().bold());
-        for result in &found {
-            if let Some(url) = &result.url {
-                println!("  {}: {}", result.site.green(), url.blue());
-            } else {
-                println!("  {}", result.site.green());
+        println!("\n{}", section_title(outcome));
+        for result in matching {
+            let explanation = explanation(result).dimmed();
+            match (&result.url, &result.error) {
+                (Some(url), _) if outcome == Outcome::Found => {
+                    println!("  {}: {}{}", result.site.green(), url.blue(), explanation)
+                }
+                (_, Some(error)) => println!(
+                    "  {}: {}{}",
+                    colorize(outcome, &result.site),
+                    error,
+                    explanation
+                ),
+                _ => println!("  {}{}", colorize(outcome, &result.site), explanation),
+            }
+            print_profile(result, "    ");
+        }
+    }
+
+    print_totals(results);
+}
+
+/// Print the results of a recursive search, then which hit led to which
+pub fn print_pivot_report(report: &PivotReport, format: &str) {
+    match format {
+        "json" => match serde_json::to_string_pretty(report) {
+            Ok(json) => println!("{}", json),
+            Err(e) => eprintln!("Error serializing to JSON: {}", e),
+        },
+        "csv" => print_csv(&report.results),
+        _ => print_pivot_text(report),
+    }
+}
+
+/// Print the recursive searches of a batch, one report per searched username
+pub fn print_pivot_reports(reports: &[PivotReport], format: &str) {
+    match format {
+        "json" => match serde_json::to_string_pretty(reports) {
+            Ok(json) => println!("{}", json),
+            Err(e) => eprintln!("Error serializing to JSON: {}", e),
+        },
+        "csv" => {
+            println!("{}", CSV_HEADER);
+            for result in reports.iter().flat_map(|report| &report.results) {
+                print_csv_row(result);
             }
         }
+        _ => reports.iter().for_each(print_pivot_text),
+    }
+}
+
+fn print_pivot_text(report: &PivotReport) {
+    for discovery in &report.discoveries {
+        let results: Vec<SearchResult> = report.results_for(&discovery.username).cloned().collect();
+        print_username_title(&discovery.username);
+        print_text(&results);
+    }
+    println!("\n{}", "Discovery graph:".bold());
+    for line in discovery_lines(report) {
+        println!("  {}", line);
     }
+}
+
+/// Print which variants of a seed were found on which site
+pub fn print_variant_report(report: &VariantReport, format: &str) {
+    match format {
+        "json" => match serde_json::to_string_pretty(report) {
+            Ok(json) => println!("{}", json),
+            Err(e) => eprintln!("Error serializing to JSON: {}", e),
+        },
+        _ => print_variant_reports(std::slice::from_ref(report), format),
+    }
+}
 
-    if !not_found.is_empty() {
-        println!("\n{}", "Not Found:".red().bold());
-        for result in &not_found {
-            println!("  {}", result.site.red());
+/// Print the variant searches of a batch, one report per seed
+pub fn print_variant_reports(reports: &[VariantReport], format: &str) {
+    match format {
+        "json" => match serde_json::to_string_pretty(reports) {
+            Ok(json) => println!("{}", json),
+            Err(e) => eprintln!("Error serializing to JSON: {}", e),
+        },
+        "csv" => {
+            println!("{}", VARIANT_CSV_HEADER);
+            for report in reports {
+                for found in &report.matches {
+                    println!(
+                        "{},{},{},{},{}",
+                        report.seed,
+                        found.site,
+                        found.username,
+                        variant_kind(found.kind),
+                        found.url.as_deref().unwrap_or("")
+                    );
+                }
+            }
         }
+        _ => reports.iter().for_each(print_variant_text),
     }
+}
 
+const VARIANT_CSV_HEADER: &str = "seed,site,username,kind,url";
+
+fn print_variant_text(report: &VariantReport) {
+    let variants: Vec<&str> = report
+        .variants
+        .iter()
+        .map(|v| v.username.as_str())
+        .collect();
     println!(
-        "\nTotal: {} found, {} not found",
-        found.len(),
-        not_found.len()
+        "\n{}",
+        format!("Variants of {} ({} searched):", report.seed, variants.len()).bold()
     );
+    println!("  {}", variants.join(", ").dimmed());
+
+    if report.matches.is_empty() {
+        println!("\n{}", "No variant found".red().bold());
+    } else {
+        println!("\n{}", "Found:".green().bold());
+        for found in &report.matches {
+            println!(
+                "  {}: {} [{}] {}",
+                found.site.green(),
+                found.username,
+                variant_kind(found.kind),
+                found.url.as_deref().unwrap_or("").blue()
+            );
+        }
+    }
+    print_totals(&report.results);
+}
+
+fn variant_kind(kind: VariantKind) -> &'static str {
+    match kind {
+        VariantKind::Seed => "seed",
+        VariantKind::Separator => "separator",
+        VariantKind::Name => "name",
+        VariantKind::Suffix => "suffix",
+        VariantKind::Leetspeak => "leetspeak",
+    }
+}
+
+fn print_username_title(username: &str) {
+    println!("\n{}", format!("Results for {}:", username).bold());
+}
+
+/// The discovery graph as an indented tree, one scanned username per line
+fn discovery_lines(report: &PivotReport) -> Vec<String> {
+    fn walk(report: &PivotReport, discovery: &Discovery, lines: &mut Vec<String>) {
+        let indent = "  ".repeat(discovery.depth);
+        match &discovery.from {
+            Some(from) => lines.push(format!(
+                "{}-> {} (from {} on {}, via {})",
+                indent, discovery.username, from.username, from.site, from.via
+            )),
+            None => lines.push(discovery.username.clone()),
+        }
+        for child in report.discovered_from(&discovery.username) {
+            walk(report, child, lines);
+        }
+    }
+
+    let mut lines = Vec::new();
+    for root in report.discoveries.iter().filter(|d| d.from.is_none()) {
+        walk(report, root, &mut lines);
+    }
+    lines
+}
+
+/// Confidence and reason of a result, e.g. ` [90%] rule 'ok' matched HTTP 200 (...)`
+fn explanation(result: &SearchResult) -> String {
+    if result.reason.is_empty() {
+        return String::new();
+    }
+    format!(" [{}%] {}", result.confidence, result.reason)
+}
+
+/// Print the profile details of a result, one field per line
+fn print_profile(result: &SearchResult, indent: &str) {
+    if let Some(profile) = &result.profile {
+        for line in profile_lines(profile) {
+            println!("{}{}", indent, line.dimmed());
+        }
+    }
+}
+
+/// Profile fields as `Label: value` lines, skipping unknown fields
+fn profile_lines(profile: &ProfileInfo) -> Vec<String> {
+    let counts = |label: &str, count: Option<u64>| count.map(|n| format!("{}: {}", label, n));
+    [
+        profile.name.as_ref().map(|v| format!("Name: {}", v)),
+        profile
+            .username
+            .as_ref()
+            .map(|v| format!("Username: {}", v)),
+        profile.bio.as_ref().map(|v| format!("Bio: {}", v)),
+        profile
+            .location
+            .as_ref()
+            .map(|v| format!("Location: {}", v)),
+        counts("Followers", profile.followers),
+        counts("Following", profile.following),
+        profile
+            .created_at
+            .as_ref()
+            .map(|v| format!("Created: {}", v)),
+        profile
+            .avatar_url
+            .as_ref()
+            .map(|v| format!("Avatar: {}", v)),
+        (!profile.links.is_empty()).then(|| format!("Links: {}", profile.links.join(", "))),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+fn section_title(outcome: Outcome) -> ColoredString {
+    let title = match outcome {
+        Outcome::Found => "Found:",
+        Outcome::NotFound => "Not Found:",
+        Outcome::Uncertain => "Uncertain:",
+        Outcome::Blocked => "Blocked:",
+        Outcome::RateLimited => "Rate limited:",
+        Outcome::TimedOut => "Timed out:",
+        Outcome::Error => "Errors:",
+        Outcome::InvalidUsername => "Invalid for site (skipped):",
+        Outcome::Skipped => "Skipped:",
+    };
+    colorize(outcome, title).bold()
+}
+
+fn colorize(outcome: Outcome, text: &str) -> ColoredString {
+    match outcome {
+        Outcome::Found => text.green(),
+        Outcome::NotFound => text.red(),
+        Outcome::Error => text.magenta(),
+        _ => text.yellow(),
+    }
+}
+
+fn print_totals(results: &[SearchResult]) {
+    // Found and not found are always shown, other outcomes only when present
+    let total: Vec<String> = Outcome::ALL
+        .iter()
+        .map(|&outcome| {
+            (
+                outcome,
+                results.iter().filter(|r| r.outcome == outcome).count(),
+            )
+        })
+        .filter(|&(outcome, count)| count > 0 || outcome.is_conclusive())
+        .map(|(outcome, count)| format!("{} {}", count, outcome.label()))
+        .collect();
+    println!("\nTotal: {}", total.join(", "));
+}
+
+/// Print one result as a single line of text
+fn print_text_line(result: &SearchResult) {
+    let marker = match result.outcome {
+        _ if result.verified => "[v]",
+        Outcome::Found => "[+]",
+        Outcome::NotFound => "[-]",
+        Outcome::InvalidUsername | Outcome::Skipped => "[!]",
+        _ => "[?]",
+    };
+    let site = colorize(result.outcome, &result.site);
+    let verified = if result.verified { "verified " } else { "" };
+    let explanation = explanation(result).dimmed();
+
+    match (&result.url, &result.error) {
+        (Some(url), _) if result.exists() => {
+            println!(
+                "{} {}: {}{} {}{}",
+                marker,
+                site,
+                verified,
+                result.outcome.label(),
+                url.blue(),
+                explanation
+            )
+        }
+        (_, Some(error)) => println!(
+            "{} {}: {}{} ({}){}",
+            marker,
+            site,
+            verified,
+            result.outcome.label(),
+            error,
+            explanation
+        ),
+        _ => println!(
+            "{} {}: {}{}{}",
+            marker,
+            site,
+            verified,
+            result.outcome.label(),
+            explanation
+        ),
+    }
+    print_profile(result, "    ");
 }
 
 fn print_json(results: &[SearchResult]) {
@@ -49,20 +335,194 @@ fn print_json(results: &[SearchResult]) {
 }
 
 fn print_csv(results: &[SearchResult]) {
-    println!("site,username,exists,url");
+    println!("{}", CSV_HEADER);
     for result in results {
-        let url = result.url.as_deref().unwrap_or("");
-        println!(
-            "{},{},{},{}",
-            result.site, result.username, result.exists, url
-        );
+        print_csv_row(result);
+    }
+}
+
+const CSV_HEADER: &str = "site,username,outcome,url";
+
+fn print_csv_row(result: &SearchResult) {
+    let url = result.url.as_deref().unwrap_or("");
+    println!(
+        "{},{},{},{}",
+        result.site, result.username, result.outcome, url
+    );
+}
+
+/// Prints results as a scan streams them in
+///
+/// Text and CSV print one line per result as it arrives; a verification update
+/// prints another line for its site. JSON prints the merged array once the
+/// scan is done, and text ends with the totals.
+pub struct ResultPrinter {
+    format: String,
+    results: Vec<SearchResult>,
+}
+
+impl ResultPrinter {
+    pub fn new(format: &str) -> Self {
+        if format == "csv" {
+            println!("{}", CSV_HEADER);
+        }
+        Self {
+            format: format.to_string(),
+            results: Vec::new(),
+        }
+    }
+
+    /// Print a result as it arrives
+    pub fn print(&mut self, result: SearchResult) {
+        match self.format.as_str() {
+            "json" => {}
+            "csv" => print_csv_row(&result),
+            _ => print_text_line(&result),
+        }
+        merge_result(&mut self.results, result);
+    }
+
+    /// Finish the output and return the merged results
+    pub fn finish(self) -> Vec<SearchResult> {
+        match self.format.as_str() {
+            "json" => print_json(&self.results),
+            "csv" => {}
+            _ => print_totals(&self.results),
+        }
+        self.results
+    }
+}
+
+/// Prints the results of a batch search, grouped per username
+///
+/// Text and CSV print each username's results as its search completes; JSON
+/// prints an array of `{username, results}` objects once the batch is done,
+/// and text ends with the totals over every username.
+pub struct BatchPrinter {
+    format: String,
+    groups: Vec<UsernameResults>,
+}
+
+impl BatchPrinter {
+    pub fn new(format: &str) -> Self {
+        if format == "csv" {
+            println!("{}", CSV_HEADER);
+        }
+        Self {
+            format: format.to_string(),
+            groups: Vec::new(),
+        }
+    }
+
+    /// Print the results of one username
+    pub fn print(&mut self, group: UsernameResults) {
+        match self.format.as_str() {
+            "json" => {}
+            "csv" => group.results.iter().for_each(print_csv_row),
+            _ => {
+                print_username_title(&group.username);
+                print_text(&group.results);
+            }
+        }
+        self.groups.push(group);
+    }
+
+    /// Finish the output and return every username's results
+    pub fn finish(self) -> Vec<UsernameResults> {
+        match self.format.as_str() {
+            "json" => match serde_json::to_string_pretty(&self.groups) {
+                Ok(json) => println!("{}", json),
+                Err(e) => eprintln!("Error serializing to JSON: {}", e),
+            },
+            "csv" => {}
+            _ => {
+                let results: Vec<SearchResult> = self
+                    .groups
+                    .iter()
+                    .flat_map(|group| group.results.iter().cloned())
+                    .collect();
+                println!(
+                    "\n{}",
+                    format!("All {} usernames:", self.groups.len()).bold()
+                );
+                print_totals(&results);
+            }
+        }
+        self.groups
+    }
+}
+
+/// Print a site self-test summary
+pub fn print_self_test(summary: &SelfTestSummary, format: &str) {
+    match format {
+        "json" => match serde_json::to_string_pretty(summary) {
+            Ok(json) => println!("{}", json),
+            Err(e) => eprintln!("Error serializing to JSON: {}", e),
+        },
+        "csv" => print_self_test_csv(summary),
+        _ => print_self_test_text(summary),
+    }
+}
+
+fn print_self_test_text(summary: &SelfTestSummary) {
+    let failures: Vec<_> = summary.failures().collect();
+    if !failures.is_empty() {
+        println!("\n{}", "Failing checkers:".red().bold());
+        for report in failures {
+            for check in [&report.claimed, &report.unclaimed].into_iter().flatten() {
+                if check.status != CheckStatus::Pass {
+                    println!("  {}: {}", report.site.red(), describe_check(check));
+                }
+            }
+        }
+    }
+
+    println!(
+        "\nTotal: {} passed, {} failed, {} untested",
+        summary.passed, summary.failed, summary.untested
+    );
+}
+
+fn describe_check(check: &UsernameCheck) -> String {
+    let expected = if check.expected_exists {
+        "found"
+    } else {
+        "not found"
+    };
+    let actual = match (check.outcome, check.actual_exists) {
+        (Some(outcome), _) => outcome.label(),
+        (None, Some(true)) => "found",
+        (None, Some(false)) => "not found",
+        (None, None) => "an error",
+    };
+    format!("'{}' expected {}, got {}", check.username, expected, actual)
+}
+
+fn print_self_test_csv(summary: &SelfTestSummary) {
+    println!("site,username,expected_exists,actual_exists,status");
+    for report in &summary.sites {
+        for check in [&report.claimed, &report.unclaimed].into_iter().flatten() {
+            let actual = check
+                .actual_exists
+                .map(|a| a.to_string())
+                .unwrap_or_default();
+            let status = match check.status {
+                CheckStatus::Pass => "pass",
+                CheckStatus::Fail => "fail",
+                CheckStatus::Error => "error",
+            };
+            println!(
+                "{},{},{},{},{}",
+                report.site, check.username, check.expected_exists, actual, status
+            );
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::result::SearchResult;
+    use crate::core::result::{Outcome, SearchResult};
 
     #[test]
     fn test_print_results_text() {
@@ -77,6 +537,177 @@ mod tests {
         print_results(&results, "text");
     }
 
+    #[test]
+    fn test_explanation() {
+        let result = SearchResult::not_found("GitHub".to_string(), "test".to_string());
+        assert_eq!(explanation(&result), "");
+
+        let result = result.with_reason(70, "rule 'missing' matched HTTP 404");
+        assert_eq!(
+            explanation(&result),
+            " [70%] rule 'missing' matched HTTP 404"
+        );
+        print_results(&[result], "text");
+    }
+
+    #[test]
+    fn test_profile_lines() {
+        let profile = ProfileInfo {
+            name: Some("Linus Torvalds".to_string()),
+            followers: Some(230000),
+            links: vec!["https://kernel.org".to_string()],
+            ..ProfileInfo::default()
+        };
+        assert_eq!(
+            profile_lines(&profile),
+            vec![
+                "Name: Linus Torvalds",
+                "Followers: 230000",
+                "Links: https://kernel.org"
+            ]
+        );
+
+        let result = SearchResult::found(
+            "GitHub".to_string(),
+            "torvalds".to_string(),
+            "https://github.com/torvalds".to_string(),
+        )
+        .with_profile(Some(profile));
+        print_text_line(&result);
+        print_results(&[result], "text");
+    }
+
+    #[test]
+    fn test_discovery_lines() {
+        use crate::core::pivot::PivotSource;
+
+        let discovery = |username: &str, depth: usize, from: Option<(&str, &str)>| Discovery {
+            username: username.to_string(),
+            depth,
+            from: from.map(|(parent, via)| PivotSource {
+                site: "GitHub".to_string(),
+                username: parent.to_string(),
+                via: via.to_string(),
+            }),
+        };
+        let report = PivotReport {
+            results: vec![SearchResult::not_found(
+                "GitHub".to_string(),
+                "sky".to_string(),
+            )],
+            discoveries: vec![
+                discovery("blue", 0, None),
+                discovery("navy", 1, Some(("blue", "profile username"))),
+                discovery("sky", 1, Some(("blue", "https://sky.example"))),
+                discovery("ink", 2, Some(("navy", "https://github.com/ink"))),
+            ],
+        };
+        assert_eq!(
+            discovery_lines(&report),
+            vec![
+                "blue",
+                "  -> navy (from blue on GitHub, via profile username)",
+                "    -> ink (from navy on GitHub, via https://github.com/ink)",
+                "  -> sky (from blue on GitHub, via https://sky.example)",
+            ]
+        );
+
+        print_pivot_report(&report, "text");
+        print_pivot_report(&report, "json");
+        print_pivot_report(&report, "csv");
+        let reports = [report.clone(), report];
+        for format in ["text", "json", "csv"] {
+            print_pivot_reports(&reports, format);
+        }
+    }
+
+    #[test]
+    fn test_print_variant_reports() {
+        use crate::core::variants::{generate_variants, VariantOptions};
+
+        let variants = generate_variants("jane{?}doe", &VariantOptions::default());
+        let results = vec![SearchResult::found(
+            "GitHub".to_string(),
+            "jane_doe".to_string(),
+            "https://github.com/jane_doe".to_string(),
+        )];
+        let report = VariantReport::new("jane{?}doe", variants, results);
+        assert_eq!(variant_kind(report.matches[0].kind), "separator");
+
+        for format in ["text", "json", "csv"] {
+            print_variant_report(&report, format);
+            print_variant_reports(&[report.clone(), report.clone()], format);
+        }
+        print_variant_report(&VariantReport::new("nobody", vec![], vec![]), "text");
+    }
+
+    #[test]
+    fn test_batch_printer() {
+        let group = |username: &str| UsernameResults {
+            username: username.to_string(),
+            results: vec![
+                SearchResult::found(
+                    "GitHub".to_string(),
+                    username.to_string(),
+                    format!("https://github.com/{}", username),
+                ),
+                SearchResult::not_found("Reddit".to_string(), username.to_string()),
+            ],
+        };
+        for format in ["text", "json", "csv"] {
+            let mut printer = BatchPrinter::new(format);
+            printer.print(group("blue"));
+            printer.print(group("navy"));
+            let groups = printer.finish();
+            assert_eq!(groups.len(), 2);
+            assert_eq!(groups[1].username, "navy");
+        }
+
+        let json = serde_json::to_value([group("blue")]).unwrap();
+        assert_eq!(json[0]["username"], "blue");
+        assert_eq!(json[0]["results"][1]["outcome"], "not_found");
+    }
+
+    #[test]
+    fn test_print_results_text_invalid() {
+        let results = vec![
+            SearchResult::not_found("GitHub".to_string(), "test".to_string()),
+            SearchResult::invalid("Twitter".to_string(), "test".to_string()),
+        ];
+        print_results(&results, "text");
+    }
+
+    #[test]
+    fn test_result_printer() {
+        for format in ["text", "json", "csv"] {
+            let mut printer = ResultPrinter::new(format);
+            printer.print(SearchResult::found(
+                "OnlyFans".to_string(),
+                "test".to_string(),
+                "https://onlyfans.com/test".to_string(),
+            ));
+            printer.print(SearchResult::timed_out(
+                "GitHub".to_string(),
+                "test".to_string(),
+            ));
+            printer.print(
+                SearchResult::not_found("OnlyFans".to_string(), "test".to_string()).into_verified(),
+            );
+            let results = printer.finish();
+            assert_eq!(results.len(), 2);
+            assert!(!results[0].exists());
+        }
+    }
+
+    #[test]
+    fn test_print_results_text_timed_out() {
+        let results = vec![
+            SearchResult::not_found("GitHub".to_string(), "test".to_string()),
+            SearchResult::timed_out("OnlyFans".to_string(), "test".to_string()),
+        ];
+        print_results(&results, "text");
+    }
+
     #[test]
     fn test_print_results_text_empty() {
         let results: Vec<SearchResult> = vec![];
@@ -173,4 +804,31 @@ mod tests {
         // Should default to text format
         print_results(&results, "invalid");
     }
+
+    #[test]
+    fn test_print_self_test() {
+        use crate::core::self_test::SiteTestReport;
+
+        let check = UsernameCheck {
+            username: "blue".to_string(),
+            expected_exists: true,
+            actual_exists: Some(false),
+            outcome: Some(Outcome::NotFound),
+            rule: None,
+            status: CheckStatus::Fail,
+        };
+        assert_eq!(
+            describe_check(&check),
+            "'blue' expected found, got not found"
+        );
+
+        let summary = SelfTestSummary::from_reports(vec![SiteTestReport {
+            site: "GitHub".to_string(),
+            claimed: Some(check),
+            unclaimed: None,
+        }]);
+        print_self_test(&summary, "text");
+        print_self_test(&summary, "json");
+        print_self_test(&summary, "csv");
+    }
 }
@@ -1,8 +1,10 @@
 //! Configuration management
 
+pub mod sherlock;
 pub mod sites;
 pub mod timeout;
 pub mod user_agent;
+pub mod wmn;
 
 #[cfg(test)]
 mod tests {
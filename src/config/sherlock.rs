@@ -0,0 +1,444 @@
+//! Importer for sherlock's `data.json` site database
+//!
+//! See <https://github.com/sherlock-project/sherlock/blob/master/sherlock_project/resources/data.json>
+
+use crate::config::sites::SiteImport;
+use crate::data::site_info::SiteType;
+use crate::request::spec;
+use crate::sites::declarative::{RequestBody, SiteDefinition};
+use crate::sites::detection::{Condition, Rule, RuleSet, Verdict};
+use crate::utils::error::{Result, SleuthError};
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// A field sherlock allows as either a single value or a list
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+impl<T> OneOrMany<T> {
+    fn into_vec(self) -> Vec<T> {
+        match self {
+            OneOrMany::One(value) => vec![value],
+            OneOrMany::Many(values) => values,
+        }
+    }
+}
+
+/// A single entry of sherlock's `data.json`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SherlockEntry {
+    url: String,
+    #[serde(default)]
+    url_probe: Option<String>,
+    error_type: OneOrMany<String>,
+    #[serde(default)]
+    error_msg: Option<OneOrMany<String>>,
+    #[serde(default)]
+    error_code: Option<OneOrMany<u16>>,
+    #[serde(default)]
+    error_url: Option<String>,
+    #[serde(default)]
+    regex_check: Option<String>,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    #[serde(default, rename = "request_method")]
+    request_method: Option<String>,
+    #[serde(default, rename = "request_payload")]
+    request_payload: Option<serde_json::Value>,
+    #[serde(default, rename = "isNSFW")]
+    is_nsfw: bool,
+    #[serde(default, rename = "username_claimed")]
+    username_claimed: Option<String>,
+    #[serde(default, rename = "username_unclaimed")]
+    username_unclaimed: Option<String>,
+}
+
+/// Parse sherlock's `data.json` content
+///
+/// Entries that cannot be represented (unknown `errorType`, unsupported request
+/// method, malformed fields) are reported in [`SiteImport::skipped`] rather
+/// than failing the whole import.
+pub fn import_sherlock(content: &str) -> Result<SiteImport> {
+    let entries: BTreeMap<String, serde_json::Value> = serde_json::from_str(content)
+        .map_err(|e| SleuthError::Config(format!("Invalid sherlock data.json: {}", e)))?;
+
+    let mut import = SiteImport::default();
+    for (name, value) in entries {
+        // Top-level keys such as "$schema" are not site entries
+        if name.starts_with('$') {
+            continue;
+        }
+
+        let entry: SherlockEntry = match serde_json::from_value(value) {
+            Ok(entry) => entry,
+            Err(e) => {
+                import
+                    .skipped
+                    .push((name, format!("malformed entry: {}", e)));
+                continue;
+            }
+        };
+
+        match convert_entry(&name, entry) {
+            Ok((definition, note)) => {
+                if let Some(note) = note {
+                    import.notes.push((name, note));
+                }
+                import.add(definition);
+            }
+            Err(reason) => import.skipped.push((name, reason)),
+        }
+    }
+
+    Ok(import)
+}
+
+/// Load and import sherlock's `data.json` from disk
+pub fn load_sherlock(path: &Path) -> Result<SiteImport> {
+    import_sherlock(&std::fs::read_to_string(path)?)
+}
+
+/// Convert an entry, with a note on anything that was dropped
+fn convert_entry(
+    name: &str,
+    entry: SherlockEntry,
+) -> std::result::Result<(SiteDefinition, Option<String>), String> {
+    let error_types = entry.error_type.into_vec();
+    let error_type = match error_types.as_slice() {
+        [single] => single.as_str(),
+        _ => return Err("multiple errorType values are not supported".to_string()),
+    };
+
+    let rules = match error_type {
+        "status_code" => {
+            let mut rules = vec![];
+            if let Some(codes) = entry.error_code {
+                rules.push(Rule::new(
+                    "error-code",
+                    Verdict::NotFound,
+                    vec![Condition::Status(codes.into_vec())],
+                ));
+            }
+            rules.push(Rule::new(
+                "success-status",
+                Verdict::Found,
+                vec![Condition::success()],
+            ));
+            rules.push(Rule::new("fallback", Verdict::NotFound, vec![]));
+            rules
+        }
+        "message" => {
+            let messages = entry.error_msg.map(OneOrMany::into_vec).unwrap_or_default();
+            if messages.is_empty() {
+                return Err("errorType 'message' without errorMsg".to_string());
+            }
+            let mut rules: Vec<Rule> = messages
+                .iter()
+                .map(|msg| {
+                    Rule::new(
+                        "error-message",
+                        Verdict::NotFound,
+                        vec![Condition::body_contains(msg)],
+                    )
+                })
+                .collect();
+            rules.push(Rule::new("fallback", Verdict::Found, vec![]));
+            rules
+        }
+        "response_url" => {
+            // sherlock requests these without following redirects: the
+            // missing-user case is a redirect to `errorUrl`
+            let mut rules = vec![];
+            if let Some(error_url) = &entry.error_url {
+                rules.push(Rule::new(
+                    "error-url",
+                    Verdict::NotFound,
+                    vec![Condition::LocationContains(error_url.clone())],
+                ));
+            }
+            rules.push(Rule::new(
+                "success-status",
+                Verdict::Found,
+                vec![Condition::success()],
+            ));
+            rules.push(Rule::new("fallback", Verdict::NotFound, vec![]));
+            rules
+        }
+        other => return Err(format!("unsupported errorType '{}'", other)),
+    };
+
+    // sherlock only needs the body for message checks
+    let default_method = if error_type == "message" {
+        "GET"
+    } else {
+        "HEAD"
+    };
+    let method = match entry.request_method.as_deref() {
+        None => default_method,
+        Some(m) => {
+            spec::supported_method(m).ok_or(format!("unsupported request_method '{}'", m))?
+        }
+    };
+    if entry.request_payload.is_some() && matches!(method, "HEAD" | "GET") {
+        return Err(format!("request_payload with request_method {}", method));
+    }
+
+    // Python regexes that the regex crate cannot compile are dropped
+    // instead of discarding the whole site
+    let (username_pattern, note) = match entry.regex_check {
+        Some(pattern) => match Regex::new(&pattern) {
+            Ok(_) => (Some(pattern), None),
+            Err(_) => (
+                None,
+                Some(format!("unsupported regexCheck '{}' dropped", pattern)),
+            ),
+        },
+        None => (None, None),
+    };
+
+    Ok((
+        SiteDefinition {
+            name: name.to_string(),
+            site_type: if entry.is_nsfw {
+                SiteType::Nsfw
+            } else {
+                SiteType::Other
+            },
+            url_pattern: entry.url,
+            // Detection runs against the probe URL when there is one
+            probe_url: entry.url_probe,
+            method: method.to_string(),
+            headers: entry.headers,
+            cookies: BTreeMap::new(),
+            body: entry.request_payload.map(RequestBody::Json),
+            requires_browser: false,
+            expects_json: false,
+            follow_redirects: error_type != "response_url",
+            timeout: None,
+            rate_limit: None,
+            rules: RuleSet::new(rules),
+            username_pattern,
+            tags: vec![],
+            known_usernames: entry.username_claimed.into_iter().collect(),
+            unclaimed_username: entry.username_unclaimed,
+        },
+        note,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::RequestResponse;
+    use crate::sites::declarative::DeclarativeSite;
+    use crate::sites::Site;
+
+    const SAMPLE: &str = r#"{
+        "$schema": "data.schema.json",
+        "GitHub": {
+            "errorType": "status_code",
+            "regexCheck": "^[a-zA-Z0-9](?:[a-zA-Z0-9]|-(?=[a-zA-Z0-9])){0,38}$",
+            "url": "https://www.github.com/{}",
+            "urlMain": "https://www.github.com/",
+            "username_claimed": "blue"
+        },
+        "Pornhub": {
+            "errorMsg": ["Error Page Not Found", "<title>Page Not Found</title>"],
+            "errorType": "message",
+            "isNSFW": true,
+            "url": "https://pornhub.com/users/{}",
+            "urlMain": "https://pornhub.com/",
+            "username_claimed": "blue"
+        },
+        "Roblox": {
+            "errorCode": [400, 404],
+            "errorType": "status_code",
+            "regexCheck": "^[a-zA-Z0-9_]{3,20}$",
+            "url": "https://www.roblox.com/user.aspx?username={}",
+            "urlProbe": "https://users.roblox.com/v1/users/search?keyword={}",
+            "headers": { "Accept": "application/json" },
+            "username_claimed": "bluewolfekiller",
+            "username_unclaimed": "noonewouldeverusethis7"
+        },
+        "Codecademy": {
+            "errorType": "response_url",
+            "errorUrl": "https://www.codecademy.com/",
+            "url": "https://www.codecademy.com/profiles/{}",
+            "username_claimed": "blue"
+        },
+        "Anilist": {
+            "errorType": "status_code",
+            "request_method": "POST",
+            "request_payload": { "query": "{}" },
+            "url": "https://anilist.co/user/{}/",
+            "urlProbe": "https://graphql.anilist.co/",
+            "username_claimed": "Josh"
+        },
+        "Broken": {
+            "url": "https://broken.example/{}"
+        },
+        "Static": {
+            "errorType": "status_code",
+            "url": "https://static.example/profile"
+        }
+    }"#;
+
+    fn definition<'a>(import: &'a SiteImport, name: &str) -> &'a SiteDefinition {
+        import.definitions.iter().find(|d| d.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_import_sherlock_counts() {
+        let import = import_sherlock(SAMPLE).unwrap();
+        assert_eq!(import.definitions.len(), 5);
+        assert_eq!(import.skipped.len(), 2);
+        assert!(import.skipped.iter().any(|(name, _)| name == "Broken"));
+    }
+
+    #[test]
+    fn test_import_sherlock_skips_invalid_definitions() {
+        // Converts fine, but DeclarativeSite::new() rejects the URL
+        let import = import_sherlock(SAMPLE).unwrap();
+        let (_, reason) = import
+            .skipped
+            .iter()
+            .find(|(name, _)| name == "Static")
+            .unwrap();
+        assert!(reason.contains("placeholder"));
+        assert!(import.definitions.iter().all(|d| d.name != "Static"));
+        assert_eq!(import.into_sites().len(), 5);
+    }
+
+    #[test]
+    fn test_import_sherlock_status_code() {
+        let import = import_sherlock(SAMPLE).unwrap();
+        let site = DeclarativeSite::new(definition(&import, "Roblox").clone()).unwrap();
+        assert_eq!(site.http_method(), "HEAD");
+        assert_eq!(
+            site.build_url("blue"),
+            "https://www.roblox.com/user.aspx?username=blue"
+        );
+        assert_eq!(
+            site.build_probe_url("blue"),
+            "https://users.roblox.com/v1/users/search?keyword=blue"
+        );
+        assert_eq!(site.headers(), vec![("Accept", "application/json")]);
+        assert_eq!(site.parse_response("blue", 200, None), Some(true));
+        assert_eq!(site.parse_response("blue", 400, None), Some(false));
+        assert_eq!(site.parse_response("blue", 503, None), Some(false));
+        let detection = site.detect("blue", &RequestResponse::new(404));
+        assert_eq!(detection.rule.as_deref(), Some("error-code"));
+        assert!(site.validate_username("blue_wolf"));
+        assert!(!site.validate_username("a"));
+        assert_eq!(site.claimed_username(), Some("bluewolfekiller"));
+        assert_eq!(site.unclaimed_username(), Some("noonewouldeverusethis7"));
+    }
+
+    #[test]
+    fn test_import_sherlock_post_payload() {
+        let import = import_sherlock(SAMPLE).unwrap();
+        let site = DeclarativeSite::new(definition(&import, "Anilist").clone()).unwrap();
+        assert_eq!(site.http_method(), "POST");
+        assert_eq!(site.build_url("Josh"), "https://anilist.co/user/Josh/");
+        let spec = site.build_request("Josh");
+        assert_eq!(spec.url, "https://graphql.anilist.co/");
+        assert_eq!(spec.body.as_deref(), Some(r#"{"query":"Josh"}"#));
+        assert_eq!(spec.header("Content-Type"), Some("application/json"));
+
+        let entry = r#"{ "Odd": {
+            "errorType": "status_code",
+            "request_method": "DELETE",
+            "url": "https://odd.example/{}"
+        }, "Odder": {
+            "errorType": "status_code",
+            "request_payload": { "name": "{}" },
+            "url": "https://odder.example/{}"
+        } }"#;
+        let import = import_sherlock(entry).unwrap();
+        assert!(import.definitions.is_empty());
+        assert!(import.skipped[0].1.contains("request_method"));
+        assert!(import.skipped[1].1.contains("request_payload"));
+
+        // Any method the request layer supports is accepted, whatever its case
+        let entry = r#"{ "Patchy": {
+            "errorType": "status_code",
+            "request_method": "patch",
+            "request_payload": { "name": "{}" },
+            "url": "https://patchy.example/{}"
+        } }"#;
+        let import = import_sherlock(entry).unwrap();
+        assert!(import.skipped.is_empty());
+        assert_eq!(import.definitions[0].method, "PATCH");
+    }
+
+    #[test]
+    fn test_import_sherlock_message() {
+        let import = import_sherlock(SAMPLE).unwrap();
+        let def = definition(&import, "Pornhub");
+        assert_eq!(def.site_type, SiteType::Nsfw);
+        assert_eq!(def.method, "GET");
+
+        let site = DeclarativeSite::new(def.clone()).unwrap();
+        assert_eq!(
+            site.parse_response("blue", 200, Some("<title>Page Not Found</title>")),
+            Some(false)
+        );
+        assert_eq!(
+            site.parse_response("blue", 200, Some("<title>blue</title>")),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_import_sherlock_response_url() {
+        let import = import_sherlock(SAMPLE).unwrap();
+        let site = DeclarativeSite::new(definition(&import, "Codecademy").clone()).unwrap();
+        assert_eq!(site.site_type(), SiteType::Other);
+        assert_eq!(site.parse_response("blue", 200, None), Some(true));
+        assert!(!site.follow_redirects());
+        assert_eq!(site.parse_response("blue", 302, None), Some(false));
+
+        let mut redirect = RequestResponse::new(302);
+        redirect.headers = vec![(
+            "Location".to_string(),
+            "https://www.codecademy.com/".to_string(),
+        )];
+        let detection = site.detect("nobody", &redirect);
+        assert_eq!(detection.exists, Some(false));
+        assert_eq!(detection.rule.as_deref(), Some("error-url"));
+
+        assert!(DeclarativeSite::new(definition(&import, "Roblox").clone())
+            .unwrap()
+            .follow_redirects());
+    }
+
+    #[test]
+    fn test_import_sherlock_drops_unsupported_regex() {
+        // The GitHub pattern uses a lookahead, which the regex crate rejects
+        let import = import_sherlock(SAMPLE).unwrap();
+        assert!(definition(&import, "GitHub").username_pattern.is_none());
+        assert!(definition(&import, "Roblox").username_pattern.is_some());
+        assert_eq!(import.notes.len(), 1);
+        assert_eq!(import.notes[0].0, "GitHub");
+        assert!(import.notes[0].1.contains("regexCheck"));
+    }
+
+    #[test]
+    fn test_import_sherlock_into_sites() {
+        let sites = import_sherlock(SAMPLE).unwrap().into_sites();
+        assert_eq!(sites.len(), 5);
+    }
+
+    #[test]
+    fn test_import_sherlock_invalid_json() {
+        assert!(import_sherlock("[1, 2, 3]").is_err());
+        assert!(import_sherlock("not json").is_err());
+    }
+}
@@ -1,12 +1,217 @@
 //! Site definitions/registry
 
+use crate::sites::declarative::{DeclarativeSite, SiteDefinition};
+use crate::sites::Site;
+use crate::utils::error::{Result, SleuthError};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+
+/// A manifest file listing declarative site definitions
+///
+/// JSON:
+/// ```json
+/// { "sites": [ { "name": "Example", "type": "dev", "url_pattern": "https://example.com/{}" } ] }
+/// ```
+///
+/// TOML:
+/// ```toml
+/// [[sites]]
+/// name = "Example"
+/// type = "dev"
+/// url_pattern = "https://example.com/{}"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SiteManifest {
+    /// Site definitions in declaration order
+    #[serde(default)]
+    pub sites: Vec<SiteDefinition>,
+}
+
+impl SiteManifest {
+    /// Parse a manifest from JSON
+    pub fn from_json(content: &str) -> Result<Self> {
+        serde_json::from_str(content)
+            .map_err(|e| SleuthError::Config(format!("Invalid JSON site manifest: {}", e)))
+    }
+
+    /// Parse a manifest from TOML
+    pub fn from_toml(content: &str) -> Result<Self> {
+        toml::from_str(content)
+            .map_err(|e| SleuthError::Config(format!("Invalid TOML site manifest: {}", e)))
+    }
+
+    /// Load a manifest from disk, picking the format from the file extension
+    /// (`.toml` is parsed as TOML, anything else as JSON)
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let is_toml = path
+            .extension()
+            .map(|ext| ext.eq_ignore_ascii_case("toml"))
+            .unwrap_or(false);
+
+        if is_toml {
+            Self::from_toml(&content)
+        } else {
+            Self::from_json(&content)
+        }
+    }
+
+    /// Build site checkers from every definition in the manifest
+    pub fn into_sites(self) -> Result<Vec<Arc<dyn Site>>> {
+        self.sites
+            .into_iter()
+            .map(|definition| {
+                DeclarativeSite::new(definition).map(|site| Arc::new(site) as Arc<dyn Site>)
+            })
+            .collect()
+    }
+}
+
+/// Result of importing a third-party site database
+#[derive(Debug, Clone, Default)]
+pub struct SiteImport {
+    /// Site definitions converted from supported entries, all valid
+    pub definitions: Vec<SiteDefinition>,
+    /// Entries that could not be converted, with the reason
+    pub skipped: Vec<(String, String)>,
+    /// Entries imported with a loss (e.g. a dropped username pattern), with what was lost
+    pub notes: Vec<(String, String)>,
+}
+
+impl SiteImport {
+    /// Keep a converted definition if `DeclarativeSite::new()` accepts it,
+    /// otherwise record it as skipped
+    pub fn add(&mut self, definition: SiteDefinition) {
+        match DeclarativeSite::new(definition.clone()) {
+            Ok(_) => self.definitions.push(definition),
+            Err(e) => self.skipped.push((definition.name, e.to_string())),
+        }
+    }
+
+    /// Build site checkers from the imported definitions
+    ///
+    /// Definitions added with [`SiteImport::add`] are valid; any other that is
+    /// not is left out.
+    pub fn into_sites(self) -> Vec<Arc<dyn Site>> {
+        self.definitions
+            .into_iter()
+            .filter_map(|definition| DeclarativeSite::new(definition).ok())
+            .map(|site| Arc::new(site) as Arc<dyn Site>)
+            .collect()
+    }
+}
+
+/// Load site checkers from a manifest file
+pub fn load_sites(path: &Path) -> Result<Vec<Arc<dyn Site>>> {
+    SiteManifest::load(path)?.into_sites()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::data::site_info::SiteType;
 
     #[test]
     fn test_sites_config_module() {
         // Sites config tests
         assert!(true);
     }
+
+    #[test]
+    fn test_manifest_from_json() {
+        let json = r#"{
+            "sites": [
+                {
+                    "name": "Example",
+                    "type": "social",
+                    "url_pattern": "https://example.com/{}",
+                    "method": "GET",
+                    "headers": { "Accept": "text/html" },
+                    "rules": [
+                        {
+                            "name": "error-page",
+                            "verdict": "not_found",
+                            "when": [ { "status": [200] }, { "body_contains": "No such user" } ]
+                        },
+                        { "name": "ok", "verdict": "found", "when": [ { "status": [200] } ] }
+                    ]
+                }
+            ]
+        }"#;
+        let sites = SiteManifest::from_json(json).unwrap().into_sites().unwrap();
+        assert_eq!(sites.len(), 1);
+        assert_eq!(sites[0].name(), "Example");
+        assert_eq!(sites[0].site_type(), SiteType::Social);
+        assert_eq!(sites[0].http_method(), "GET");
+        assert_eq!(
+            sites[0].parse_response("user", 200, Some("No such user")),
+            Some(false)
+        );
+        assert_eq!(sites[0].parse_response("user", 200, Some("")), Some(true));
+    }
+
+    #[test]
+    fn test_manifest_from_toml() {
+        let toml = r#"
+            [[sites]]
+            name = "Example"
+            type = "dev"
+            url_pattern = "https://example.com/{}"
+
+            [[sites.rules]]
+            name = "gone"
+            verdict = "not_found"
+            when = [ { status = [404, 410] } ]
+        "#;
+        let sites = SiteManifest::from_toml(toml).unwrap().into_sites().unwrap();
+        assert_eq!(sites.len(), 1);
+        assert_eq!(sites[0].http_method(), "HEAD");
+        assert_eq!(sites[0].parse_response("user", 410, None), Some(false));
+    }
+
+    #[test]
+    fn test_manifest_defaults_to_other_type() {
+        let json =
+            r#"{ "sites": [ { "name": "Misc", "url_pattern": "https://misc.example/{}" } ] }"#;
+        let manifest = SiteManifest::from_json(json).unwrap();
+        assert_eq!(manifest.sites[0].site_type, SiteType::Other);
+    }
+
+    #[test]
+    fn test_manifest_invalid_content() {
+        assert!(SiteManifest::from_json("not json").is_err());
+        assert!(SiteManifest::from_toml("sites = 3").is_err());
+
+        let json =
+            r#"{ "sites": [ { "name": "Broken", "url_pattern": "https://broken.example/" } ] }"#;
+        let err = SiteManifest::from_json(json)
+            .unwrap()
+            .into_sites()
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("Broken"));
+    }
+
+    #[test]
+    fn test_load_sites_from_file() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("sleuth-manifest-{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            "[[sites]]\nname = \"FromFile\"\nurl_pattern = \"https://file.example/{}\"\n",
+        )
+        .unwrap();
+
+        let sites = load_sites(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(sites.len(), 1);
+        assert_eq!(sites[0].name(), "FromFile");
+    }
+
+    #[test]
+    fn test_load_sites_missing_file() {
+        let result = load_sites(Path::new("/nonexistent/sleuth/sites.json"));
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,362 @@
+//! Importer for the WhatsMyName site list (`wmn-data.json`)
+//!
+//! See <https://github.com/WebBreacher/WhatsMyName>
+
+use crate::config::sites::SiteImport;
+use crate::data::site_info::SiteType;
+use crate::sites::declarative::{RequestBody, SiteDefinition};
+use crate::sites::detection::{Condition, Rule, RuleSet, Verdict};
+use crate::utils::error::{Result, SleuthError};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Top-level structure of `wmn-data.json`
+#[derive(Debug, Clone, Deserialize)]
+struct WmnData {
+    sites: Vec<serde_json::Value>,
+}
+
+/// A single entry of the WhatsMyName `sites` list
+#[derive(Debug, Clone, Deserialize)]
+struct WmnEntry {
+    name: String,
+    uri_check: String,
+    #[serde(default)]
+    uri_pretty: Option<String>,
+    #[serde(default)]
+    post_body: Option<String>,
+    e_code: u16,
+    #[serde(default)]
+    e_string: String,
+    m_code: u16,
+    #[serde(default)]
+    m_string: String,
+    #[serde(default)]
+    known: Vec<String>,
+    #[serde(default)]
+    cat: String,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+}
+
+/// Parse WhatsMyName's `wmn-data.json` content
+///
+/// Entries that cannot be represented (POST checks without a profile URL,
+/// malformed fields) are reported in [`SiteImport::skipped`] rather than
+/// failing the whole import.
+pub fn import_wmn(content: &str) -> Result<SiteImport> {
+    let data: WmnData = serde_json::from_str(content)
+        .map_err(|e| SleuthError::Config(format!("Invalid wmn-data.json: {}", e)))?;
+
+    let mut import = SiteImport::default();
+    for value in data.sites {
+        let name = value
+            .get("name")
+            .and_then(|n| n.as_str())
+            .unwrap_or("<unnamed>")
+            .to_string();
+
+        let entry: WmnEntry = match serde_json::from_value(value) {
+            Ok(entry) => entry,
+            Err(e) => {
+                import
+                    .skipped
+                    .push((name, format!("malformed entry: {}", e)));
+                continue;
+            }
+        };
+
+        // The profile URL of a POST check is its uri_pretty
+        if entry.post_body.is_some()
+            && entry.uri_pretty.is_none()
+            && !entry.uri_check.contains("{account}")
+        {
+            import.skipped.push((
+                name,
+                "POST check without a profile URL (uri_pretty)".to_string(),
+            ));
+            continue;
+        }
+
+        import.add(convert_entry(entry));
+    }
+
+    Ok(import)
+}
+
+/// Load and import WhatsMyName's `wmn-data.json` from disk
+pub fn load_wmn(path: &Path) -> Result<SiteImport> {
+    import_wmn(&std::fs::read_to_string(path)?)
+}
+
+/// Map a WhatsMyName category onto a `SiteType`
+///
+/// Returns `None` for categories without a matching type; those are kept as tags.
+pub fn map_category(cat: &str) -> Option<SiteType> {
+    match cat.to_lowercase().as_str() {
+        "coding" | "tech" => Some(SiteType::Dev),
+        "social" => Some(SiteType::Social),
+        "business" => Some(SiteType::Professional),
+        "gaming" => Some(SiteType::Gaming),
+        "xx nsfw xx" => Some(SiteType::Nsfw),
+        _ => None,
+    }
+}
+
+fn convert_entry(entry: WmnEntry) -> SiteDefinition {
+    let (site_type, tags) = match map_category(&entry.cat) {
+        Some(site_type) => (site_type, vec![]),
+        None if entry.cat.is_empty() => (SiteType::Other, vec![]),
+        None => (SiteType::Other, vec![entry.cat.to_lowercase()]),
+    };
+
+    // WhatsMyName requires both the status code and the marker string to match;
+    // anything else is left uncertain
+    let rules = RuleSet::new(vec![
+        rule("e_string", Verdict::Found, entry.e_code, &entry.e_string),
+        rule("m_string", Verdict::NotFound, entry.m_code, &entry.m_string),
+    ]);
+
+    SiteDefinition {
+        name: entry.name,
+        site_type,
+        // uri_check is what gets requested; uri_pretty (if any) is the profile page
+        url_pattern: entry
+            .uri_pretty
+            .as_deref()
+            .unwrap_or(&entry.uri_check)
+            .replace("{account}", "{}"),
+        probe_url: entry
+            .uri_pretty
+            .as_ref()
+            .map(|_| entry.uri_check.replace("{account}", "{}")),
+        method: if entry.post_body.is_some() {
+            "POST"
+        } else {
+            "GET"
+        }
+        .to_string(),
+        headers: entry.headers,
+        cookies: BTreeMap::new(),
+        body: entry
+            .post_body
+            .map(|body| RequestBody::Text(body.replace("{account}", "{}"))),
+        requires_browser: false,
+        expects_json: false,
+        follow_redirects: true,
+        timeout: None,
+        rate_limit: None,
+        rules,
+        username_pattern: None,
+        tags,
+        known_usernames: entry.known,
+        unclaimed_username: None,
+    }
+}
+
+fn rule(name: &str, verdict: Verdict, status: u16, marker: &str) -> Rule {
+    let mut when = vec![Condition::status(status)];
+    if !marker.is_empty() {
+        when.push(Condition::body_contains(marker));
+    }
+    Rule::new(name, verdict, when)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sites::declarative::DeclarativeSite;
+    use crate::sites::Site;
+
+    const SAMPLE: &str = r#"{
+        "license": ["CC BY-SA 4.0"],
+        "categories": ["coding", "gaming", "hobby", "social", "xx NSFW xx"],
+        "sites": [
+            {
+                "name": "GitLab",
+                "uri_check": "https://gitlab.com/api/v4/users?username={account}",
+                "uri_pretty": "https://gitlab.com/{account}",
+                "e_code": 200,
+                "e_string": "\"username\":",
+                "m_code": 200,
+                "m_string": "[]",
+                "known": ["skennedy", "KennBro"],
+                "cat": "coding"
+            },
+            {
+                "name": "Chess.com",
+                "uri_check": "https://www.chess.com/member/{account}",
+                "e_code": 200,
+                "e_string": "member-header",
+                "m_code": 404,
+                "m_string": "",
+                "known": ["hikaru"],
+                "cat": "gaming",
+                "headers": { "Accept-Language": "en-US" }
+            },
+            {
+                "name": "Knitting Circle",
+                "uri_check": "https://knit.example/u/{account}",
+                "e_code": 200,
+                "e_string": "profile",
+                "m_code": 404,
+                "m_string": "not found",
+                "known": ["yarn"],
+                "cat": "hobby"
+            },
+            {
+                "name": "Poster",
+                "uri_check": "https://poster.example/api/check",
+                "post_body": "{\"username\":\"{account}\"}",
+                "e_code": 200,
+                "e_string": "taken",
+                "m_code": 200,
+                "m_string": "available",
+                "known": ["someone"],
+                "cat": "social"
+            },
+            {
+                "name": "Handles",
+                "uri_check": "https://handles.example/api/available",
+                "uri_pretty": "https://handles.example/@{account}",
+                "post_body": "handle={account}",
+                "headers": { "Content-Type": "application/x-www-form-urlencoded" },
+                "e_code": 200,
+                "e_string": "taken",
+                "m_code": 200,
+                "m_string": "available",
+                "known": ["someone"],
+                "cat": "social"
+            },
+            {
+                "name": "Incomplete",
+                "uri_check": "https://incomplete.example/{account}"
+            }
+        ]
+    }"#;
+
+    fn definition<'a>(import: &'a SiteImport, name: &str) -> &'a SiteDefinition {
+        import.definitions.iter().find(|d| d.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_import_wmn_counts() {
+        let import = import_wmn(SAMPLE).unwrap();
+        assert_eq!(import.definitions.len(), 4);
+        assert_eq!(import.skipped.len(), 2);
+        assert!(import
+            .skipped
+            .iter()
+            .any(|(name, reason)| name == "Poster" && reason.contains("POST")));
+        assert!(import.skipped.iter().any(|(name, _)| name == "Incomplete"));
+    }
+
+    #[test]
+    fn test_import_wmn_skips_invalid_definitions() {
+        // The uri_pretty passes the POST check but has no {account}, so the
+        // definition has no username in its URL
+        let json = r#"{
+            "sites": [
+                {
+                    "name": "Pretty",
+                    "uri_check": "https://pretty.example/api/check",
+                    "uri_pretty": "https://pretty.example/profile",
+                    "post_body": "{\"username\":\"{account}\"}",
+                    "e_code": 200,
+                    "e_string": "taken",
+                    "m_code": 200,
+                    "m_string": "available",
+                    "known": ["someone"],
+                    "cat": "social"
+                }
+            ]
+        }"#;
+        let import = import_wmn(json).unwrap();
+        assert!(import.definitions.is_empty());
+        assert_eq!(import.skipped.len(), 1);
+        let (name, reason) = &import.skipped[0];
+        assert_eq!(name, "Pretty");
+        assert!(reason.contains("placeholder"));
+    }
+
+    #[test]
+    fn test_import_wmn_detection() {
+        let import = import_wmn(SAMPLE).unwrap();
+        let site = DeclarativeSite::new(definition(&import, "GitLab").clone()).unwrap();
+        assert_eq!(site.site_type(), SiteType::Dev);
+        assert_eq!(site.http_method(), "GET");
+        assert_eq!(site.build_url("skennedy"), "https://gitlab.com/skennedy");
+        assert_eq!(
+            site.build_probe_url("skennedy"),
+            "https://gitlab.com/api/v4/users?username=skennedy"
+        );
+        assert_eq!(
+            site.parse_response("skennedy", 200, Some(r#"[{"username":"skennedy"}]"#)),
+            Some(true)
+        );
+        assert_eq!(site.parse_response("nobody", 200, Some("[]")), Some(false));
+        // Neither marker matches: uncertain
+        assert_eq!(site.parse_response("nobody", 500, Some("oops")), None);
+    }
+
+    #[test]
+    fn test_import_wmn_post_check() {
+        let import = import_wmn(SAMPLE).unwrap();
+        let site = DeclarativeSite::new(definition(&import, "Handles").clone()).unwrap();
+        assert_eq!(site.http_method(), "POST");
+        assert_eq!(
+            site.build_url("someone"),
+            "https://handles.example/@someone"
+        );
+        let spec = site.build_request("someone");
+        assert_eq!(spec.url, "https://handles.example/api/available");
+        assert_eq!(spec.body.as_deref(), Some("handle=someone"));
+        assert_eq!(
+            spec.header("Content-Type"),
+            Some("application/x-www-form-urlencoded")
+        );
+    }
+
+    #[test]
+    fn test_import_wmn_status_only_marker() {
+        let import = import_wmn(SAMPLE).unwrap();
+        let site = DeclarativeSite::new(definition(&import, "Chess.com").clone()).unwrap();
+        assert_eq!(site.site_type(), SiteType::Gaming);
+        assert_eq!(site.headers(), vec![("Accept-Language", "en-US")]);
+        assert_eq!(site.build_probe_url("hikaru"), site.build_url("hikaru"));
+        assert_eq!(site.parse_response("nobody", 404, Some("")), Some(false));
+    }
+
+    #[test]
+    fn test_import_wmn_category_tags() {
+        let import = import_wmn(SAMPLE).unwrap();
+        let knitting = definition(&import, "Knitting Circle");
+        assert_eq!(knitting.site_type, SiteType::Other);
+        assert_eq!(knitting.tags, vec!["hobby"]);
+        assert!(definition(&import, "GitLab").tags.is_empty());
+    }
+
+    #[test]
+    fn test_import_wmn_known_usernames() {
+        let import = import_wmn(SAMPLE).unwrap();
+        assert_eq!(
+            definition(&import, "GitLab").known_usernames,
+            vec!["skennedy", "KennBro"]
+        );
+    }
+
+    #[test]
+    fn test_map_category() {
+        assert_eq!(map_category("coding"), Some(SiteType::Dev));
+        assert_eq!(map_category("xx NSFW xx"), Some(SiteType::Nsfw));
+        assert_eq!(map_category("business"), Some(SiteType::Professional));
+        assert_eq!(map_category("music"), None);
+    }
+
+    #[test]
+    fn test_import_wmn_invalid_json() {
+        assert!(import_wmn("{}").is_err());
+        assert!(import_wmn("not json").is_err());
+    }
+}
@@ -0,0 +1,274 @@
+//! Soft-404 calibration against a random control username
+//!
+//! Many sites answer `200` for users that do not exist. Calibration requests a
+//! random, certainly-unclaimed username as well, and a hit only counts as found
+//! when its response differs meaningfully from the control's: status, redirect
+//! target, page title, page structure or body length.
+
+use crate::request::RequestResponse;
+use crate::sites::Site;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use regex::{Regex, RegexBuilder};
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+/// Pages whose tag structure is at least this similar (Jaccard, 0-1) look alike
+pub const STRUCTURE_THRESHOLD: f64 = 0.9;
+
+/// Bodies whose lengths differ by at most this fraction look alike
+pub const LENGTH_TOLERANCE: f64 = 0.1;
+
+/// Confidence of a hit overturned because it looks like the control
+pub const CALIBRATED_CONFIDENCE: u8 = 60;
+
+/// Consecutive tags hashed together when comparing page structure
+const SHINGLE_SIZE: usize = 4;
+
+/// A random username the site allows, or None if no candidate fits its rules
+///
+/// Candidates are 12 then 8 random characters, first mixed lowercase letters
+/// and digits (starting with a letter), then letters only.
+pub fn control_username(site: &dyn Site) -> Option<String> {
+    let mut rng = rand::thread_rng();
+    let mut random = |len: usize, digits: bool| -> String {
+        let first = rng.gen_range(b'a'..=b'z') as char;
+        let rest: String = (1..len)
+            .map(|_| loop {
+                let c = (rng.sample(Alphanumeric) as char).to_ascii_lowercase();
+                if digits || c.is_ascii_alphabetic() {
+                    break c;
+                }
+            })
+            .collect();
+        format!("{}{}", first, rest)
+    };
+    let candidates = [
+        random(12, true),
+        random(8, true),
+        random(12, false),
+        random(8, false),
+    ];
+    candidates
+        .into_iter()
+        .find(|candidate| site.validate_username(candidate))
+}
+
+/// How the response for `username` meaningfully differs from the control's,
+/// or None if they look alike
+///
+/// Both usernames are masked before comparing, so pages that echo the
+/// requested name are compared on everything else.
+pub fn difference(
+    username: &str,
+    response: &RequestResponse,
+    control_username: &str,
+    control: &RequestResponse,
+) -> Option<String> {
+    if response.status_code != control.status_code {
+        return Some(format!(
+            "HTTP {} vs {}",
+            response.status_code, control.status_code
+        ));
+    }
+
+    let target = |response: &RequestResponse, name: &str| {
+        response
+            .location()
+            .or(response.final_url.as_deref())
+            .map(|url| mask(url, name))
+    };
+    let (hit_target, control_target) = (
+        target(response, username),
+        target(control, control_username),
+    );
+    if hit_target != control_target {
+        return Some(format!(
+            "redirected to {} vs {}",
+            hit_target.as_deref().unwrap_or("nothing"),
+            control_target.as_deref().unwrap_or("nothing")
+        ));
+    }
+
+    let (Some(body), Some(control_body)) = (&response.body, &control.body) else {
+        return None;
+    };
+    let (body, control_body) = (mask(body, username), mask(control_body, control_username));
+
+    let (title, control_title) = (title(&body), title(&control_body));
+    if title != control_title {
+        return Some(format!(
+            "title '{}' vs '{}'",
+            title.unwrap_or_default(),
+            control_title.unwrap_or_default()
+        ));
+    }
+
+    let similarity = structure_similarity(&body, &control_body);
+    if similarity < STRUCTURE_THRESHOLD {
+        return Some(format!("page structure {:.0}% similar", similarity * 100.0));
+    }
+
+    let (length, control_length) = (body.len(), control_body.len());
+    let longest = length.max(control_length).max(1) as f64;
+    if length.abs_diff(control_length) as f64 / longest > LENGTH_TOLERANCE {
+        return Some(format!("body {} vs {} bytes", length, control_length));
+    }
+
+    None
+}
+
+/// Replace every case-insensitive occurrence of `name` with `{}`
+fn mask(text: &str, name: &str) -> String {
+    if name.is_empty() {
+        return text.to_string();
+    }
+    match RegexBuilder::new(&regex::escape(name))
+        .case_insensitive(true)
+        .build()
+    {
+        Ok(pattern) => pattern.replace_all(text, "{}").into_owned(),
+        Err(_) => text.to_string(),
+    }
+}
+
+/// Text of the page's `<title>`, whitespace collapsed
+fn title(body: &str) -> Option<String> {
+    static TITLE: OnceLock<Regex> = OnceLock::new();
+    let title =
+        TITLE.get_or_init(|| Regex::new(r"(?is)<title[^>]*>(.*?)</title>").expect("valid pattern"));
+    title
+        .captures(body)
+        .map(|captures| captures[1].split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+/// Jaccard similarity of the two pages' tag sequences, in shingles of
+/// [`SHINGLE_SIZE`] tags (1.0 for two pages without tags)
+fn structure_similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (shingles(a), shingles(b));
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 1.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+fn shingles(body: &str) -> HashSet<Vec<String>> {
+    static TAG: OnceLock<Regex> = OnceLock::new();
+    let tag = TAG.get_or_init(|| Regex::new(r"<(/?[a-zA-Z][a-zA-Z0-9-]*)").expect("valid pattern"));
+    let tags: Vec<String> = tag
+        .captures_iter(body)
+        .map(|captures| captures[1].to_lowercase())
+        .collect();
+    if tags.len() < SHINGLE_SIZE {
+        return [tags].into_iter().filter(|t| !t.is_empty()).collect();
+    }
+    tags.windows(SHINGLE_SIZE).map(<[String]>::to_vec).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::site_info::SiteType;
+
+    struct StrictSite;
+
+    impl Site for StrictSite {
+        fn name(&self) -> &str {
+            "Strict"
+        }
+        fn url_pattern(&self) -> &str {
+            "https://strict.example/{}"
+        }
+        fn site_type(&self) -> SiteType {
+            SiteType::Other
+        }
+        fn username_pattern(&self) -> Option<&Regex> {
+            static PATTERN: OnceLock<Regex> = OnceLock::new();
+            Some(PATTERN.get_or_init(|| Regex::new("^[a-z]{3,10}$").unwrap()))
+        }
+    }
+
+    fn page(title: &str, main: &str) -> RequestResponse {
+        RequestResponse::with_body(
+            200,
+            format!(
+                "<html><head><title>{}</title></head><body><nav><a>Home</a></nav>{}<footer><p>(c)</p></footer></body></html>",
+                title, main
+            ),
+        )
+    }
+
+    #[test]
+    fn test_control_username_fits_site_rules() {
+        let control = control_username(&StrictSite).unwrap();
+        assert_eq!(control.len(), 8);
+        assert!(control.chars().all(|c| c.is_ascii_lowercase()));
+        assert_ne!(control_username(&StrictSite), Some(control));
+    }
+
+    #[test]
+    fn test_soft_404_looks_alike() {
+        // Same "not found" page, echoing the requested name
+        let hit = page("Blue - not found", "<div><p>No user blue</p></div>");
+        let control = page("qzxkwv - not found", "<div><p>No user qzxkwv</p></div>");
+        assert_eq!(difference("blue", &hit, "qzxkwv", &control), None);
+    }
+
+    #[test]
+    fn test_real_profile_differs() {
+        let control = page("qzxkwv - not found", "<div><p>No user qzxkwv</p></div>");
+
+        let retitled = page("Blue (@blue)", "<div><p>No user blue</p></div>");
+        assert_eq!(
+            difference("blue", &retitled, "qzxkwv", &control).as_deref(),
+            Some("title '{} (@{})' vs '{} - not found'")
+        );
+
+        let profile = page(
+            "{} - not found",
+            "<main><section><h1>Blue</h1><ul><li>a</li><li>b</li><li>c</li></ul></section><article><p>Posts</p></article></main>",
+        );
+        let diff = difference("blue", &profile, "qzxkwv", &control).unwrap();
+        assert!(diff.starts_with("page structure"), "{}", diff);
+
+        let longer = page(
+            "blue - not found",
+            &format!("<div><p>No user blue{}</p></div>", " bio".repeat(100)),
+        );
+        let diff = difference("blue", &longer, "qzxkwv", &control).unwrap();
+        assert!(diff.starts_with("body"), "{}", diff);
+    }
+
+    #[test]
+    fn test_status_and_redirects_differ() {
+        let control = RequestResponse::new(404);
+        assert_eq!(
+            difference("blue", &RequestResponse::new(200), "qzx", &control).as_deref(),
+            Some("HTTP 200 vs 404")
+        );
+
+        let redirect = |to: &str| RequestResponse {
+            final_url: Some(to.to_string()),
+            ..RequestResponse::new(200)
+        };
+        let hit = redirect("https://example.com/users/blue");
+        assert_eq!(
+            difference(
+                "blue",
+                &hit,
+                "qzx",
+                &redirect("https://example.com/users/QZX")
+            ),
+            None
+        );
+        assert!(difference("blue", &hit, "qzx", &redirect("https://example.com/")).is_some());
+    }
+
+    #[test]
+    fn test_structure_similarity() {
+        assert_eq!(structure_similarity("no tags", "at all"), 1.0);
+        assert_eq!(structure_similarity("<a><b>", "<a><b>"), 1.0);
+        assert_eq!(structure_similarity("<a><b>", "<p><q><r><s>"), 0.0);
+    }
+}
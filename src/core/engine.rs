@@ -1,24 +1,68 @@
 //! Main search engine orchestrator
 
-use crate::core::result::SearchResult;
-use crate::core::scanner::scan_username;
+use crate::core::limits::ConnectionLimits;
+use crate::core::options::ScanOptions;
+use crate::core::pivot::{pivot_candidates, Discovery, PivotOptions, PivotReport, PivotSource};
+use crate::core::result::{SearchResult, UsernameResults};
+use crate::core::scanner::{
+    default_request, scan_username_stream, scan_username_with_options, ScanStream,
+};
+use crate::core::variants::{generate_variants, VariantOptions, VariantReport};
 use crate::data::site_info::SiteType;
 use crate::request::Request;
 use crate::sites::{Site, SiteRegistry};
 use crate::utils::error::Result;
-use std::sync::Arc;
+use futures::stream::{self, Stream, StreamExt};
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, OnceLock};
+
+/// Usernames of a batch searched at the same time
+pub const BATCH_CONCURRENCY: usize = 4;
 
 pub struct Engine {
     registry: SiteRegistry,
+    options: ScanOptions,
+    /// HTTP client shared by searches that are given no request
+    http: OnceLock<Arc<dyn Request>>,
 }
 
 impl Engine {
     pub fn new() -> Self {
+        Self::with_registry(SiteRegistry::new())
+    }
+
+    /// Create an engine that searches the given registry
+    pub fn with_registry(registry: SiteRegistry) -> Self {
         Self {
-            registry: SiteRegistry::new(),
+            registry,
+            options: ScanOptions::default(),
+            http: OnceLock::new(),
         }
     }
 
+    /// Set the options used for every search (`verify` is still given per search)
+    pub fn with_scan_options(mut self, options: ScanOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Cap concurrent requests at `max_total` overall and `max_per_host` per host,
+    /// shared by every search of this engine
+    pub fn with_concurrency(mut self, max_total: usize, max_per_host: usize) -> Self {
+        self.options.limits = ConnectionLimits::new(max_total, max_per_host);
+        self
+    }
+
+    /// Get the options used for every search
+    pub fn scan_options(&self) -> &ScanOptions {
+        &self.options
+    }
+
+    /// Get the registry this engine searches
+    pub fn registry(&self) -> &SiteRegistry {
+        &self.registry
+    }
+
     pub async fn search(
         &self,
         username: &str,
@@ -35,7 +79,160 @@ impl Engine {
         }
 
         // Scan username across all filtered sites
-        scan_username(username, sites, request, verify).await
+        let options = ScanOptions {
+            verify,
+            ..self.options.clone()
+        };
+        let request = self.request(request)?;
+        scan_username_with_options(username, sites, Some(request), &options).await
+    }
+
+    /// Like [`Engine::search`], but yield results as each site completes
+    ///
+    /// Verification updates arrive as follow-up results with `verified = true`.
+    pub fn search_stream(
+        &self,
+        username: &str,
+        site_types: &[SiteType],
+        site_names: &[String],
+        request: Option<Arc<dyn Request>>,
+        verify: bool,
+    ) -> Result<ScanStream> {
+        let sites: Vec<Arc<dyn Site>> = self.registry.filter(site_types, site_names);
+        let options = ScanOptions {
+            verify,
+            ..self.options.clone()
+        };
+        scan_username_stream(username, sites, Some(self.request(request)?), &options)
+    }
+
+    /// Search several usernames, yielding each one's results in input order
+    ///
+    /// Every search shares the engine's limits, rate limiter and HTTP client.
+    /// Up to [`BATCH_CONCURRENCY`] usernames are searched at once.
+    pub fn search_batch<'a>(
+        &'a self,
+        usernames: &'a [String],
+        site_types: &'a [SiteType],
+        site_names: &'a [String],
+        request: Option<Arc<dyn Request>>,
+        verify: bool,
+    ) -> Result<impl Stream<Item = Result<UsernameResults>> + 'a> {
+        let request = self.request(request)?;
+        Ok(stream::iter(usernames)
+            .map(move |username| {
+                let request = request.clone();
+                async move {
+                    let results = self
+                        .search(username, site_types, site_names, Some(request), verify)
+                        .await?;
+                    Ok(UsernameResults {
+                        username: username.clone(),
+                        results,
+                    })
+                }
+            })
+            .buffered(BATCH_CONCURRENCY))
+    }
+
+    /// `request`, or the engine's HTTP client, created on first use
+    ///
+    /// The client allows the longest timeout of any registered site and each
+    /// check enforces its own, so every search can share its connection pool.
+    fn request(&self, request: Option<Arc<dyn Request>>) -> Result<Arc<dyn Request>> {
+        if let Some(request) = request.or_else(|| self.http.get().cloned()) {
+            return Ok(request);
+        }
+        let request = default_request(self.registry.all(), &self.options)?;
+        Ok(Arc::clone(self.http.get_or_init(|| request)))
+    }
+
+    /// Search the variants of a seed username (see [`generate_variants`])
+    ///
+    /// Variants are searched as a batch, and the report matches each found
+    /// result back to the variant and site it came from.
+    pub async fn search_variants(
+        &self,
+        seed: &str,
+        variant_options: &VariantOptions,
+        site_types: &[SiteType],
+        site_names: &[String],
+        request: Option<Arc<dyn Request>>,
+        verify: bool,
+    ) -> Result<VariantReport> {
+        let variants = generate_variants(seed, variant_options);
+        let usernames: Vec<String> = variants.iter().map(|v| v.username.clone()).collect();
+
+        let mut results = Vec::new();
+        let mut batch = self.search_batch(&usernames, site_types, site_names, request, verify)?;
+        while let Some(group) = batch.next().await {
+            results.extend(group?.results);
+        }
+        Ok(VariantReport::new(seed, variants, results))
+    }
+
+    /// Like [`Engine::search`], then pivot on what the found profiles point to
+    ///
+    /// Usernames shown on found profiles, and profile or bio links that match a
+    /// registered site, are scanned in turn (breadth first) across the same
+    /// sites, up to `pivot.max_depth` hops and `pivot.max_scans` extra usernames.
+    pub async fn search_recursive(
+        &self,
+        username: &str,
+        site_types: &[SiteType],
+        site_names: &[String],
+        request: Option<Arc<dyn Request>>,
+        verify: bool,
+        pivot: &PivotOptions,
+    ) -> Result<PivotReport> {
+        let request = self.request(request)?;
+        let mut report = PivotReport::default();
+        let mut seen = HashSet::from([username.to_lowercase()]);
+        let mut queue = VecDeque::from([Discovery {
+            username: username.to_string(),
+            depth: 0,
+            from: None,
+        }]);
+
+        while let Some(discovery) = queue.pop_front() {
+            let results = self
+                .search(
+                    &discovery.username,
+                    site_types,
+                    site_names,
+                    Some(request.clone()),
+                    verify,
+                )
+                .await?;
+
+            if discovery.depth < pivot.max_depth {
+                for result in &results {
+                    for candidate in pivot_candidates(result, self.registry.all()) {
+                        // The username being scanned is in neither list, so this counts the extra ones
+                        let scans = report.discoveries.len() + queue.len();
+                        if scans >= pivot.max_scans
+                            || !seen.insert(candidate.username.to_lowercase())
+                        {
+                            continue;
+                        }
+                        queue.push_back(Discovery {
+                            username: candidate.username,
+                            depth: discovery.depth + 1,
+                            from: Some(PivotSource {
+                                site: result.site.clone(),
+                                username: discovery.username.clone(),
+                                via: candidate.via,
+                            }),
+                        });
+                    }
+                }
+            }
+
+            report.results.extend(results);
+            report.discoveries.push(discovery);
+        }
+
+        Ok(report)
     }
 }
 
@@ -63,6 +260,189 @@ mod tests {
         assert!(true);
     }
 
+    #[tokio::test]
+    async fn test_engine_with_registry() {
+        let engine = Engine::with_registry(SiteRegistry::from_sites(vec![]));
+        assert_eq!(engine.registry().count(), 0);
+        assert_eq!(engine.scan_options().retry.max_retries, 0);
+
+        let engine = engine.with_concurrency(8, 2);
+        assert_eq!(engine.scan_options().limits.max_total(), 8);
+        assert_eq!(engine.scan_options().limits.max_per_host(), 2);
+        let results = engine.search("testuser", &[], &[], None, false).await;
+        assert!(results.unwrap().is_empty());
+    }
+
+    struct ProfileSite;
+
+    impl Site for ProfileSite {
+        fn name(&self) -> &str {
+            "Profiles"
+        }
+        fn url_pattern(&self) -> &str {
+            "https://profiles.example/{}"
+        }
+        fn site_type(&self) -> SiteType {
+            SiteType::Other
+        }
+        fn http_method(&self) -> &'static str {
+            "GET"
+        }
+    }
+
+    fn profile_page(links: &[&str]) -> crate::request::RequestResponse {
+        let links: String = links
+            .iter()
+            .map(|link| format!(r#"<link rel="me" href="{}">"#, link))
+            .collect();
+        crate::request::RequestResponse::with_body(200, links)
+    }
+
+    fn pivot_engine() -> (Engine, Arc<dyn Request>) {
+        use crate::request::{FixtureRequest, FixtureSet};
+
+        let mut fixtures = FixtureSet::default();
+        fixtures.insert(
+            "GET",
+            "https://profiles.example/blue",
+            profile_page(&[
+                "https://profiles.example/navy",
+                "https://profiles.example/sky",
+            ]),
+        );
+        fixtures.insert(
+            "GET",
+            "https://profiles.example/navy",
+            profile_page(&[
+                "https://profiles.example/blue",
+                "https://profiles.example/ink",
+            ]),
+        );
+        fixtures.insert(
+            "GET",
+            "https://profiles.example/sky",
+            crate::request::RequestResponse::with_body(404, String::new()),
+        );
+        fixtures.insert("GET", "https://profiles.example/ink", profile_page(&[]));
+        let engine = Engine::with_registry(SiteRegistry::from_sites(vec![Arc::new(ProfileSite)]));
+        (engine, Arc::new(FixtureRequest::new(fixtures)))
+    }
+
+    #[tokio::test]
+    async fn test_engine_search_recursive() {
+        let (engine, request) = pivot_engine();
+        let pivot = PivotOptions {
+            max_depth: 2,
+            max_scans: 10,
+        };
+        let report = engine
+            .search_recursive("blue", &[], &[], Some(request), false, &pivot)
+            .await
+            .unwrap();
+
+        let scanned: Vec<_> = report
+            .discoveries
+            .iter()
+            .map(|d| (d.username.as_str(), d.depth))
+            .collect();
+        assert_eq!(
+            scanned,
+            vec![("blue", 0), ("navy", 1), ("sky", 1), ("ink", 2)]
+        );
+        let ink = &report.discoveries[3];
+        let from = ink.from.as_ref().unwrap();
+        assert_eq!(from.site, "Profiles");
+        assert_eq!(from.username, "navy");
+        assert_eq!(from.via, "https://profiles.example/ink");
+        assert_eq!(report.results.len(), 4);
+        assert!(!report.results_for("sky").next().unwrap().exists());
+    }
+
+    #[tokio::test]
+    async fn test_engine_search_recursive_limits() {
+        let (engine, request) = pivot_engine();
+        let shallow = PivotOptions {
+            max_depth: 1,
+            max_scans: 10,
+        };
+        let report = engine
+            .search_recursive("blue", &[], &[], Some(request.clone()), false, &shallow)
+            .await
+            .unwrap();
+        assert_eq!(report.discoveries.len(), 3);
+
+        let budget = PivotOptions {
+            max_depth: 2,
+            max_scans: 1,
+        };
+        let report = engine
+            .search_recursive("blue", &[], &[], Some(request.clone()), false, &budget)
+            .await
+            .unwrap();
+        assert_eq!(report.discoveries.len(), 2);
+
+        let off = PivotOptions::default();
+        let report = engine
+            .search_recursive("blue", &[], &[], Some(request), false, &off)
+            .await
+            .unwrap();
+        assert_eq!(report.discoveries.len(), 1);
+        assert_eq!(report.results.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_engine_search_batch() {
+        let (engine, request) = pivot_engine();
+        let usernames: Vec<String> = ["ink", "sky", "blue", "ghost"]
+            .iter()
+            .map(|u| u.to_string())
+            .collect();
+        let batch: Vec<UsernameResults> = engine
+            .search_batch(&usernames, &[], &[], Some(request), false)
+            .unwrap()
+            .map(|results| results.unwrap())
+            .collect()
+            .await;
+
+        let order: Vec<_> = batch.iter().map(|b| b.username.as_str()).collect();
+        assert_eq!(order, vec!["ink", "sky", "blue", "ghost"]);
+        assert!(batch[0].results[0].exists());
+        assert!(!batch[1].results[0].exists());
+        assert!(batch.iter().all(|b| b.results.len() == 1));
+        assert!(batch
+            .iter()
+            .all(|b| b.results.iter().all(|r| r.username == b.username)));
+    }
+
+    #[tokio::test]
+    async fn test_engine_search_variants() {
+        let (engine, request) = pivot_engine();
+        let options = VariantOptions {
+            full_name: Some("Navy Ink".to_string()),
+            suffixes: false,
+            leetspeak: false,
+            ..VariantOptions::default()
+        };
+        let report = engine
+            .search_variants("blue", &options, &[], &[], Some(request), false)
+            .await
+            .unwrap();
+
+        assert_eq!(report.variants[0].username, "blue");
+        assert_eq!(report.results.len(), report.variants.len());
+        let found: Vec<_> = report.matches.iter().map(|m| m.username.as_str()).collect();
+        assert_eq!(found, vec!["blue", "navy", "ink"]);
+    }
+
+    #[tokio::test]
+    async fn test_engine_search_stream_empty() {
+        let engine = Engine::with_registry(SiteRegistry::from_sites(vec![]));
+        let mut stream = engine
+            .search_stream("testuser", &[], &[], None, false)
+            .unwrap();
+        assert!(stream.next().await.is_none());
+    }
+
     #[tokio::test]
     async fn test_engine_search_empty_sites() {
         let engine = Engine::new();
@@ -0,0 +1,159 @@
+//! Evidence recorded for each check
+
+use crate::request::{RequestResponse, RequestType};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::time::Duration;
+
+/// Response headers kept as evidence (compared case-insensitively)
+pub const EVIDENCE_HEADERS: &[&str] = &[
+    "content-type",
+    "content-length",
+    "content-language",
+    "date",
+    "last-modified",
+    "etag",
+    "location",
+    "server",
+    "retry-after",
+    "x-ratelimit-remaining",
+    "cf-ray",
+];
+
+/// What was requested and received for a check, to justify its outcome later
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Evidence {
+    /// HTTP method of the request
+    pub method: String,
+    /// URL that was requested
+    pub url: String,
+    /// URL of the response, after any redirects
+    pub final_url: String,
+    /// Status code of the response
+    pub status_code: u16,
+    /// Selected response headers (see [`EVIDENCE_HEADERS`]), with lowercase names
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// Time from sending the request to receiving the response, in milliseconds
+    pub response_time_ms: u64,
+    /// Size of the body in bytes (None if no body was fetched, e.g. for HEAD)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_size: Option<usize>,
+    /// Hex-encoded SHA-256 of the body (None if no body was fetched)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_sha256: Option<String>,
+    /// Transport the request went through
+    pub transport: RequestType,
+    /// When the response was received
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Evidence {
+    /// Record the evidence of a response received just now
+    pub fn capture(
+        method: &str,
+        url: &str,
+        response: &RequestResponse,
+        transport: RequestType,
+        response_time: Duration,
+    ) -> Self {
+        let headers = response
+            .headers
+            .iter()
+            .filter(|(name, _)| {
+                EVIDENCE_HEADERS
+                    .iter()
+                    .any(|kept| name.eq_ignore_ascii_case(kept))
+            })
+            .map(|(name, value)| (name.to_lowercase(), value.clone()))
+            .collect();
+        let body = response.body.as_deref();
+
+        Self {
+            method: method.to_uppercase(),
+            url: url.to_string(),
+            final_url: response
+                .final_url
+                .clone()
+                .unwrap_or_else(|| url.to_string()),
+            status_code: response.status_code,
+            headers,
+            response_time_ms: response_time.as_millis() as u64,
+            body_size: body.map(str::len),
+            body_sha256: body.map(sha256_hex),
+            transport,
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+/// Hex-encoded SHA-256 of a text
+fn sha256_hex(text: &str) -> String {
+    Sha256::digest(text.as_bytes())
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{:02x}", byte);
+            hex
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_body_and_headers() {
+        let response = RequestResponse {
+            headers: vec![
+                ("Content-Type".to_string(), "text/html".to_string()),
+                ("Set-Cookie".to_string(), "session=secret".to_string()),
+            ],
+            final_url: Some("https://example.com/blue/".to_string()),
+            ..RequestResponse::with_body(200, "abc".to_string())
+        };
+        let evidence = Evidence::capture(
+            "get",
+            "https://example.com/blue",
+            &response,
+            RequestType::Http,
+            Duration::from_millis(120),
+        );
+
+        assert_eq!(evidence.method, "GET");
+        assert_eq!(evidence.final_url, "https://example.com/blue/");
+        assert_eq!(evidence.status_code, 200);
+        assert_eq!(evidence.response_time_ms, 120);
+        assert_eq!(evidence.body_size, Some(3));
+        assert_eq!(
+            evidence.body_sha256.as_deref(),
+            Some("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+        );
+        assert_eq!(
+            evidence.headers.get("content-type").map(String::as_str),
+            Some("text/html")
+        );
+        assert!(!evidence.headers.contains_key("set-cookie"));
+    }
+
+    #[test]
+    fn test_capture_without_body() {
+        let evidence = Evidence::capture(
+            "HEAD",
+            "https://example.com/blue",
+            &RequestResponse::new(404),
+            RequestType::Browser,
+            Duration::ZERO,
+        );
+        assert_eq!(evidence.final_url, "https://example.com/blue");
+        assert!(evidence.body_size.is_none());
+        assert!(evidence.body_sha256.is_none());
+
+        let json = serde_json::to_value(&evidence).unwrap();
+        assert_eq!(json["transport"], "browser");
+        assert!(json["timestamp"].as_str().unwrap().ends_with('Z'));
+        assert!(json.get("body_sha256").is_none());
+    }
+}
@@ -0,0 +1,143 @@
+//! Concurrency limits for outgoing requests
+
+use crate::request::host_key;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Default cap on requests in flight across all sites
+pub const DEFAULT_MAX_CONCURRENCY: usize = 32;
+
+/// Default cap on requests in flight to a single host
+pub const DEFAULT_MAX_PER_HOST: usize = 4;
+
+/// Caps on concurrent requests, globally and per host
+///
+/// Clones share the same limits, so an engine enforces them across searches.
+#[derive(Debug, Clone)]
+pub struct ConnectionLimits {
+    global: Arc<Semaphore>,
+    max_total: usize,
+    max_per_host: usize,
+    hosts: Arc<Mutex<HashMap<String, Arc<Semaphore>>>>,
+}
+
+/// Permission to send one request; the slots are released when dropped
+#[derive(Debug)]
+pub struct ConnectionPermit {
+    _host: OwnedSemaphorePermit,
+    _global: OwnedSemaphorePermit,
+}
+
+impl ConnectionLimits {
+    /// Create limits allowing `max_total` requests in flight overall and
+    /// `max_per_host` to any one host (both at least 1)
+    pub fn new(max_total: usize, max_per_host: usize) -> Self {
+        let max_total = max_total.max(1);
+        Self {
+            global: Arc::new(Semaphore::new(max_total)),
+            max_total,
+            max_per_host: max_per_host.max(1),
+            hosts: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Maximum number of requests in flight overall
+    pub fn max_total(&self) -> usize {
+        self.max_total
+    }
+
+    /// Maximum number of requests in flight to one host
+    pub fn max_per_host(&self) -> usize {
+        self.max_per_host
+    }
+
+    /// Wait for a free slot for a request to `url`
+    pub async fn acquire(&self, url: &str) -> ConnectionPermit {
+        // Take the host slot first so that requests queued behind a busy host
+        // do not hold global slots other hosts could use
+        let host = self.host_semaphore(url);
+        let host = host
+            .acquire_owned()
+            .await
+            .expect("connection semaphores are never closed");
+        let global = Arc::clone(&self.global)
+            .acquire_owned()
+            .await
+            .expect("connection semaphores are never closed");
+        ConnectionPermit {
+            _host: host,
+            _global: global,
+        }
+    }
+
+    fn host_semaphore(&self, url: &str) -> Arc<Semaphore> {
+        let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        Arc::clone(
+            hosts
+                .entry(host_key(url))
+                .or_insert_with(|| Arc::new(Semaphore::new(self.max_per_host))),
+        )
+    }
+}
+
+impl Default for ConnectionLimits {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CONCURRENCY, DEFAULT_MAX_PER_HOST)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_limits_are_at_least_one() {
+        let limits = ConnectionLimits::new(0, 0);
+        assert_eq!(limits.max_total(), 1);
+        assert_eq!(limits.max_per_host(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_per_host_limit() {
+        let limits = ConnectionLimits::new(10, 1);
+        let _first = limits.acquire("https://example.com/a").await;
+
+        // Same host has to wait, another host does not
+        let same_host = tokio::time::timeout(
+            Duration::from_millis(20),
+            limits.acquire("https://example.com/b"),
+        )
+        .await;
+        assert!(same_host.is_err());
+        let other_host = tokio::time::timeout(
+            Duration::from_millis(20),
+            limits.acquire("https://example.org/b"),
+        )
+        .await;
+        assert!(other_host.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_global_limit_shared_by_clones() {
+        let limits = ConnectionLimits::new(1, 4);
+        let shared = limits.clone();
+        let first = limits.acquire("https://example.com/a").await;
+
+        let blocked = tokio::time::timeout(
+            Duration::from_millis(20),
+            shared.acquire("https://example.org/a"),
+        )
+        .await;
+        assert!(blocked.is_err());
+
+        drop(first);
+        let freed = tokio::time::timeout(
+            Duration::from_millis(20),
+            shared.acquire("https://example.org/a"),
+        )
+        .await;
+        assert!(freed.is_ok());
+    }
+}
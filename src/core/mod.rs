@@ -1,12 +1,27 @@
 //! Core engine functionality
 
+pub mod calibration;
 pub mod engine;
+pub mod evidence;
+pub mod limits;
+pub mod options;
+pub mod pivot;
 pub mod result;
 pub mod scanner;
+pub mod self_test;
+pub mod variants;
 
 pub use engine::Engine;
-pub use result::SearchResult;
-pub use scanner::scan_username;
+pub use evidence::Evidence;
+pub use limits::ConnectionLimits;
+pub use options::{RetryPolicy, ScanOptions};
+pub use pivot::{Discovery, PivotOptions, PivotReport};
+pub use result::{Outcome, SearchResult, UsernameResults};
+pub use scanner::{
+    merge_result, scan_username, scan_username_stream, scan_username_with_options, ScanStream,
+};
+pub use self_test::{test_sites, SelfTestSummary};
+pub use variants::{generate_variants, VariantOptions, VariantReport};
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,144 @@
+//! Scan configuration
+
+use crate::core::limits::ConnectionLimits;
+use crate::request::RateLimiter;
+use rand::Rng;
+use std::time::Duration;
+
+/// When and how often to retry a site
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt (0 disables retrying)
+    pub max_retries: u32,
+    /// Delay before the first retry; doubles with every further retry
+    pub base_delay: Duration,
+    /// Upper bound for a single delay
+    pub max_delay: Duration,
+    /// Longest a site's retries wait, in total, for pauses its server asks for
+    /// (`Retry-After`, exhausted `X-RateLimit-*`); a longer pause ends the retries
+    pub max_pause: Duration,
+}
+
+impl RetryPolicy {
+    /// Create a policy with `max_retries` retries and the default delays
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            ..Self::default()
+        }
+    }
+
+    /// Exponential delay before retry number `retry` (1-based), without jitter
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+
+    /// Delay before retry number `retry`, with jitter in `[backoff / 2, backoff]`
+    /// so that sites failing together do not retry in lockstep
+    pub fn delay(&self, retry: u32) -> Duration {
+        let backoff = self.backoff(retry);
+        let half = backoff / 2;
+        half + rand::thread_rng().gen_range(Duration::ZERO..=half)
+    }
+
+    /// Whether a status code is worth retrying (5xx, 429)
+    pub fn is_retryable_status(status_code: u16) -> bool {
+        status_code == 429 || (500..=599).contains(&status_code)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+            max_pause: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Options controlling a scan
+///
+/// Clones share the same [`ConnectionLimits`] and [`RateLimiter`].
+///
+/// The default retry policy does not retry; the CLI's `--retries` defaults
+/// to 3, so library scans have to opt in with [`RetryPolicy::new`].
+#[derive(Debug, Clone)]
+pub struct ScanOptions {
+    /// Verify found results with a headless browser (second pass)
+    pub verify: bool,
+    /// Check found results against a random control username (soft-404 calibration)
+    pub calibrate: bool,
+    /// Retry policy for failed or uncertain checks
+    pub retry: RetryPolicy,
+    /// Time allowed for a single request, unless the site declares its own
+    pub timeout: Duration,
+    /// Overall time allowed for the scan; sites still pending are reported as timed out
+    pub deadline: Option<Duration>,
+    /// Caps on concurrent requests, globally and per host
+    pub limits: ConnectionLimits,
+    /// Per-host request budgets and server-requested pauses
+    pub rate_limiter: RateLimiter,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            verify: false,
+            calibrate: false,
+            retry: RetryPolicy::default(),
+            timeout: Duration::from_secs(10),
+            deadline: None,
+            limits: ConnectionLimits::default(),
+            rate_limiter: RateLimiter::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_policy_backoff() {
+        let policy = RetryPolicy::new(5);
+        assert_eq!(policy.backoff(1), Duration::from_millis(500));
+        assert_eq!(policy.backoff(2), Duration::from_secs(1));
+        assert_eq!(policy.backoff(3), Duration::from_secs(2));
+        // Capped at max_delay
+        assert_eq!(policy.backoff(10), Duration::from_secs(10));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_retry_policy_jitter_bounds() {
+        let policy = RetryPolicy::new(3);
+        for _ in 0..50 {
+            let delay = policy.delay(2);
+            assert!(delay >= Duration::from_millis(500));
+            assert!(delay <= Duration::from_secs(1));
+        }
+    }
+
+    #[test]
+    fn test_retryable_status() {
+        assert!(RetryPolicy::is_retryable_status(429));
+        assert!(RetryPolicy::is_retryable_status(503));
+        assert!(!RetryPolicy::is_retryable_status(404));
+        assert!(!RetryPolicy::is_retryable_status(200));
+    }
+
+    #[test]
+    fn test_scan_options_default() {
+        let options = ScanOptions::default();
+        assert!(!options.verify);
+        assert!(!options.calibrate);
+        assert_eq!(options.retry.max_retries, 0);
+        assert_eq!(options.timeout, Duration::from_secs(10));
+        assert!(options.deadline.is_none());
+        assert_eq!(options.limits.max_total(), 32);
+        assert_eq!(options.limits.max_per_host(), 4);
+    }
+}
@@ -0,0 +1,242 @@
+//! Recursive pivoting on usernames and links found in profiles
+//!
+//! A found profile often names the same person elsewhere: a different handle,
+//! a website field, bio links or a link page. [`pivot_candidates`] maps those
+//! back onto registered sites, and `Engine::search_recursive` scans every new
+//! username up to a depth and a budget, recording which hit led to which.
+
+use crate::core::result::SearchResult;
+use crate::sites::Site;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, OnceLock};
+
+/// Limits of a recursive search
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PivotOptions {
+    /// How many hops away from the searched username to go (0 disables pivoting)
+    pub max_depth: usize,
+    /// How many discovered usernames to scan at most, over all depths
+    pub max_scans: usize,
+}
+
+impl Default for PivotOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 0,
+            max_scans: 10,
+        }
+    }
+}
+
+/// A username worth scanning, found in a profile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    pub username: String,
+    /// What pointed to it: `"profile username"` or the link it was read from
+    pub via: String,
+}
+
+/// The found result a username was discovered from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PivotSource {
+    /// Site of the found profile
+    pub site: String,
+    /// Username the profile was found for
+    pub username: String,
+    /// What in the profile pointed to the new username
+    pub via: String,
+}
+
+/// A username scanned by a recursive search
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Discovery {
+    pub username: String,
+    /// Hops away from the searched username (0 for the searched username itself)
+    pub depth: usize,
+    /// Where it was discovered (None for the searched username)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<PivotSource>,
+}
+
+/// Results of a recursive search, with the discovery graph
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PivotReport {
+    /// Results of every scanned username
+    pub results: Vec<SearchResult>,
+    /// Scanned usernames in the order they were scanned
+    pub discoveries: Vec<Discovery>,
+}
+
+impl PivotReport {
+    /// Results of one scanned username
+    pub fn results_for<'a>(&'a self, username: &'a str) -> impl Iterator<Item = &'a SearchResult> {
+        self.results.iter().filter(move |r| r.username == username)
+    }
+
+    /// Usernames discovered from results of `username`
+    pub fn discovered_from<'a>(&'a self, username: &'a str) -> impl Iterator<Item = &'a Discovery> {
+        self.discoveries.iter().filter(move |d| {
+            d.from
+                .as_ref()
+                .is_some_and(|from| from.username == username)
+        })
+    }
+}
+
+/// Usernames a found result points to: the handle shown on the profile, and
+/// profile links or bio URLs that match a site in `sites`
+pub fn pivot_candidates(result: &SearchResult, sites: &[Arc<dyn Site>]) -> Vec<Candidate> {
+    let Some(profile) = result.profile.as_ref().filter(|_| result.exists()) else {
+        return vec![];
+    };
+
+    let mut candidates = Vec::new();
+    let mut add = |username: &str, via: &str| {
+        let known = candidates
+            .iter()
+            .any(|c: &Candidate| c.username.eq_ignore_ascii_case(username));
+        if !username.eq_ignore_ascii_case(&result.username) && !known {
+            candidates.push(Candidate {
+                username: username.to_string(),
+                via: via.to_string(),
+            });
+        }
+    };
+
+    if let Some(username) = &profile.username {
+        add(username.trim_start_matches('@'), "profile username");
+    }
+
+    let bio_links = profile.bio.as_deref().map(urls_in).unwrap_or_default();
+    for link in profile.links.iter().map(String::as_str).chain(bio_links) {
+        for site in sites {
+            if let Some(username) = site.username_from_url(link) {
+                add(&username, link);
+            }
+        }
+    }
+    candidates
+}
+
+/// URLs written in a text
+fn urls_in(text: &str) -> Vec<&str> {
+    static URL: OnceLock<Regex> = OnceLock::new();
+    let url = URL.get_or_init(|| Regex::new(r#"https?://[^\s<>"')]+"#).expect("valid pattern"));
+    url.find_iter(text)
+        .map(|m| m.as_str().trim_end_matches(['.', ',', ';', '!']))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::site_info::SiteType;
+    use crate::sites::profile::ProfileInfo;
+
+    struct PatternSite(&'static str);
+
+    impl Site for PatternSite {
+        fn name(&self) -> &str {
+            self.0
+        }
+        fn url_pattern(&self) -> &str {
+            self.0
+        }
+        fn site_type(&self) -> SiteType {
+            SiteType::Other
+        }
+    }
+
+    fn sites() -> Vec<Arc<dyn Site>> {
+        vec![
+            Arc::new(PatternSite("https://code.example/{}")),
+            Arc::new(PatternSite("https://{}.blog.example")),
+        ]
+    }
+
+    fn found(profile: ProfileInfo) -> SearchResult {
+        SearchResult::found(
+            "Code".to_string(),
+            "blue".to_string(),
+            "https://code.example/blue".to_string(),
+        )
+        .with_profile(Some(profile))
+    }
+
+    #[test]
+    fn test_pivot_candidates() {
+        let result = found(ProfileInfo {
+            username: Some("@bluebird".to_string()),
+            bio: Some(
+                "Writing at https://skyblue.blog.example. Code: https://code.example/BLUE"
+                    .to_string(),
+            ),
+            links: vec![
+                "https://code.example/bluebird".to_string(),
+                "https://unknown.example/navy".to_string(),
+            ],
+            ..ProfileInfo::default()
+        });
+        let candidates = pivot_candidates(&result, &sites());
+        assert_eq!(
+            candidates,
+            vec![
+                Candidate {
+                    username: "bluebird".to_string(),
+                    via: "profile username".to_string(),
+                },
+                Candidate {
+                    username: "skyblue".to_string(),
+                    via: "https://skyblue.blog.example".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pivot_candidates_need_a_found_profile() {
+        let profile = ProfileInfo {
+            username: Some("bluebird".to_string()),
+            ..ProfileInfo::default()
+        };
+        let mut result = found(profile);
+        result.outcome = crate::core::result::Outcome::Uncertain;
+        assert!(pivot_candidates(&result, &sites()).is_empty());
+
+        let result = found(ProfileInfo::default()).with_profile(None);
+        assert!(pivot_candidates(&result, &sites()).is_empty());
+    }
+
+    #[test]
+    fn test_pivot_report_graph() {
+        let report = PivotReport {
+            results: vec![found(ProfileInfo::default())],
+            discoveries: vec![
+                Discovery {
+                    username: "blue".to_string(),
+                    depth: 0,
+                    from: None,
+                },
+                Discovery {
+                    username: "bluebird".to_string(),
+                    depth: 1,
+                    from: Some(PivotSource {
+                        site: "Code".to_string(),
+                        username: "blue".to_string(),
+                        via: "profile username".to_string(),
+                    }),
+                },
+            ],
+        };
+        assert_eq!(report.results_for("blue").count(), 1);
+        assert_eq!(report.results_for("bluebird").count(), 0);
+        let children: Vec<_> = report.discovered_from("blue").collect();
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0].username, "bluebird");
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert!(json["discoveries"][0].get("from").is_none());
+        assert_eq!(json["discoveries"][1]["from"]["via"], "profile username");
+    }
+}
@@ -1,6 +1,90 @@
 //! Search result types
 
+use crate::core::evidence::Evidence;
+use crate::request::block::Protection;
+use crate::sites::profile::ProfileInfo;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// What a check concluded about a username on a site
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    /// The username exists on the site
+    Found,
+    /// The username does not exist on the site
+    NotFound,
+    /// The site answered, but the answer proves neither
+    Uncertain,
+    /// The site refused to answer (e.g. 403 or a bot-protection page)
+    Blocked,
+    /// The site asked us to slow down (429) and never answered
+    RateLimited,
+    /// The site did not answer in time (timeout or scan deadline)
+    TimedOut,
+    /// The check failed (network error, 5xx)
+    Error,
+    /// The username is impossible on the site; no request was made
+    InvalidUsername,
+    /// The site was not checked (e.g. the scan deadline passed first)
+    Skipped,
+}
+
+impl Outcome {
+    /// All outcomes, in the order the CLI reports them
+    pub const ALL: [Outcome; 9] = [
+        Outcome::Found,
+        Outcome::NotFound,
+        Outcome::Uncertain,
+        Outcome::Blocked,
+        Outcome::RateLimited,
+        Outcome::TimedOut,
+        Outcome::Error,
+        Outcome::InvalidUsername,
+        Outcome::Skipped,
+    ];
+
+    /// Machine-readable name, as used in JSON and CSV
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Outcome::Found => "found",
+            Outcome::NotFound => "not_found",
+            Outcome::Uncertain => "uncertain",
+            Outcome::Blocked => "blocked",
+            Outcome::RateLimited => "rate_limited",
+            Outcome::TimedOut => "timed_out",
+            Outcome::Error => "error",
+            Outcome::InvalidUsername => "invalid_username",
+            Outcome::Skipped => "skipped",
+        }
+    }
+
+    /// Human-readable name, as used in text output
+    pub fn label(&self) -> &'static str {
+        match self {
+            Outcome::Found => "found",
+            Outcome::NotFound => "not found",
+            Outcome::Uncertain => "uncertain",
+            Outcome::Blocked => "blocked",
+            Outcome::RateLimited => "rate limited",
+            Outcome::TimedOut => "timed out",
+            Outcome::Error => "error",
+            Outcome::InvalidUsername => "invalid for site",
+            Outcome::Skipped => "skipped",
+        }
+    }
+
+    /// Whether the site gave a definite answer (found or not found)
+    pub fn is_conclusive(&self) -> bool {
+        matches!(self, Outcome::Found | Outcome::NotFound)
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
 /// Result of a username search on a site
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -9,49 +93,173 @@ pub struct SearchResult {
     pub site: String,
     /// Username that was searched
     pub username: String,
-    /// Whether the username exists on this site
-    pub exists: bool,
-    /// URL where the profile was found (if exists)
+    /// What the check concluded
+    pub outcome: Outcome,
+    /// URL where the profile was found (if found)
     pub url: Option<String>,
-    /// Additional metadata
-    pub metadata: Option<String>,
+    /// What went wrong, for errors and other inconclusive outcomes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Whether this is a browser verification update superseding the first-pass result
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub verified: bool,
+    /// Number of requests made for the site's probe (0 if none was needed);
+    /// calibration control requests are not counted
+    #[serde(default)]
+    pub attempts: u32,
+    /// Detection rule that decided the result, for rule-based sites
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+    /// How much the outcome can be trusted, from 0 (not at all) to 100
+    #[serde(default)]
+    pub confidence: u8,
+    /// How the outcome was decided: indicator, pass and transport
+    #[serde(default)]
+    pub reason: String,
+    /// The response the outcome was decided on (None if no response was received)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evidence: Option<Evidence>,
+    /// Profile details read from the response, for found accounts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<ProfileInfo>,
+    /// Bot protection that answered instead of the site, for blocked results
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protection: Option<Protection>,
 }
 
 impl SearchResult {
     /// Create a new search result
     pub fn new(site: String, username: String, exists: bool) -> Self {
+        let outcome = if exists {
+            Outcome::Found
+        } else {
+            Outcome::NotFound
+        };
+        Self::with_outcome(site, username, outcome)
+    }
+
+    /// Create a result with the given outcome
+    pub fn with_outcome(site: String, username: String, outcome: Outcome) -> Self {
         Self {
             site,
             username,
-            exists,
+            outcome,
             url: None,
-            metadata: None,
+            error: None,
+            verified: false,
+            attempts: 1,
+            rule: None,
+            confidence: 0,
+            reason: String::new(),
+            evidence: None,
+            profile: None,
+            protection: None,
         }
     }
 
     /// Create a result indicating the username exists
     pub fn found(site: String, username: String, url: String) -> Self {
         Self {
-            site,
-            username,
-            exists: true,
             url: Some(url),
-            metadata: None,
+            ..Self::with_outcome(site, username, Outcome::Found)
         }
     }
 
     /// Create a result indicating the username was not found
     pub fn not_found(site: String, username: String) -> Self {
+        Self::with_outcome(site, username, Outcome::NotFound)
+    }
+
+    /// Create a result for a username the site does not allow
+    pub fn invalid(site: String, username: String) -> Self {
         Self {
-            site,
-            username,
-            exists: false,
-            url: None,
-            metadata: None,
+            attempts: 0,
+            ..Self::with_outcome(site, username, Outcome::InvalidUsername)
         }
+        .with_reason(
+            100,
+            "username does not fit the site's rules; no request sent",
+        )
+    }
+
+    /// Create a result for a site that did not answer in time
+    pub fn timed_out(site: String, username: String) -> Self {
+        Self::with_outcome(site, username, Outcome::TimedOut).with_reason(0, "no answer in time")
+    }
+
+    /// Create a result for a check that failed
+    pub fn error(site: String, username: String, error: String) -> Self {
+        Self {
+            error: Some(error),
+            ..Self::with_outcome(site, username, Outcome::Error)
+        }
+        .with_reason(0, "request failed")
+    }
+
+    /// Whether the username was found on the site
+    pub fn exists(&self) -> bool {
+        self.outcome == Outcome::Found
+    }
+
+    /// Mark the result as a verification update
+    pub fn into_verified(mut self) -> Self {
+        self.verified = true;
+        self
+    }
+
+    /// Record what went wrong
+    pub fn with_error(mut self, error: Option<String>) -> Self {
+        self.error = error;
+        self
+    }
+
+    /// Record how many requests the result took
+    pub fn with_attempts(mut self, attempts: u32) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    /// Record the detection rule that decided the result
+    pub fn with_rule(mut self, rule: Option<String>) -> Self {
+        self.rule = rule;
+        self
+    }
+
+    /// Record how much the outcome can be trusted and how it was decided
+    pub fn with_reason(mut self, confidence: u8, reason: impl Into<String>) -> Self {
+        self.confidence = confidence.min(100);
+        self.reason = reason.into();
+        self
+    }
+
+    /// Record the response the outcome was decided on
+    pub fn with_evidence(mut self, evidence: Option<Evidence>) -> Self {
+        self.evidence = evidence;
+        self
+    }
+
+    /// Record the profile details read from the response
+    pub fn with_profile(mut self, profile: Option<ProfileInfo>) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Record the bot protection that answered instead of the site
+    pub fn with_protection(mut self, protection: Option<Protection>) -> Self {
+        self.protection = protection;
+        self
     }
 }
 
+/// Results of one username of a batch search
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UsernameResults {
+    /// Username that was searched
+    pub username: String,
+    /// Its results, one per site
+    pub results: Vec<SearchResult>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -61,7 +269,7 @@ mod tests {
         let result = SearchResult::new("github".to_string(), "testuser".to_string(), true);
         assert_eq!(result.site, "github");
         assert_eq!(result.username, "testuser");
-        assert!(result.exists);
+        assert!(result.exists());
         assert!(result.url.is_none());
     }
 
@@ -72,14 +280,109 @@ mod tests {
             "testuser".to_string(),
             "https://github.com/testuser".to_string(),
         );
-        assert!(result.exists);
+        assert!(result.exists());
+        assert_eq!(result.outcome, Outcome::Found);
         assert_eq!(result.url, Some("https://github.com/testuser".to_string()));
     }
 
     #[test]
     fn test_search_result_not_found() {
         let result = SearchResult::not_found("github".to_string(), "testuser".to_string());
-        assert!(!result.exists);
+        assert!(!result.exists());
+        assert_eq!(result.outcome, Outcome::NotFound);
+        assert!(result.url.is_none());
+    }
+
+    #[test]
+    fn test_search_result_invalid() {
+        let result = SearchResult::invalid("github".to_string(), "bad name".to_string());
+        assert!(!result.exists());
+        assert_eq!(result.outcome, Outcome::InvalidUsername);
         assert!(result.url.is_none());
+        assert_eq!(result.attempts, 0);
+
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(json.contains("\"outcome\":\"invalid_username\""));
+    }
+
+    #[test]
+    fn test_search_result_timed_out() {
+        let result = SearchResult::timed_out("github".to_string(), "testuser".to_string());
+        assert!(!result.exists());
+        assert_eq!(result.outcome, Outcome::TimedOut);
+
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(json.contains("\"outcome\":\"timed_out\""));
+    }
+
+    #[test]
+    fn test_search_result_error() {
+        let result = SearchResult::error(
+            "github".to_string(),
+            "testuser".to_string(),
+            "connection refused".to_string(),
+        );
+        assert_eq!(result.outcome, Outcome::Error);
+        assert_eq!(result.error.as_deref(), Some("connection refused"));
+
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(json.contains("\"error\":\"connection refused\""));
+        let json = serde_json::to_string(&SearchResult::not_found(
+            "github".to_string(),
+            "testuser".to_string(),
+        ))
+        .unwrap();
+        assert!(!json.contains("error"));
+    }
+
+    #[test]
+    fn test_outcome_names() {
+        for outcome in Outcome::ALL {
+            let json = serde_json::to_string(&outcome).unwrap();
+            assert_eq!(json, format!("\"{}\"", outcome.as_str()));
+            assert_eq!(outcome.to_string(), outcome.as_str());
+        }
+        assert_eq!(Outcome::RateLimited.label(), "rate limited");
+        assert!(Outcome::NotFound.is_conclusive());
+        assert!(!Outcome::Uncertain.is_conclusive());
+    }
+
+    #[test]
+    fn test_search_result_into_verified() {
+        let result = SearchResult::not_found("github".to_string(), "testuser".to_string());
+        assert!(!result.verified);
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(!json.contains("verified"));
+
+        let result = result.into_verified();
+        assert!(result.verified);
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(json.contains("\"verified\":true"));
+    }
+
+    #[test]
+    fn test_search_result_with_rule() {
+        let result = SearchResult::not_found("github".to_string(), "testuser".to_string())
+            .with_rule(Some("missing".to_string()));
+        assert_eq!(result.rule.as_deref(), Some("missing"));
+
+        let json = serde_json::to_string(&result.with_rule(None)).unwrap();
+        assert!(!json.contains("rule"));
+    }
+
+    #[test]
+    fn test_search_result_with_reason() {
+        let result = SearchResult::not_found("github".to_string(), "testuser".to_string())
+            .with_reason(120, "rule 'missing' matched HTTP 404");
+        assert_eq!(result.confidence, 100);
+        assert_eq!(result.reason, "rule 'missing' matched HTTP 404");
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["confidence"], 100);
+        assert_eq!(json["reason"], "rule 'missing' matched HTTP 404");
+
+        let invalid = SearchResult::invalid("github".to_string(), "bad name".to_string());
+        assert_eq!(invalid.confidence, 100);
+        assert!(!invalid.reason.is_empty());
     }
 }
@@ -1,13 +1,30 @@
 //! Site scanner logic
 
-use crate::core::result::SearchResult;
+use crate::core::calibration::{control_username, difference, CALIBRATED_CONFIDENCE};
+use crate::core::evidence::Evidence;
+use crate::core::options::{RetryPolicy, ScanOptions};
+use crate::core::result::{Outcome, SearchResult};
 use crate::data::site_info::SiteType;
-use crate::request::{create_request, Request, RequestType};
+use crate::request::rate_limit::{requested_pause, MAX_PAUSE};
+use crate::request::{
+    create_request, detect_block, Block, Request, RequestResponse, RequestSpec, RequestType,
+};
+use crate::sites::detection::{Detection, STATUS_CONFIDENCE};
+use crate::sites::page_profile::page_profile;
+use crate::sites::profile::ProfileInfo;
 use crate::sites::Site;
-use crate::utils::error::Result;
-use std::collections::HashSet;
+use crate::utils::error::{Result, SleuthError};
+use futures::{Stream, StreamExt};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tokio::task::JoinSet;
+use std::task::{Context, Poll};
+use std::time::{Duration, SystemTime};
+use tokio::sync::mpsc;
+use tokio::task::{Id, JoinError, JoinHandle, JoinSet};
+use tokio::time::{timeout_at, Instant};
 
 /// Filter sites by type
 pub fn filter_sites_by_type<S: Site + ?Sized>(
@@ -66,21 +83,25 @@ pub fn filter_sites<S: Site + ?Sized>(
 /// - `username`: The username to search for
 /// - `sites`: List of sites to check
 /// - `request`: Optional request implementation (defaults to HTTP if None)
-/// - `verify`: If true, perform a second verification pass with browser headless for found sites that require it
+/// - `verify`: If true, perform a second verification pass with headless browser for found sites that require it
 ///
 /// # Strategy
 ///
 /// ## First Pass (Always)
-/// - Uses HTTP HEAD requests for all sites (fast, ~0.5s per site)
-/// - Sites that `requires_browser()` and return 200 with HEAD are marked as "found" (to be verified)
-/// - Most sites work correctly with HEAD and provide immediate results
+/// - Sends each site's probe as described by its [`RequestSpec`]
+///   (`Site::build_request()`): usually a fast HEAD, a GET when detection
+///   needs the body, or a POST to an availability endpoint
+/// - Every site gets an [`Outcome`]: found, not found, uncertain, blocked,
+///   rate limited, timed out, error, invalid username or skipped
+/// - Sites that `requires_browser()` cannot be confirmed over plain HTTP, so
+///   their `Found` outcomes wait for the second pass
 ///
 /// ## Second Pass (If `verify` is true)
-/// - Verifies found results using headless browser rendering
-/// - Only checks sites that:
-///   - Were found in the first pass (`exists == true`)
+/// - Sends the same probe through a headless browser for sites that:
+///   - Came out `Found` in the first pass
 ///   - Require browser rendering (`requires_browser() == true`)
-/// - Corrects false positives from HEAD-only detection
+/// - The verified result (`verified == true`) replaces the first-pass one,
+///   with `Outcome::Error` if the browser check fails
 /// - Slower (~4s per site) but more accurate for JavaScript-rendered content
 ///
 /// This approach provides the best balance between speed and accuracy.
@@ -90,165 +111,710 @@ pub async fn scan_username(
     request: Option<Arc<dyn Request>>,
     verify: bool,
 ) -> Result<Vec<SearchResult>> {
-    // Default to HTTP if no request provided
-    let default_request = request.unwrap_or_else(|| {
-        create_request(RequestType::Http, 10).expect("Failed to create default HTTP request")
-    });
+    let options = ScanOptions {
+        verify,
+        ..ScanOptions::default()
+    };
+    scan_username_with_options(username, sites, request, &options).await
+}
 
-    // ===== FIRST PASS: HTTP HEAD for all sites =====
-    let mut tasks: JoinSet<Result<SearchResult>> = JoinSet::new();
-    let username = username.to_string();
-    let mut site_map: Vec<(Arc<dyn Site>, usize)> = Vec::new(); // Track sites for second pass
+/// Scan a username across multiple sites with explicit [`ScanOptions`]
+///
+/// Same two-pass strategy as [`scan_username`], collected from
+/// [`scan_username_stream`]: verification updates replace the first-pass result
+/// of their site.
+pub async fn scan_username_with_options(
+    username: &str,
+    sites: Vec<Arc<dyn Site>>,
+    request: Option<Arc<dyn Request>>,
+    options: &ScanOptions,
+) -> Result<Vec<SearchResult>> {
+    let mut stream = scan_username_stream(username, sites, request, options)?;
+    let mut results = Vec::new();
+    while let Some(result) = stream.next().await {
+        merge_result(&mut results, result);
+    }
+    Ok(results)
+}
 
-    // Spawn tasks for all sites using HTTP HEAD
+/// Add a streamed result to `results`, replacing the first-pass result of the
+/// same site if it is a verification update
+pub fn merge_result(results: &mut Vec<SearchResult>, result: SearchResult) {
+    if result.verified {
+        if let Some(existing) = results.iter_mut().find(|r| r.site == result.site) {
+            *existing = result;
+            return;
+        }
+    }
+    results.push(result);
+}
+
+/// Results of a scan, yielded as each site completes
+///
+/// First-pass results come first, in completion order. When verification is
+/// enabled, a site that needs it is reported again with `verified = true`
+/// once the browser check finishes; that result supersedes the earlier one.
+/// Dropping the stream cancels the scan.
+#[derive(Debug)]
+pub struct ScanStream {
+    receiver: mpsc::UnboundedReceiver<SearchResult>,
+    scan: JoinHandle<()>,
+}
+
+impl Stream for ScanStream {
+    type Item = SearchResult;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<SearchResult>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+impl Drop for ScanStream {
+    fn drop(&mut self) {
+        self.scan.abort();
+    }
+}
+
+/// Scan a username across multiple sites, streaming results as they complete
+///
+/// Checks that fail with a transient error, get a 5xx/429 status or an uncertain
+/// detection are retried according to `options.retry`; each result records how
+/// many attempts it took.
+///
+/// Requests wait for their host's budget in `options.rate_limiter` (which also
+/// pauses hosts that answer with `Retry-After` or exhausted `X-RateLimit-*`
+/// headers) and for a slot in `options.limits`, which caps how many are in
+/// flight overall and per host. Each request gets the site's own
+/// [`Site::timeout`] or `options.timeout`; sites that never answer in time are
+/// reported as timed out. Once `options.deadline` has passed, sites still
+/// pending are reported as timed out and unfinished verifications are dropped.
+pub fn scan_username_stream(
+    username: &str,
+    sites: Vec<Arc<dyn Site>>,
+    request: Option<Arc<dyn Request>>,
+    options: &ScanOptions,
+) -> Result<ScanStream> {
+    let request = match request {
+        Some(request) => request,
+        None => default_request(&sites, options)?,
+    };
+
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let scan = tokio::spawn(run_scan(
+        username.to_string(),
+        sites,
+        request,
+        options.clone(),
+        sender,
+    ));
+    Ok(ScanStream { receiver, scan })
+}
+
+/// Run both passes, sending every result as soon as it is known
+async fn run_scan(
+    username: String,
+    sites: Vec<Arc<dyn Site>>,
+    request: Arc<dyn Request>,
+    options: ScanOptions,
+    sender: mpsc::UnboundedSender<SearchResult>,
+) {
+    let deadline = options.deadline.map(|deadline| Instant::now() + deadline);
+
+    // ===== FIRST PASS: each site's RequestSpec over HTTP =====
+    let mut tasks: JoinSet<(usize, SearchResult)> = JoinSet::new();
+    // Site of each check task, to report the ones that crash
+    let mut checking: HashMap<Id, usize> = HashMap::new();
+    // Whether each site's request has been sent, to tell timeouts from skips
+    let started: Arc<Vec<AtomicBool>> =
+        Arc::new(sites.iter().map(|_| AtomicBool::new(false)).collect());
+
+    // Spawn a task per site sending the probe from `Site::build_request()`
     for (idx, site) in sites.iter().enumerate() {
         let username_clone = username.clone();
         let site_clone = Arc::clone(site);
-        let request_clone = Arc::clone(&default_request);
-        site_map.push((Arc::clone(site), idx));
-
-        tasks.spawn(async move {
-            let url = site_clone.build_url(&username_clone);
-            let method = site_clone.http_method();
-
-            // Use site's preferred HTTP method for first pass
-            // Most sites use HEAD (fast), but some need GET to get body for parsing
-            // Sites that need JavaScript rendering will be verified in second pass if --verify
-            let response = if method == "GET" {
-                request_clone.get(&url).await?
-            } else {
-                request_clone.head(&url).await?
-            };
+        let request_clone = Arc::clone(&request);
+        let options = options.clone();
+        let started = Arc::clone(&started);
 
-            // Parse response using site-specific logic
-            let exists = site_clone.parse_response(
+        let task = tasks.spawn(async move {
+            let result = check_site(
+                &*site_clone,
                 &username_clone,
-                response.status_code,
-                response.body.as_deref(),
-            );
-
-            match exists {
-                Some(true) => Ok(SearchResult::found(
-                    site_clone.name().to_string(),
-                    username_clone,
-                    url,
-                )),
-                Some(false) => Ok(SearchResult::not_found(
-                    site_clone.name().to_string(),
-                    username_clone,
-                )),
-                None => {
-                    // If parse_response returns None (uncertain), check if site requires browser
-                    // For sites that require browser, HEAD returning 200 is a positive indicator
-                    // (will be verified in second pass if --verify is enabled)
-                    if site_clone.requires_browser() && (200..=299).contains(&response.status_code)
-                    {
-                        Ok(SearchResult::found(
-                            site_clone.name().to_string(),
-                            username_clone,
-                            url,
-                        ))
-                    } else {
-                        Ok(SearchResult::not_found(
-                            site_clone.name().to_string(),
-                            username_clone,
-                        ))
-                    }
-                }
-            }
+                &*request_clone,
+                &options,
+                &started[idx],
+                deadline,
+            )
+            .await;
+            (idx, result)
         });
+        checking.insert(task.id(), idx);
     }
 
-    // Collect first pass results
-    let mut results = Vec::new();
-    while let Some(res) = tasks.join_next().await {
-        match res {
-            Ok(Ok(result)) => results.push(result),
-            Ok(Err(e)) => {
-                eprintln!("Error checking site: {}", e);
+    // ===== SECOND PASS: browser verification, started as first-pass results arrive =====
+    let mut verify_tasks: JoinSet<SearchResult> = JoinSet::new();
+    // Site of each verification task, to report the ones that crash
+    let mut verifying: HashMap<Id, usize> = HashMap::new();
+
+    // Send first pass results
+    let mut pending: HashSet<usize> = (0..sites.len()).collect();
+    while let Some(res) = join_next_before(&mut tasks, deadline).await {
+        let result = match res {
+            Ok((idx, result)) => {
+                pending.remove(&idx);
+                // Found sites that require a browser are verified if --verify
+                let site = &sites[idx];
+                if options.verify && result.exists() && site.requires_browser() {
+                    let task = verify_tasks.spawn(verify_site(
+                        Arc::clone(site),
+                        username.clone(),
+                        options.clone(),
+                        deadline,
+                    ));
+                    verifying.insert(task.id(), idx);
+                }
+                result
             }
             Err(e) => {
-                eprintln!("Task error: {}", e);
+                let Some(&idx) = checking.get(&e.id()) else {
+                    continue;
+                };
+                pending.remove(&idx);
+                SearchResult::error(
+                    sites[idx].name().to_string(),
+                    username.clone(),
+                    format!("check task failed: {}", e),
+                )
             }
+        };
+        if sender.send(result).is_err() {
+            return; // Nobody is listening anymore
         }
     }
 
-    // ===== SECOND PASS: Browser verification if --verify is enabled =====
-    if verify {
-        // Find sites that were found and require browser
-        let mut verify_tasks: JoinSet<Result<SearchResult>> = JoinSet::new();
-        let username_for_verify = username.clone(); // Clone for second pass
-
-        for (site, idx) in site_map {
-            // Check if this site was found in first pass
-            if let Some(result) = results.get(idx) {
-                if result.exists && site.requires_browser() {
-                    let username_clone = username_for_verify.clone();
-                    let site_clone = Arc::clone(&site);
-                    let browser_request = create_request(RequestType::Browser, 30)?;
-
-                    verify_tasks.spawn(async move {
-                        let url = site_clone.build_url(&username_clone);
-                        let method = site_clone.http_method();
-
-                        // Use browser for verification
-                        let response = browser_request.request(method, &url).await?;
-
-                        // Parse response using site-specific logic
-                        let exists = site_clone.parse_response(
-                            &username_clone,
-                            response.status_code,
-                            response.body.as_deref(),
-                        );
-
-                        match exists {
-                            Some(true) => Ok(SearchResult::found(
-                                site_clone.name().to_string(),
-                                username_clone,
-                                url,
-                            )),
-                            Some(false) => Ok(SearchResult::not_found(
-                                site_clone.name().to_string(),
-                                username_clone,
-                            )),
-                            None => Ok(SearchResult::not_found(
-                                site_clone.name().to_string(),
-                                username_clone,
-                            )),
-                        }
-                    });
-                }
+    // Past the deadline, sites still waiting for an answer timed out and sites
+    // that never sent their request were skipped; crashed checks were
+    // reported as they ended
+    let past_deadline = !tasks.is_empty();
+    tasks.abort_all();
+    let mut pending: Vec<usize> = pending.into_iter().collect();
+    pending.sort_unstable();
+    for idx in pending {
+        let site = sites[idx].name().to_string();
+        let result = if !past_deadline {
+            SearchResult::error(site, username.clone(), "check did not complete".to_string())
+        } else if started[idx].load(Ordering::SeqCst) {
+            SearchResult::timed_out(site, username.clone())
+                .with_error(Some("scan deadline passed".to_string()))
+        } else {
+            SearchResult::with_outcome(site, username.clone(), Outcome::Skipped)
+                .with_error(Some("scan deadline passed".to_string()))
+                .with_reason(0, "not checked before the scan deadline")
+                .with_attempts(0)
+        };
+        if sender.send(result).is_err() {
+            return;
+        }
+    }
+
+    // Send verification updates
+    while let Some(res) = join_next_before(&mut verify_tasks, deadline).await {
+        let verified_result = match res {
+            Ok(verified_result) => verified_result,
+            Err(e) => {
+                let Some(&idx) = verifying.get(&e.id()) else {
+                    continue;
+                };
+                SearchResult::error(
+                    sites[idx].name().to_string(),
+                    username.clone(),
+                    format!("verification task failed: {}", e),
+                )
+                .into_verified()
             }
+        };
+        if sender.send(verified_result).is_err() {
+            return;
         }
+    }
+    verify_tasks.abort_all();
+}
 
-        // Collect verification results and update original results
-        while let Some(res) = verify_tasks.join_next().await {
-            match res {
-                Ok(Ok(verified_result)) => {
-                    // Update the corresponding result in results vector
-                    if let Some(result) =
-                        results.iter_mut().find(|r| r.site == verified_result.site)
-                    {
-                        *result = verified_result;
-                    }
-                }
-                Ok(Err(e)) => {
-                    eprintln!("Error verifying site: {}", e);
-                }
-                Err(e) => {
-                    eprintln!("Verification task error: {}", e);
+/// HTTP request used when a scan is given none
+///
+/// The client allows the longest site timeout and each check enforces its own.
+/// Scans that pass the same request share its connection pool.
+pub fn default_request(sites: &[Arc<dyn Site>], options: &ScanOptions) -> Result<Arc<dyn Request>> {
+    let longest = sites
+        .iter()
+        .filter_map(|site| site.timeout())
+        .fold(options.timeout, Duration::max);
+    create_request(RequestType::Http, whole_secs(longest))
+}
+
+/// Second-pass check of a found site with a headless browser
+///
+/// A browser that fails is reported as a verified error, which supersedes the
+/// first-pass result.
+async fn verify_site(
+    site: Arc<dyn Site>,
+    username: String,
+    options: ScanOptions,
+    deadline: Option<Instant>,
+) -> SearchResult {
+    let spec = probe_request(&*site, &username, &options);
+    let browser_request = match create_request(
+        RequestType::Browser,
+        whole_secs(spec_timeout(&spec, &options)),
+    ) {
+        Ok(request) => request,
+        Err(e) => {
+            return failed_result(
+                &*site,
+                &username,
+                FailedCheck {
+                    error: e,
+                    attempts: 0,
+                },
+            )
+            .into_verified()
+        }
+    };
+    let url = site.build_url(&username);
+
+    // Use browser for verification
+    let checked = check_with_retries(&*site, &username, &options.retry, deadline, || {
+        fetch_probe(&*site, &*browser_request, &spec, &options, None)
+    })
+    .await;
+    let checked = match checked {
+        Ok(checked) => checked,
+        Err(failed) => return failed_result(&*site, &username, failed).into_verified(),
+    };
+
+    let result = detected_result(
+        &*site,
+        &username,
+        url,
+        &checked,
+        Pass::Browser(&spec.method),
+    );
+    result.with_attempts(checked.attempts).into_verified()
+}
+
+/// Result of a check that failed: timed out or errored, after its attempts
+fn failed_result(site: &dyn Site, username: &str, failed: FailedCheck) -> SearchResult {
+    let FailedCheck { error, attempts } = failed;
+    let result = if error.is_timeout() {
+        SearchResult::timed_out(site.name().to_string(), username.to_string())
+            .with_error(Some(error.to_string()))
+    } else {
+        SearchResult::error(
+            site.name().to_string(),
+            username.to_string(),
+            error.to_string(),
+        )
+    };
+    result.with_attempts(attempts)
+}
+
+/// First-pass check of one site: username rules, probe request and detection
+async fn check_site(
+    site: &dyn Site,
+    username: &str,
+    request: &dyn Request,
+    options: &ScanOptions,
+    started: &AtomicBool,
+    deadline: Option<Instant>,
+) -> SearchResult {
+    // Skip usernames the site cannot have, without a network call
+    if !site.validate_username(username) {
+        return SearchResult::invalid(site.name().to_string(), username.to_string());
+    }
+
+    // Probe URL is requested; the profile URL is what gets reported
+    let url = site.build_url(username);
+
+    // Use site's preferred request for first pass
+    // Most sites use HEAD (fast), but some need GET to get body for parsing,
+    // or POST to an availability endpoint
+    // Sites that need JavaScript rendering will be verified in second pass if --verify
+    let spec = probe_request(site, username, options);
+
+    let checked = check_with_retries(site, username, &options.retry, deadline, || {
+        fetch_probe(site, request, &spec, options, Some(started))
+    })
+    .await;
+
+    let checked = match checked {
+        Ok(checked) => checked,
+        Err(failed) => return failed_result(site, username, failed),
+    };
+    let result = detected_result(site, username, url, &checked, Pass::Http(&spec.method))
+        .with_attempts(checked.attempts);
+    if options.calibrate && result.exists() {
+        return calibrated(site, request, options, deadline, result, &checked.response).await;
+    }
+    result
+}
+
+/// The site's probe request for a username, with the scan timeout if the site
+/// has none of its own
+fn probe_request(site: &dyn Site, username: &str, options: &ScanOptions) -> RequestSpec {
+    let mut spec = site.build_request(username);
+    spec.timeout.get_or_insert(options.timeout);
+    spec
+}
+
+fn spec_timeout(spec: &RequestSpec, options: &ScanOptions) -> Duration {
+    spec.timeout.unwrap_or(options.timeout)
+}
+
+/// Send a probe request within the site's budget, connection slot and
+/// timeout, and capture its evidence
+///
+/// Sites that detect on redirects get the 3xx response itself.
+async fn fetch_probe(
+    site: &dyn Site,
+    request: &dyn Request,
+    spec: &RequestSpec,
+    options: &ScanOptions,
+    started: Option<&AtomicBool>,
+) -> Result<(RequestResponse, Evidence)> {
+    // Wait for the host's budget and a free connection slot before the timeout starts
+    options
+        .rate_limiter
+        .acquire(&spec.url, site.rate_limit())
+        .await;
+    let _permit = options.limits.acquire(&spec.url).await;
+    if let Some(started) = started {
+        started.store(true, Ordering::SeqCst);
+    }
+    let sent = Instant::now();
+    let response =
+        with_timeout(site.name(), spec_timeout(spec, options), request.send(spec)).await?;
+    options.rate_limiter.observe(&spec.url, &response);
+    let evidence = Evidence::capture(
+        &spec.method,
+        &spec.url,
+        &response,
+        request.request_type(),
+        sent.elapsed(),
+    );
+    Ok((response, evidence))
+}
+
+/// Check a first-pass hit against a random control username
+///
+/// The control is fetched like the probe, with retries and block detection.
+/// A hit whose response looks like the control's is a soft 404 and becomes
+/// not found; otherwise the reason records how it differs. If no control can
+/// be made, the hit is kept as it is; if the control fails, is blocked or
+/// keeps getting a 5xx/429, the calibration is inconclusive and the reason
+/// says so.
+async fn calibrated(
+    site: &dyn Site,
+    request: &dyn Request,
+    options: &ScanOptions,
+    deadline: Option<Instant>,
+    result: SearchResult,
+    response: &RequestResponse,
+) -> SearchResult {
+    let Some(control) = control_username(site) else {
+        return result;
+    };
+    let spec = probe_request(site, &control, options);
+    let checked = check_with_retries(site, &control, &options.retry, deadline, || {
+        fetch_probe(site, request, &spec, options, None)
+    })
+    .await;
+    let inconclusive = |result: SearchResult, why: String| {
+        let confidence = result.confidence;
+        let reason = format!(
+            "{}; calibration inconclusive: control '{}' {}",
+            result.reason, control, why
+        );
+        result.with_reason(confidence, reason)
+    };
+    let checked = match checked {
+        Ok(checked) => checked,
+        Err(failed) => return inconclusive(result, format!("failed: {}", failed.error)),
+    };
+    if let Some(block) = &checked.block {
+        return inconclusive(result, format!("blocked by {}", block));
+    }
+    let control_response = checked.response;
+    if RetryPolicy::is_retryable_status(control_response.status_code) {
+        return inconclusive(result, format!("got HTTP {}", control_response.status_code));
+    }
+
+    // The site's own detection already tells the control apart
+    let difference = if checked.detection.exists == Some(false) {
+        Some("control detected as not found".to_string())
+    } else {
+        difference(&result.username, response, &control, &control_response)
+    };
+    match difference {
+        Some(difference) => {
+            let reason = format!(
+                "{}; differs from control '{}': {}",
+                result.reason, control, difference
+            );
+            let confidence = result.confidence;
+            result.with_reason(confidence, reason)
+        }
+        None => SearchResult::not_found(result.site, result.username)
+            .with_reason(
+                CALIBRATED_CONFIDENCE,
+                format!(
+                    "HTTP {} looks like random control '{}' ({})",
+                    response.status_code,
+                    control,
+                    Pass::Http(&spec.method)
+                ),
+            )
+            .with_evidence(result.evidence)
+            .with_attempts(result.attempts),
+    }
+}
+
+/// Confidence of a verdict from a site's own response parsing rather than rules
+const CHECKER_CONFIDENCE: u8 = STATUS_CONFIDENCE;
+
+/// Confidence of a bare 2xx from a site that needs a browser to render profiles
+const UNVERIFIED_CONFIDENCE: u8 = 30;
+
+/// The pass and request method that produced a response, for result reasons
+#[derive(Debug, Clone, Copy)]
+enum Pass<'a> {
+    /// First pass over plain HTTP
+    Http(&'a str),
+    /// Browser verification pass
+    Browser(&'a str),
+}
+
+impl std::fmt::Display for Pass<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pass::Http(method) => write!(f, "first pass, HTTP {}", method),
+            Pass::Browser(method) => write!(f, "browser verification, {}", method),
+        }
+    }
+}
+
+/// Turn a detection into a result with its confidence and reason
+fn detected_result(
+    site: &dyn Site,
+    username: &str,
+    url: String,
+    checked: &Checked,
+    pass: Pass<'_>,
+) -> SearchResult {
+    let (name, username) = (site.name().to_string(), username.to_string());
+    let status = checked.response.status_code;
+    let detection = &checked.detection;
+
+    if let Some(block) = &checked.block {
+        return SearchResult::with_outcome(name, username, Outcome::Blocked)
+            .with_error(Some(format!("blocked by {}", block)))
+            .with_protection(Some(block.protection))
+            .with_reason(
+                0,
+                format!(
+                    "{} answered instead of the site: {} on HTTP {} ({})",
+                    block.protection, block.indicator, status, pass
+                ),
+            )
+            .with_evidence(Some(checked.evidence.clone()));
+    }
+
+    let result = match detection.exists {
+        Some(exists) => {
+            let result = if exists {
+                SearchResult::found(name, username, url)
+            } else {
+                SearchResult::not_found(name, username)
+            };
+            let (confidence, reason) = match &detection.rule {
+                Some(rule) => (
+                    detection.confidence.unwrap_or(CHECKER_CONFIDENCE),
+                    format!("rule '{}' matched HTTP {} ({})", rule, status, pass),
+                ),
+                None => (
+                    CHECKER_CONFIDENCE,
+                    format!("site checker read HTTP {} ({})", status, pass),
+                ),
+            };
+            result
+                .with_rule(detection.rule.clone())
+                .with_reason(confidence, reason)
+        }
+        // For sites that require a browser, a 2xx in the first pass is a positive
+        // indicator (checked again in the verification pass if --verify is enabled)
+        None if matches!(pass, Pass::Http(_))
+            && site.requires_browser()
+            && (200..=299).contains(&status) =>
+        {
+            SearchResult::found(name, username, url).with_reason(
+                UNVERIFIED_CONFIDENCE,
+                format!("HTTP {} only, not checked in a browser ({})", status, pass),
+            )
+        }
+        None => inconclusive_result(&name, &username, status).with_reason(
+            0,
+            format!("no indicator matched HTTP {} ({})", status, pass),
+        ),
+    };
+    let profile = result
+        .exists()
+        .then(|| found_profile(site, &result.username, &checked.response))
+        .flatten();
+    result
+        .with_profile(profile)
+        .with_evidence(Some(checked.evidence.clone()))
+}
+
+/// Profile details of a found account: the site's own extractor first, gaps filled
+/// from the page's meta tags and JSON-LD
+fn found_profile(
+    site: &dyn Site,
+    username: &str,
+    response: &RequestResponse,
+) -> Option<ProfileInfo> {
+    let generic = response
+        .body
+        .as_deref()
+        .map(page_profile)
+        .unwrap_or_default();
+    site.extract_profile(username, response)
+        .unwrap_or_default()
+        .merge(generic)
+        .non_empty()
+}
+
+/// Result for a response that detection could not decide, classified by status
+fn inconclusive_result(site: &str, username: &str, status_code: u16) -> SearchResult {
+    let outcome = match status_code {
+        429 => Outcome::RateLimited,
+        401 | 403 => Outcome::Blocked,
+        500..=599 => Outcome::Error,
+        _ => Outcome::Uncertain,
+    };
+    SearchResult::with_outcome(site.to_string(), username.to_string(), outcome)
+        .with_error(Some(format!("HTTP {}", status_code)))
+}
+
+/// Wait for the next task to finish, or `None` once the set is empty or the
+/// deadline has passed
+async fn join_next_before<T: 'static>(
+    tasks: &mut JoinSet<T>,
+    deadline: Option<Instant>,
+) -> Option<std::result::Result<T, JoinError>> {
+    match deadline {
+        Some(deadline) => timeout_at(deadline, tasks.join_next()).await.ok().flatten(),
+        None => tasks.join_next().await,
+    }
+}
+
+/// Run one request attempt, failing with [`SleuthError::Timeout`] after `timeout`
+async fn with_timeout(
+    site: &str,
+    timeout: Duration,
+    request: impl Future<Output = Result<RequestResponse>>,
+) -> Result<RequestResponse> {
+    match tokio::time::timeout(timeout, request).await {
+        Ok(response) => response,
+        Err(_) => Err(SleuthError::Timeout(format!(
+            "{} did not answer within {:?}",
+            site, timeout
+        ))),
+    }
+}
+
+/// Round a duration up to whole seconds (at least 1) for request clients
+fn whole_secs(duration: Duration) -> u64 {
+    (duration.as_millis().div_ceil(1000) as u64).max(1)
+}
+
+/// A response with its evidence, its detection and the number of attempts it took
+struct Checked {
+    response: RequestResponse,
+    evidence: Evidence,
+    /// Block page recognised in the response; detection is skipped if set
+    block: Option<Block>,
+    detection: Detection,
+    attempts: u32,
+}
+
+/// A check that failed with an error, after `attempts` requests
+struct FailedCheck {
+    error: SleuthError,
+    attempts: u32,
+}
+
+/// Fetch a site and run detection, retrying transient errors, 5xx/429 statuses,
+/// block pages and uncertain detections with exponential backoff
+///
+/// Retries stop early when the server asks for a pause that would run past
+/// `deadline` or past the policy's total `max_pause` for the site.
+async fn check_with_retries<F, Fut>(
+    site: &dyn Site,
+    username: &str,
+    retry: &RetryPolicy,
+    deadline: Option<Instant>,
+    fetch: F,
+) -> std::result::Result<Checked, FailedCheck>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<(RequestResponse, Evidence)>>,
+{
+    let mut attempts = 0;
+    let mut paused = Duration::ZERO;
+    loop {
+        attempts += 1;
+        let can_retry = attempts <= retry.max_retries;
+
+        match fetch().await {
+            Ok((response, evidence)) => {
+                // Block pages say nothing about the username, so rules never see them
+                let block = detect_block(&response);
+                let detection = match block {
+                    Some(_) => Detection::default(),
+                    None => site.detect(username, &response),
+                };
+                // Browser-only sites are uncertain by design until the verify pass
+                let worth_retrying = RetryPolicy::is_retryable_status(response.status_code)
+                    || block.is_some()
+                    || (detection.exists.is_none() && !site.requires_browser());
+                // The rate limiter holds the next attempt for as long as the server asks
+                let pause = requested_pause(&response, SystemTime::now())
+                    .map_or(Duration::ZERO, |pause| pause.min(MAX_PAUSE));
+                paused += pause;
+                let pause_fits = paused <= retry.max_pause
+                    && deadline.is_none_or(|deadline| Instant::now() + pause < deadline);
+                if !(can_retry && worth_retrying && pause_fits) {
+                    return Ok(Checked {
+                        response,
+                        evidence,
+                        block,
+                        detection,
+                        attempts,
+                    });
                 }
             }
+            Err(e) if can_retry && e.is_retryable() => {}
+            Err(error) => return Err(FailedCheck { error, attempts }),
         }
-    }
 
-    Ok(results)
+        tokio::time::sleep(retry.delay(attempts)).await;
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::result::SearchResult;
+    use crate::core::limits::ConnectionLimits;
+    use crate::core::result::{Outcome, SearchResult};
     use crate::data::site_info::SiteType;
     use crate::sites::Site;
 
@@ -483,4 +1049,1057 @@ mod tests {
         let results = scan_username("test", sites, None, false).await;
         assert!(results.is_ok());
     }
+
+    /// Request that answers 200 to everything and counts calls
+    struct CountingRequest {
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl crate::request::Request for CountingRequest {
+        async fn head(&self, _url: &str) -> Result<crate::request::RequestResponse> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(crate::request::RequestResponse::new(200))
+        }
+
+        async fn get(&self, url: &str) -> Result<crate::request::RequestResponse> {
+            self.head(url).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scan_username_skips_invalid_username() {
+        use crate::sites::social::twitter::TwitterChecker;
+
+        let request = Arc::new(CountingRequest {
+            calls: Default::default(),
+        });
+        let sites: Vec<Arc<dyn Site>> = vec![Arc::new(TwitterChecker::new())];
+
+        // Dots are not allowed in Twitter handles
+        let results = scan_username("not.valid", sites, Some(request.clone()), false)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].outcome, Outcome::InvalidUsername);
+        assert!(!results[0].exists());
+        assert_eq!(request.calls.load(std::sync::atomic::Ordering::SeqCst), 0);
+    }
+
+    /// Request that answers with a fixed sequence of status codes, then repeats the last
+    struct SequenceRequest {
+        statuses: Vec<u16>,
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl crate::request::Request for SequenceRequest {
+        async fn head(&self, _url: &str) -> Result<crate::request::RequestResponse> {
+            let call = self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let status = self.statuses[call.min(self.statuses.len() - 1)];
+            Ok(crate::request::RequestResponse::new(status))
+        }
+
+        async fn get(&self, url: &str) -> Result<crate::request::RequestResponse> {
+            self.head(url).await
+        }
+    }
+
+    fn retry_options(max_retries: u32) -> ScanOptions {
+        ScanOptions {
+            retry: RetryPolicy {
+                base_delay: std::time::Duration::from_millis(1),
+                ..RetryPolicy::new(max_retries)
+            },
+            ..ScanOptions::default()
+        }
+    }
+
+    fn example_site() -> Vec<Arc<dyn Site>> {
+        vec![Arc::new(
+            crate::sites::declarative::DeclarativeSite::new(
+                crate::sites::declarative::SiteDefinition::new(
+                    "Example",
+                    SiteType::Other,
+                    "https://example.com/{}",
+                ),
+            )
+            .unwrap(),
+        )]
+    }
+
+    #[tokio::test]
+    async fn test_scan_username_retries_server_errors() {
+        let request = Arc::new(SequenceRequest {
+            statuses: vec![503, 429, 200],
+            calls: Default::default(),
+        });
+        let results = scan_username_with_options(
+            "user",
+            example_site(),
+            Some(request.clone()),
+            &retry_options(3),
+        )
+        .await
+        .unwrap();
+        assert!(results[0].exists());
+        assert_eq!(results[0].attempts, 3);
+        assert_eq!(request.calls.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_scan_username_retries_are_bounded() {
+        let request = Arc::new(SequenceRequest {
+            statuses: vec![500],
+            calls: Default::default(),
+        });
+        let results = scan_username_with_options(
+            "user",
+            example_site(),
+            Some(request.clone()),
+            &retry_options(2),
+        )
+        .await
+        .unwrap();
+        assert!(!results[0].exists());
+        assert_eq!(results[0].attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn test_scan_username_does_not_retry_definite_answers() {
+        let request = Arc::new(SequenceRequest {
+            statuses: vec![404, 200],
+            calls: Default::default(),
+        });
+        let results = scan_username_with_options(
+            "user",
+            example_site(),
+            Some(request.clone()),
+            &retry_options(3),
+        )
+        .await
+        .unwrap();
+        assert!(!results[0].exists());
+        assert_eq!(results[0].attempts, 1);
+    }
+
+    /// Request whose first call is reset by the peer and whose later calls fail for good
+    struct FailingRequest {
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl crate::request::Request for FailingRequest {
+        async fn head(&self, _url: &str) -> Result<crate::request::RequestResponse> {
+            let call = self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Err(if call == 0 {
+                std::io::Error::from(std::io::ErrorKind::ConnectionReset).into()
+            } else {
+                SleuthError::Unknown("refused".to_string())
+            })
+        }
+
+        async fn get(&self, url: &str) -> Result<crate::request::RequestResponse> {
+            self.head(url).await
+        }
+    }
+
+    #[test]
+    fn test_failed_result() {
+        let site = &*example_site()[0];
+        let timed_out = failed_result(
+            site,
+            "user",
+            FailedCheck {
+                error: SleuthError::Timeout("slow".to_string()),
+                attempts: 2,
+            },
+        );
+        assert_eq!(timed_out.outcome, Outcome::TimedOut);
+        assert_eq!(timed_out.attempts, 2);
+
+        // Verification failures replace the first-pass hit
+        let failed = failed_result(
+            site,
+            "user",
+            FailedCheck {
+                error: SleuthError::Unknown("Failed to launch browser".to_string()),
+                attempts: 1,
+            },
+        )
+        .into_verified();
+        assert_eq!(failed.outcome, Outcome::Error);
+        assert!(failed.error.unwrap().contains("browser"));
+        let mut results = vec![SearchResult::with_outcome(
+            "Example".to_string(),
+            "user".to_string(),
+            Outcome::Found,
+        )];
+        merge_result(
+            &mut results,
+            failed_result(
+                site,
+                "user",
+                FailedCheck {
+                    error: SleuthError::Unknown("crashed".to_string()),
+                    attempts: 1,
+                },
+            )
+            .into_verified(),
+        );
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].outcome, Outcome::Error);
+        assert!(results[0].verified);
+    }
+
+    /// Request whose every call panics
+    struct PanickingRequest;
+
+    #[async_trait::async_trait]
+    impl crate::request::Request for PanickingRequest {
+        async fn head(&self, _url: &str) -> Result<crate::request::RequestResponse> {
+            panic!("transport bug")
+        }
+
+        async fn get(&self, url: &str) -> Result<crate::request::RequestResponse> {
+            self.head(url).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scan_username_reports_crashed_checks() {
+        let results = scan_username(
+            "user",
+            example_site(),
+            Some(Arc::new(PanickingRequest)),
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].site, "Example");
+        assert_eq!(results[0].outcome, Outcome::Error);
+        assert!(
+            results[0]
+                .error
+                .as_deref()
+                .is_some_and(|error| error.contains("check task failed")),
+            "{:?}",
+            results[0].error
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scan_username_counts_attempts_of_errors() {
+        let request = Arc::new(FailingRequest {
+            calls: Default::default(),
+        });
+        let results = scan_username_with_options(
+            "user",
+            example_site(),
+            Some(request.clone()),
+            &retry_options(3),
+        )
+        .await
+        .unwrap();
+        assert_eq!(results[0].outcome, Outcome::Error);
+        // Retried after the reset, then given up on the permanent error
+        assert_eq!(results[0].attempts, 2);
+        assert_eq!(request.calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    /// Request that answers 200, but only after a delay for URLs containing "slow"
+    struct SlowRequest;
+
+    #[async_trait::async_trait]
+    impl crate::request::Request for SlowRequest {
+        async fn head(&self, url: &str) -> Result<crate::request::RequestResponse> {
+            if url.contains("slow") {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+            }
+            Ok(crate::request::RequestResponse::new(200))
+        }
+
+        async fn get(&self, url: &str) -> Result<crate::request::RequestResponse> {
+            self.head(url).await
+        }
+    }
+
+    fn fast_and_slow_sites() -> Vec<Arc<dyn Site>> {
+        ["https://fast.example/{}", "https://slow.example/{}"]
+            .iter()
+            .map(|pattern| {
+                let site = crate::sites::declarative::DeclarativeSite::new(
+                    crate::sites::declarative::SiteDefinition::new(
+                        pattern, // unique name per site
+                        SiteType::Other,
+                        pattern,
+                    ),
+                )
+                .unwrap();
+                Arc::new(site) as Arc<dyn Site>
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_scan_username_request_timeout() {
+        let options = ScanOptions {
+            timeout: Duration::from_millis(50),
+            ..ScanOptions::default()
+        };
+        let results = scan_username_with_options(
+            "user",
+            fast_and_slow_sites(),
+            Some(Arc::new(SlowRequest)),
+            &options,
+        )
+        .await
+        .unwrap();
+        assert_eq!(results.len(), 2);
+
+        let slow = results.iter().find(|r| r.site.contains("slow")).unwrap();
+        assert_eq!(slow.outcome, Outcome::TimedOut);
+        assert!(!slow.exists());
+        assert_eq!(slow.attempts, 1);
+        let fast = results.iter().find(|r| r.site.contains("fast")).unwrap();
+        assert!(fast.exists());
+    }
+
+    #[tokio::test]
+    async fn test_scan_username_deadline() {
+        let options = ScanOptions {
+            deadline: Some(Duration::from_millis(100)),
+            ..ScanOptions::default()
+        };
+        let started = std::time::Instant::now();
+        let results = scan_username_with_options(
+            "user",
+            fast_and_slow_sites(),
+            Some(Arc::new(SlowRequest)),
+            &options,
+        )
+        .await
+        .unwrap();
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert_eq!(results.len(), 2);
+
+        let slow = results.iter().find(|r| r.site.contains("slow")).unwrap();
+        assert_eq!(slow.outcome, Outcome::TimedOut);
+        let fast = results.iter().find(|r| r.site.contains("fast")).unwrap();
+        assert!(fast.exists());
+    }
+
+    /// Request that records the largest number of requests in flight at once
+    #[derive(Default)]
+    struct InFlightRequest {
+        current: std::sync::atomic::AtomicUsize,
+        max: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl crate::request::Request for InFlightRequest {
+        async fn head(&self, _url: &str) -> Result<crate::request::RequestResponse> {
+            use std::sync::atomic::Ordering::SeqCst;
+            let now = self.current.fetch_add(1, SeqCst) + 1;
+            self.max.fetch_max(now, SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.current.fetch_sub(1, SeqCst);
+            Ok(crate::request::RequestResponse::new(200))
+        }
+
+        async fn get(&self, url: &str) -> Result<crate::request::RequestResponse> {
+            self.head(url).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scan_username_per_host_limit() {
+        let sites: Vec<Arc<dyn Site>> = (0..6)
+            .map(|i| {
+                let site = crate::sites::declarative::DeclarativeSite::new(
+                    crate::sites::declarative::SiteDefinition::new(
+                        &format!("Example {}", i),
+                        SiteType::Other,
+                        &format!("https://example.com/{}/{{}}", i),
+                    ),
+                )
+                .unwrap();
+                Arc::new(site) as Arc<dyn Site>
+            })
+            .collect();
+        let options = ScanOptions {
+            limits: ConnectionLimits::new(32, 2),
+            ..ScanOptions::default()
+        };
+        let request = Arc::new(InFlightRequest::default());
+
+        let results = scan_username_with_options("user", sites, Some(request.clone()), &options)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 6);
+        assert!(results.iter().all(|r| r.exists()));
+        assert_eq!(request.max.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    /// Request that is throttled with a `Retry-After` for its first `throttled` calls
+    struct ThrottledRequest {
+        retry_after: &'static str,
+        throttled: usize,
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    impl ThrottledRequest {
+        fn new(retry_after: &'static str, throttled: usize) -> Self {
+            Self {
+                retry_after,
+                throttled,
+                calls: Default::default(),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl crate::request::Request for ThrottledRequest {
+        async fn head(&self, _url: &str) -> Result<crate::request::RequestResponse> {
+            let call = self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if call >= self.throttled {
+                return Ok(crate::request::RequestResponse::new(200));
+            }
+            Ok(crate::request::RequestResponse {
+                headers: vec![("Retry-After".to_string(), self.retry_after.to_string())],
+                ..crate::request::RequestResponse::new(429)
+            })
+        }
+
+        async fn get(&self, url: &str) -> Result<crate::request::RequestResponse> {
+            self.head(url).await
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_scan_username_pauses_throttled_host() {
+        let options = retry_options(3);
+        let results = scan_username_with_options(
+            "user",
+            example_site(),
+            Some(Arc::new(ThrottledRequest::new("120", usize::MAX))),
+            &options,
+        )
+        .await
+        .unwrap();
+        assert!(!results[0].exists());
+        // Two minutes is more than a site may wait in total, so no retry
+        assert_eq!(results[0].attempts, 1);
+
+        // The engine-wide limiter now holds off the host
+        let paused = options
+            .rate_limiter
+            .paused_for("https://example.com/other")
+            .unwrap();
+        assert!(paused > Duration::from_secs(100));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_scan_username_waits_out_short_pauses() {
+        let request = Arc::new(ThrottledRequest::new("5", 1));
+        let started = Instant::now();
+        let results = scan_username_with_options(
+            "user",
+            example_site(),
+            Some(request.clone()),
+            &retry_options(3),
+        )
+        .await
+        .unwrap();
+        assert!(results[0].exists());
+        assert_eq!(results[0].attempts, 2);
+        assert!(started.elapsed() >= Duration::from_secs(5));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_scan_username_caps_total_pause() {
+        // 25s pauses: the third would take the site past its 60s in total
+        let request = Arc::new(ThrottledRequest::new("25", usize::MAX));
+        let results = scan_username_with_options(
+            "user",
+            example_site(),
+            Some(request.clone()),
+            &retry_options(5),
+        )
+        .await
+        .unwrap();
+        assert_eq!(results[0].outcome, Outcome::RateLimited);
+        assert_eq!(results[0].attempts, 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_scan_username_stops_retrying_past_deadline() {
+        let options = ScanOptions {
+            deadline: Some(Duration::from_secs(30)),
+            ..retry_options(3)
+        };
+        let results = scan_username_with_options(
+            "user",
+            example_site(),
+            Some(Arc::new(ThrottledRequest::new("45", 1))),
+            &options,
+        )
+        .await
+        .unwrap();
+        assert_eq!(results[0].outcome, Outcome::RateLimited);
+        assert_eq!(results[0].attempts, 1);
+    }
+
+    /// Request for a site that answers 200 to every username, with a richer
+    /// page for the users that exist
+    struct SoftNotFoundRequest {
+        existing: &'static [&'static str],
+    }
+
+    #[async_trait::async_trait]
+    impl crate::request::Request for SoftNotFoundRequest {
+        async fn head(&self, _url: &str) -> Result<crate::request::RequestResponse> {
+            Ok(crate::request::RequestResponse::new(200))
+        }
+
+        async fn get(&self, url: &str) -> Result<crate::request::RequestResponse> {
+            let name = url.rsplit('/').next().unwrap_or_default();
+            let main = if self.existing.contains(&name) {
+                format!(
+                    "<main><h1>{}</h1><ul><li>Posts</li><li>Followers</li></ul></main>",
+                    name
+                )
+            } else {
+                format!("<div><p>Sorry, {} does not exist</p></div>", name)
+            };
+            Ok(crate::request::RequestResponse::with_body(
+                200,
+                format!(
+                    "<html><head><title>{} on Example</title></head><body>{}</body></html>",
+                    name, main
+                ),
+            ))
+        }
+    }
+
+    fn calibrated_options() -> ScanOptions {
+        ScanOptions {
+            calibrate: true,
+            ..ScanOptions::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scan_username_calibrates_soft_404() {
+        let mut definition = crate::sites::declarative::SiteDefinition::new(
+            "Example",
+            SiteType::Other,
+            "https://example.com/{}",
+        );
+        definition.method = "GET".to_string();
+        let sites: Vec<Arc<dyn Site>> = vec![Arc::new(
+            crate::sites::declarative::DeclarativeSite::new(definition).unwrap(),
+        )];
+        let request = Arc::new(SoftNotFoundRequest {
+            existing: &["blue"],
+        });
+
+        let results = scan_username_with_options(
+            "ghost",
+            sites.clone(),
+            Some(request.clone()),
+            &calibrated_options(),
+        )
+        .await
+        .unwrap();
+        let result = &results[0];
+        assert_eq!(result.outcome, Outcome::NotFound);
+        assert_eq!(result.confidence, CALIBRATED_CONFIDENCE);
+        assert!(
+            result.reason.contains("looks like random control"),
+            "{}",
+            result.reason
+        );
+        // The control request does not count as an attempt at the probe
+        assert_eq!(result.attempts, 1);
+        assert!(result.evidence.is_some());
+
+        let results =
+            scan_username_with_options("blue", sites, Some(request), &calibrated_options())
+                .await
+                .unwrap();
+        let result = &results[0];
+        assert!(result.exists());
+        assert!(
+            result.reason.contains("differs from control"),
+            "{}",
+            result.reason
+        );
+        assert_eq!(result.attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_scan_username_calibration_is_optional() {
+        let request = Arc::new(CountingRequest {
+            calls: Default::default(),
+        });
+        // A catch-all site: without calibration every username exists
+        let results = scan_username("ghost", example_site(), Some(request.clone()), false)
+            .await
+            .unwrap();
+        assert!(results[0].exists());
+        assert_eq!(request.calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        let results = scan_username_with_options(
+            "ghost",
+            example_site(),
+            Some(request.clone()),
+            &calibrated_options(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(results[0].outcome, Outcome::NotFound);
+        assert_eq!(request.calls.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_scan_username_retries_calibration_control() {
+        // The probe hits, the control gets a 503 once and then looks the same
+        let request = Arc::new(SequenceRequest {
+            statuses: vec![200, 503, 200],
+            calls: Default::default(),
+        });
+        let options = ScanOptions {
+            calibrate: true,
+            ..retry_options(2)
+        };
+        let results =
+            scan_username_with_options("ghost", example_site(), Some(request.clone()), &options)
+                .await
+                .unwrap();
+        assert_eq!(results[0].outcome, Outcome::NotFound);
+        assert_eq!(results[0].attempts, 1);
+        assert_eq!(request.calls.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    /// Request answering the first call with a plain 200 and every later one
+    /// with a Cloudflare challenge
+    struct ChallengedControlRequest {
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl crate::request::Request for ChallengedControlRequest {
+        async fn head(&self, _url: &str) -> Result<crate::request::RequestResponse> {
+            if self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == 0 {
+                return Ok(crate::request::RequestResponse::new(200));
+            }
+            Ok(crate::request::RequestResponse {
+                headers: vec![("cf-mitigated".to_string(), "challenge".to_string())],
+                ..crate::request::RequestResponse::new(200)
+            })
+        }
+
+        async fn get(&self, url: &str) -> Result<crate::request::RequestResponse> {
+            self.head(url).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scan_username_blocked_control_is_inconclusive() {
+        let request = Arc::new(ChallengedControlRequest {
+            calls: Default::default(),
+        });
+        let results = scan_username_with_options(
+            "ghost",
+            example_site(),
+            Some(request),
+            &calibrated_options(),
+        )
+        .await
+        .unwrap();
+        let result = &results[0];
+        // A challenged control says nothing about the probe: the hit is kept
+        assert!(result.exists());
+        assert!(
+            result.reason.contains("calibration inconclusive") && result.reason.contains("blocked"),
+            "{}",
+            result.reason
+        );
+        assert_eq!(result.attempts, 1);
+    }
+
+    /// Request answering every URL with a Cloudflare challenge
+    struct ChallengeRequest {
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl crate::request::Request for ChallengeRequest {
+        async fn head(&self, _url: &str) -> Result<crate::request::RequestResponse> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(crate::request::RequestResponse {
+                headers: vec![("cf-mitigated".to_string(), "challenge".to_string())],
+                ..crate::request::RequestResponse::new(200)
+            })
+        }
+
+        async fn get(&self, url: &str) -> Result<crate::request::RequestResponse> {
+            self.head(url).await
+        }
+    }
+
+    /// Request that records every spec it is sent and answers 200
+    #[derive(Default)]
+    struct SpecRequest {
+        sent: std::sync::Mutex<Vec<RequestSpec>>,
+    }
+
+    #[async_trait::async_trait]
+    impl crate::request::Request for SpecRequest {
+        async fn head(&self, url: &str) -> Result<crate::request::RequestResponse> {
+            self.send(&RequestSpec::head(url)).await
+        }
+
+        async fn get(&self, url: &str) -> Result<crate::request::RequestResponse> {
+            self.send(&RequestSpec::get(url)).await
+        }
+
+        async fn send(&self, spec: &RequestSpec) -> Result<crate::request::RequestResponse> {
+            self.sent.lock().unwrap().push(spec.clone());
+            Ok(crate::request::RequestResponse::with_body(
+                200,
+                "{}".to_string(),
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scan_username_sends_site_request() {
+        let mut definition = crate::sites::declarative::SiteDefinition::new(
+            "Signup",
+            SiteType::Other,
+            "https://example.com/{}",
+        );
+        definition.probe_url = Some("https://api.example.com/available".to_string());
+        definition.method = "POST".to_string();
+        definition
+            .cookies
+            .insert("consent".to_string(), "yes".to_string());
+        definition.body = Some(crate::sites::declarative::RequestBody::Json(
+            serde_json::json!({ "username": "{}" }),
+        ));
+        definition.timeout = Some(20);
+        let sites: Vec<Arc<dyn Site>> = vec![Arc::new(
+            crate::sites::declarative::DeclarativeSite::new(definition).unwrap(),
+        )];
+        let request = Arc::new(SpecRequest::default());
+
+        let results = scan_username("blue", sites, Some(request.clone()), false)
+            .await
+            .unwrap();
+        let result = &results[0];
+        assert!(result.exists());
+        assert_eq!(result.url.as_deref(), Some("https://example.com/blue"));
+        assert!(result.reason.contains("HTTP POST"), "{}", result.reason);
+        assert_eq!(result.evidence.as_ref().unwrap().method, "POST");
+
+        let sent = request.sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].url, "https://api.example.com/available");
+        assert_eq!(sent[0].body.as_deref(), Some(r#"{"username":"blue"}"#));
+        assert_eq!(sent[0].header("content-type"), Some("application/json"));
+        assert_eq!(sent[0].cookie_header().as_deref(), Some("consent=yes"));
+        assert_eq!(sent[0].timeout, Some(Duration::from_secs(20)));
+
+        // Sites without a timeout get the scan's
+        let spec = probe_request(&*example_site()[0], "blue", &ScanOptions::default());
+        assert_eq!(spec.timeout, Some(ScanOptions::default().timeout));
+    }
+
+    #[tokio::test]
+    async fn test_scan_username_reports_block_pages() {
+        let request = Arc::new(ChallengeRequest {
+            calls: Default::default(),
+        });
+        // The status alone would make the username exist on this site
+        let results = scan_username_with_options(
+            "blue",
+            example_site(),
+            Some(request.clone()),
+            &retry_options(1),
+        )
+        .await
+        .unwrap();
+        let result = &results[0];
+        assert_eq!(result.outcome, Outcome::Blocked);
+        assert_eq!(
+            result.protection,
+            Some(crate::request::Protection::Cloudflare)
+        );
+        assert_eq!(
+            result.error.as_deref(),
+            Some("blocked by Cloudflare (cf-mitigated: challenge)")
+        );
+        assert_eq!(result.confidence, 0);
+        assert!(result.evidence.is_some());
+        assert!(result.profile.is_none());
+
+        // Block pages are retried like other transient answers
+        assert_eq!(result.attempts, 2);
+        assert_eq!(request.calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_scan_username_stream_yields_incrementally() {
+        let mut stream = scan_username_stream(
+            "user",
+            fast_and_slow_sites(),
+            Some(Arc::new(SlowRequest)),
+            &ScanOptions::default(),
+        )
+        .unwrap();
+
+        // The fast site arrives long before the slow one is done
+        let first = tokio::time::timeout(Duration::from_secs(1), stream.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(first.site.contains("fast"));
+        assert!(first.exists());
+        assert!(!first.verified);
+    }
+
+    #[test]
+    fn test_merge_result() {
+        let mut results = vec![SearchResult::found(
+            "OnlyFans".to_string(),
+            "user".to_string(),
+            "https://onlyfans.com/user".to_string(),
+        )];
+        merge_result(
+            &mut results,
+            SearchResult::not_found("GitHub".to_string(), "user".to_string()),
+        );
+        assert_eq!(results.len(), 2);
+
+        // A verification update replaces the first-pass result
+        merge_result(
+            &mut results,
+            SearchResult::not_found("OnlyFans".to_string(), "user".to_string()).into_verified(),
+        );
+        assert_eq!(results.len(), 2);
+        assert!(!results[0].exists());
+        assert!(results[0].verified);
+    }
+
+    #[tokio::test]
+    async fn test_scan_username_inconclusive_outcomes() {
+        for (status, outcome) in [
+            (403, Outcome::Blocked),
+            (429, Outcome::RateLimited),
+            (503, Outcome::Error),
+            (302, Outcome::Uncertain),
+        ] {
+            let request = Arc::new(SequenceRequest {
+                statuses: vec![status],
+                calls: Default::default(),
+            });
+            let results = scan_username_with_options(
+                "user",
+                example_site(),
+                Some(request),
+                &ScanOptions::default(),
+            )
+            .await
+            .unwrap();
+            assert_eq!(results[0].outcome, outcome, "HTTP {}", status);
+            assert!(!results[0].exists());
+            assert_eq!(
+                results[0].error.as_deref(),
+                Some(format!("HTTP {}", status).as_str())
+            );
+            assert_eq!(results[0].confidence, 0);
+            assert!(results[0].reason.starts_with("no indicator matched"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scan_username_explains_results() {
+        use crate::sites::declarative::{DeclarativeSite, SiteDefinition};
+        use crate::sites::detection::{Condition, Rule, RuleSet, Verdict};
+
+        async fn scan(definition: SiteDefinition, status: u16) -> SearchResult {
+            let request = Arc::new(SequenceRequest {
+                statuses: vec![status],
+                calls: Default::default(),
+            });
+            let sites: Vec<Arc<dyn Site>> =
+                vec![Arc::new(DeclarativeSite::new(definition).unwrap())];
+            scan_username("user", sites, Some(request), false)
+                .await
+                .unwrap()
+                .remove(0)
+        }
+
+        let plain = SiteDefinition::new("Plain", SiteType::Other, "https://example.com/{}");
+        let mut ruled = plain.clone();
+        ruled.rules = RuleSet::new(vec![Rule::new(
+            "missing",
+            Verdict::NotFound,
+            vec![Condition::status(404)],
+        )]);
+        let mut browser = ruled.clone();
+        browser.requires_browser = true;
+
+        let result = scan(plain, 404).await;
+        assert_eq!(result.confidence, CHECKER_CONFIDENCE);
+        assert_eq!(
+            result.reason,
+            "site checker read HTTP 404 (first pass, HTTP HEAD)"
+        );
+
+        let result = scan(ruled, 404).await;
+        assert_eq!(result.confidence, STATUS_CONFIDENCE);
+        assert_eq!(
+            result.reason,
+            "rule 'missing' matched HTTP 404 (first pass, HTTP HEAD)"
+        );
+
+        // A bare 2xx from a browser-only site is a weak hit
+        let result = scan(browser, 200).await;
+        assert!(result.exists());
+        assert_eq!(result.confidence, UNVERIFIED_CONFIDENCE);
+        assert!(result.reason.contains("not checked in a browser"));
+    }
+
+    #[tokio::test]
+    async fn test_scan_username_records_evidence() {
+        let request = Arc::new(SequenceRequest {
+            statuses: vec![404],
+            calls: Default::default(),
+        });
+        let results = scan_username("user", example_site(), Some(request), false)
+            .await
+            .unwrap();
+        let evidence = results[0].evidence.as_ref().unwrap();
+        assert_eq!(evidence.method, "HEAD");
+        assert_eq!(evidence.url, "https://example.com/user");
+        assert_eq!(evidence.status_code, 404);
+        assert_eq!(evidence.transport, RequestType::Http);
+        assert!(evidence.body_sha256.is_none());
+
+        let json = serde_json::to_value(&results[0]).unwrap();
+        assert_eq!(json["evidence"]["status_code"], 404);
+    }
+
+    #[tokio::test]
+    async fn test_scan_username_extracts_profile() {
+        use crate::request::{FixtureRequest, FixtureSet, RequestResponse};
+        use crate::sites::dev::github::GitHubChecker;
+
+        let mut fixtures = FixtureSet::default();
+        fixtures.insert(
+            "GET",
+            "https://github.com/blue",
+            RequestResponse::with_body(
+                200,
+                r#"<span class="p-name vcard-fullname" itemprop="name">Blue</span>"#.to_string(),
+            ),
+        );
+        fixtures.insert(
+            "GET",
+            "https://github.com/ghost",
+            RequestResponse::with_body(404, "Not Found".to_string()),
+        );
+        let request = Arc::new(FixtureRequest::new(fixtures));
+        let sites = || -> Vec<Arc<dyn Site>> { vec![Arc::new(GitHubChecker::new())] };
+
+        let found = scan_username("blue", sites(), Some(request.clone()), false)
+            .await
+            .unwrap();
+        let profile = found[0].profile.as_ref().unwrap();
+        assert_eq!(profile.name.as_deref(), Some("Blue"));
+
+        let missing = scan_username("ghost", sites(), Some(request), false)
+            .await
+            .unwrap();
+        assert_eq!(missing[0].outcome, Outcome::NotFound);
+        assert!(missing[0].profile.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_scan_username_reads_page_metadata() {
+        use crate::request::{FixtureRequest, FixtureSet, RequestResponse};
+        use crate::sites::other::medium::MediumChecker;
+
+        let body = r#"<html><head>
+            <meta property="og:title" content="Blue – Medium">
+            <meta property="og:image" content="https://cdn.example/blue.png">
+        </head><body>Blue's stories</body></html>"#;
+        let mut fixtures = FixtureSet::default();
+        fixtures.insert(
+            "GET",
+            "https://medium.com/@blue",
+            RequestResponse::with_body(200, body.to_string()),
+        );
+        let request = Arc::new(FixtureRequest::new(fixtures));
+        let sites: Vec<Arc<dyn Site>> = vec![Arc::new(MediumChecker::new())];
+
+        let results = scan_username("blue", sites, Some(request), false)
+            .await
+            .unwrap();
+        assert!(results[0].exists(), "{:?}", results[0]);
+        let profile = results[0].profile.as_ref().unwrap();
+        assert_eq!(profile.name.as_deref(), Some("Blue – Medium"));
+        assert_eq!(
+            profile.avatar_url.as_deref(),
+            Some("https://cdn.example/blue.png")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scan_username_reports_request_errors() {
+        // Nothing recorded: every request fails
+        let request = Arc::new(crate::request::FixtureRequest::new(
+            crate::request::FixtureSet::default(),
+        ));
+        let results = scan_username("user", example_site(), Some(request), false)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].outcome, Outcome::Error);
+        assert!(results[0].error.is_some());
+        assert!(results[0].evidence.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_scan_username_deadline_skips_queued_sites() {
+        // One request at a time: the second slow site never gets to send its request
+        let mut sites = fast_and_slow_sites();
+        sites.retain(|site| site.name().contains("slow"));
+        sites.push(Arc::new(
+            crate::sites::declarative::DeclarativeSite::new(
+                crate::sites::declarative::SiteDefinition::new(
+                    "Queued",
+                    SiteType::Other,
+                    "https://slow.example/queued/{}",
+                ),
+            )
+            .unwrap(),
+        ));
+        let options = ScanOptions {
+            deadline: Some(Duration::from_millis(100)),
+            limits: ConnectionLimits::new(1, 1),
+            ..ScanOptions::default()
+        };
+        let results =
+            scan_username_with_options("user", sites, Some(Arc::new(SlowRequest)), &options)
+                .await
+                .unwrap();
+        let mut outcomes: Vec<Outcome> = results.iter().map(|r| r.outcome).collect();
+        outcomes.sort_by_key(|o| o.as_str());
+        assert_eq!(outcomes, vec![Outcome::Skipped, Outcome::TimedOut]);
+    }
 }
@@ -0,0 +1,404 @@
+//! Site self-test using known claimed and unclaimed usernames
+
+use crate::core::options::ScanOptions;
+use crate::core::result::Outcome;
+use crate::core::scanner::scan_username_with_options;
+use crate::request::Request;
+use crate::sites::Site;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::task::{Id, JoinSet};
+
+/// Username assumed not to exist on sites that do not declare one
+pub const DEFAULT_UNCLAIMED_USERNAME: &str = "noonewouldeverusethis7";
+
+/// Outcome of checking one username against one site
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    /// The site gave the expected answer
+    Pass,
+    /// The site gave the wrong answer
+    Fail,
+    /// The check gave no definite answer (request error, block, timeout)
+    Error,
+}
+
+/// Result of checking one username against one site
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsernameCheck {
+    /// Username that was checked
+    pub username: String,
+    /// Whether the username is expected to exist
+    pub expected_exists: bool,
+    /// What the checker reported (None if it gave no definite answer)
+    pub actual_exists: Option<bool>,
+    /// Outcome of the check (None if it produced no result)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<Outcome>,
+    /// Detection rule that decided, for rule-based sites
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+    pub status: CheckStatus,
+}
+
+/// Self-test report for one site
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SiteTestReport {
+    pub site: String,
+    /// Check with the known claimed username (None if the site has none)
+    pub claimed: Option<UsernameCheck>,
+    /// Check with the known unclaimed username
+    pub unclaimed: Option<UsernameCheck>,
+}
+
+impl SiteTestReport {
+    /// Whether every check that ran passed
+    pub fn passed(&self) -> bool {
+        self.checks().all(|c| c.status == CheckStatus::Pass)
+    }
+
+    /// Whether no checks ran
+    pub fn untested(&self) -> bool {
+        self.checks().next().is_none()
+    }
+
+    fn checks(&self) -> impl Iterator<Item = &UsernameCheck> {
+        self.claimed.iter().chain(self.unclaimed.iter())
+    }
+}
+
+/// Machine-readable summary of a self-test run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelfTestSummary {
+    /// Sites whose checks all passed
+    pub passed: usize,
+    /// Sites with at least one wrong answer or error
+    pub failed: usize,
+    /// Sites without a known claimed username
+    pub untested: usize,
+    /// Per-site reports, sorted by site name
+    pub sites: Vec<SiteTestReport>,
+}
+
+impl SelfTestSummary {
+    /// Build a summary from per-site reports
+    pub fn from_reports(mut sites: Vec<SiteTestReport>) -> Self {
+        sites.sort_by_key(|r| r.site.to_lowercase());
+        let untested = sites.iter().filter(|r| r.untested()).count();
+        let passed = sites.iter().filter(|r| !r.untested() && r.passed()).count();
+        Self {
+            passed,
+            failed: sites.len() - untested - passed,
+            untested,
+            sites,
+        }
+    }
+
+    /// Reports of sites that gave a wrong answer or errored
+    pub fn failures(&self) -> impl Iterator<Item = &SiteTestReport> {
+        self.sites.iter().filter(|r| !r.untested() && !r.passed())
+    }
+}
+
+/// Run every site against its known claimed and unclaimed usernames
+///
+/// Checks go through [`scan_username_with_options`] with `options`, so
+/// username rules, probe URLs, detection, timeouts, retries, connection
+/// limits and rate limits behave exactly as in a normal search. Sites without
+/// a claimed username are reported as untested; sites without an unclaimed
+/// username are checked with [`DEFAULT_UNCLAIMED_USERNAME`] if they allow it.
+pub async fn test_sites(
+    sites: Vec<Arc<dyn Site>>,
+    request: Arc<dyn Request>,
+    options: &ScanOptions,
+) -> SelfTestSummary {
+    let mut tasks: JoinSet<SiteTestReport> = JoinSet::new();
+    // Site of each task, to report the ones that crash
+    let mut testing: HashMap<Id, Arc<dyn Site>> = HashMap::new();
+
+    for site in sites {
+        let request = Arc::clone(&request);
+        let options = options.clone();
+        let tested = Arc::clone(&site);
+        let task = tasks.spawn(async move {
+            let claimed = match site.claimed_username() {
+                Some(username) => Some(check(&site, username, true, &request, &options).await),
+                None => None,
+            };
+
+            let unclaimed_username = site.unclaimed_username().or_else(|| {
+                Some(DEFAULT_UNCLAIMED_USERNAME).filter(|username| site.validate_username(username))
+            });
+            let unclaimed = match (claimed.is_some(), unclaimed_username) {
+                (true, Some(username)) => {
+                    Some(check(&site, username, false, &request, &options).await)
+                }
+                _ => None,
+            };
+
+            SiteTestReport {
+                site: site.name().to_string(),
+                claimed,
+                unclaimed,
+            }
+        });
+        testing.insert(task.id(), tested);
+    }
+
+    let mut reports = Vec::new();
+    while let Some(res) = tasks.join_next().await {
+        match res {
+            Ok(report) => reports.push(report),
+            Err(e) => {
+                if let Some(site) = testing.get(&e.id()) {
+                    reports.push(crashed(site.as_ref()));
+                }
+            }
+        }
+    }
+
+    SelfTestSummary::from_reports(reports)
+}
+
+/// Report for a site whose self-test task crashed: its claimed check errored
+fn crashed(site: &dyn Site) -> SiteTestReport {
+    SiteTestReport {
+        site: site.name().to_string(),
+        claimed: Some(UsernameCheck {
+            username: site.claimed_username().unwrap_or_default().to_string(),
+            expected_exists: true,
+            actual_exists: None,
+            outcome: None,
+            rule: None,
+            status: CheckStatus::Error,
+        }),
+        unclaimed: None,
+    }
+}
+
+async fn check(
+    site: &Arc<dyn Site>,
+    username: &str,
+    expected_exists: bool,
+    request: &Arc<dyn Request>,
+    options: &ScanOptions,
+) -> UsernameCheck {
+    let result = scan_username_with_options(
+        username,
+        vec![Arc::clone(site)],
+        Some(Arc::clone(request)),
+        options,
+    )
+    .await
+    .ok()
+    .and_then(|results| results.into_iter().next());
+
+    let (actual_exists, outcome, rule, status) = match result {
+        Some(result) => {
+            let status = match result.outcome {
+                Outcome::Found | Outcome::NotFound if result.exists() == expected_exists => {
+                    CheckStatus::Pass
+                }
+                // A wrong answer, or a username the checker wrongly rejects
+                Outcome::Found | Outcome::NotFound | Outcome::InvalidUsername => CheckStatus::Fail,
+                _ => CheckStatus::Error,
+            };
+            let actual_exists = result.outcome.is_conclusive().then(|| result.exists());
+            (actual_exists, Some(result.outcome), result.rule, status)
+        }
+        None => (None, None, None, CheckStatus::Error),
+    };
+
+    UsernameCheck {
+        username: username.to_string(),
+        expected_exists,
+        actual_exists,
+        outcome,
+        rule,
+        status,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::site_info::SiteType;
+    use crate::request::{FixtureRequest, FixtureSet, RequestResponse};
+
+    struct KnownSite {
+        name: &'static str,
+        claimed: Option<&'static str>,
+    }
+
+    impl Site for KnownSite {
+        fn name(&self) -> &str {
+            self.name
+        }
+        fn url_pattern(&self) -> &str {
+            "https://known.example/{}"
+        }
+        fn site_type(&self) -> SiteType {
+            SiteType::Other
+        }
+        fn claimed_username(&self) -> Option<&str> {
+            self.claimed
+        }
+        fn unclaimed_username(&self) -> Option<&str> {
+            Some("ghost")
+        }
+    }
+
+    fn fixtures(ghost_status: u16) -> Arc<dyn Request> {
+        let mut fixtures = FixtureSet::default();
+        fixtures.insert(
+            "HEAD",
+            "https://known.example/blue",
+            RequestResponse::new(200),
+        );
+        fixtures.insert(
+            "HEAD",
+            "https://known.example/ghost",
+            RequestResponse::new(ghost_status),
+        );
+        Arc::new(FixtureRequest::new(fixtures))
+    }
+
+    fn sites() -> Vec<Arc<dyn Site>> {
+        vec![
+            Arc::new(KnownSite {
+                name: "Known",
+                claimed: Some("blue"),
+            }),
+            Arc::new(KnownSite {
+                name: "Unknown",
+                claimed: None,
+            }),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_sites_pass() {
+        let summary = test_sites(sites(), fixtures(404), &ScanOptions::default()).await;
+        assert_eq!(summary.passed, 1);
+        assert_eq!(summary.failed, 0);
+        assert_eq!(summary.untested, 1);
+
+        let known = &summary.sites[0];
+        assert_eq!(known.site, "Known");
+        assert_eq!(known.claimed.as_ref().unwrap().status, CheckStatus::Pass);
+        assert_eq!(known.unclaimed.as_ref().unwrap().username, "ghost");
+    }
+
+    #[tokio::test]
+    async fn test_sites_reports_wrong_answer() {
+        // The unclaimed username "exists": the checker gives a false positive
+        let summary = test_sites(sites(), fixtures(200), &ScanOptions::default()).await;
+        assert_eq!(summary.failed, 1);
+
+        let failure = summary.failures().next().unwrap();
+        let unclaimed = failure.unclaimed.as_ref().unwrap();
+        assert_eq!(unclaimed.status, CheckStatus::Fail);
+        assert_eq!(unclaimed.actual_exists, Some(true));
+    }
+
+    #[tokio::test]
+    async fn test_sites_reports_errors() {
+        let request: Arc<dyn Request> = Arc::new(FixtureRequest::new(FixtureSet::default()));
+        let summary = test_sites(sites(), request, &ScanOptions::default()).await;
+        assert_eq!(summary.failed, 1);
+        let known = &summary.sites[0];
+        assert_eq!(known.claimed.as_ref().unwrap().status, CheckStatus::Error);
+    }
+
+    /// Site whose self-test crashes after its claimed check
+    struct CrashingSite;
+
+    impl Site for CrashingSite {
+        fn name(&self) -> &str {
+            "Crashing"
+        }
+        fn url_pattern(&self) -> &str {
+            "https://known.example/{}"
+        }
+        fn site_type(&self) -> SiteType {
+            SiteType::Other
+        }
+        fn claimed_username(&self) -> Option<&str> {
+            Some("blue")
+        }
+        fn unclaimed_username(&self) -> Option<&str> {
+            panic!("broken site definition")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sites_reports_crashed_tasks() {
+        let sites: Vec<Arc<dyn Site>> = vec![Arc::new(CrashingSite)];
+        let summary = test_sites(sites, fixtures(404), &ScanOptions::default()).await;
+        assert_eq!(summary.failed, 1);
+        let crashed = summary.failures().next().unwrap();
+        assert_eq!(crashed.site, "Crashing");
+        let claimed = crashed.claimed.as_ref().unwrap();
+        assert_eq!(claimed.username, "blue");
+        assert_eq!(claimed.status, CheckStatus::Error);
+    }
+
+    /// Request whose first call is a 503, then answers like `fixtures(404)`
+    struct FlakyRequest {
+        inner: Arc<dyn Request>,
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl Request for FlakyRequest {
+        async fn head(&self, url: &str) -> crate::utils::error::Result<RequestResponse> {
+            if self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == 0 {
+                return Ok(RequestResponse::new(503));
+            }
+            self.inner.head(url).await
+        }
+
+        async fn get(&self, url: &str) -> crate::utils::error::Result<RequestResponse> {
+            self.head(url).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sites_uses_scan_options() {
+        let flaky = || -> Arc<dyn Request> {
+            Arc::new(FlakyRequest {
+                inner: fixtures(404),
+                calls: Default::default(),
+            })
+        };
+        let summary = test_sites(sites(), flaky(), &ScanOptions::default()).await;
+        assert_eq!(summary.failed, 1);
+
+        // With retries, the 503 is retried like in a normal search
+        let options = ScanOptions {
+            retry: crate::core::options::RetryPolicy {
+                base_delay: std::time::Duration::from_millis(1),
+                ..crate::core::options::RetryPolicy::new(1)
+            },
+            ..ScanOptions::default()
+        };
+        let summary = test_sites(sites(), flaky(), &options).await;
+        assert_eq!(summary.passed, 1);
+        let claimed = summary.sites[0].claimed.as_ref().unwrap();
+        assert_eq!(claimed.status, CheckStatus::Pass);
+    }
+
+    #[test]
+    fn test_summary_serializes() {
+        let summary = SelfTestSummary::from_reports(vec![SiteTestReport {
+            site: "Known".to_string(),
+            claimed: None,
+            unclaimed: None,
+        }]);
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["untested"], 1);
+        assert_eq!(json["sites"][0]["site"], "Known");
+    }
+}
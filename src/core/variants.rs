@@ -0,0 +1,398 @@
+//! Username variants expanded from a seed
+//!
+//! People rarely use the exact same handle everywhere. [`generate_variants`]
+//! expands a seed into ranked candidates: sherlock-style `{?}` placeholders,
+//! first/last-name combinations, digit and birth-year suffixes and
+//! leetspeak. `Engine::search_variants` scans them and reports which variant
+//! matched on which site.
+
+use crate::core::result::SearchResult;
+use serde::{Deserialize, Serialize};
+
+/// Placeholder replaced by each of [`PLACEHOLDER_SEPARATORS`]
+pub const PLACEHOLDER: &str = "{?}";
+
+/// Separators a `{?}` placeholder expands to, as in sherlock
+pub const PLACEHOLDER_SEPARATORS: &[&str] = &["_", "-", "."];
+
+/// Digit suffixes people commonly add to a taken handle
+pub const DIGIT_SUFFIXES: &[&str] = &["1", "123", "01", "2"];
+
+/// How a seed is expanded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariantOptions {
+    /// Maximum number of variants, the seed included
+    pub max_variants: usize,
+    /// Full name to combine first and last names from (e.g. "Jane Doe")
+    pub full_name: Option<String>,
+    /// Birth year to add as a suffix, in full and as two digits
+    pub birth_year: Option<u16>,
+    /// Add digit suffixes
+    pub suffixes: bool,
+    /// Add leetspeak spellings
+    pub leetspeak: bool,
+}
+
+impl Default for VariantOptions {
+    fn default() -> Self {
+        Self {
+            max_variants: 25,
+            full_name: None,
+            birth_year: None,
+            suffixes: true,
+            leetspeak: true,
+        }
+    }
+}
+
+/// How a variant was derived
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VariantKind {
+    /// The seed as given
+    Seed,
+    /// A `{?}` placeholder filled with a separator
+    Separator,
+    /// First and last names from the full name
+    Name,
+    /// A digit or birth-year suffix
+    Suffix,
+    /// Letters replaced by look-alike digits
+    Leetspeak,
+}
+
+/// A candidate username
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Variant {
+    pub username: String,
+    pub kind: VariantKind,
+    /// How likely the variant is to be used, from 0 to 100; variants are ranked by it
+    pub score: u8,
+}
+
+/// A variant found on a site
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VariantMatch {
+    pub site: String,
+    pub username: String,
+    pub kind: VariantKind,
+    /// Profile URL (None if the site reported none)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// Results of scanning every variant of a seed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VariantReport {
+    pub seed: String,
+    /// Scanned variants, best ranked first
+    pub variants: Vec<Variant>,
+    /// Results of every variant
+    pub results: Vec<SearchResult>,
+    /// Variants found, by site; variants leading to the same profile are listed once
+    pub matches: Vec<VariantMatch>,
+}
+
+impl VariantReport {
+    /// Build a report, matching found results back to their variants
+    pub fn new(seed: &str, variants: Vec<Variant>, results: Vec<SearchResult>) -> Self {
+        // (rank of the variant, profile it leads to, match)
+        let mut matches: Vec<(usize, Option<String>, VariantMatch)> = Vec::new();
+        for result in results.iter().filter(|r| r.exists()) {
+            let Some(rank) = variants.iter().position(|v| v.username == result.username) else {
+                continue;
+            };
+            let found = VariantMatch {
+                site: result.site.clone(),
+                username: result.username.clone(),
+                kind: variants[rank].kind,
+                url: result.url.clone(),
+            };
+            // Sites that ignore case or separators show the same profile for
+            // several variants; keep the best ranked one
+            let profile = result
+                .evidence
+                .as_ref()
+                .map(|evidence| &evidence.final_url)
+                .or(result.url.as_ref())
+                .map(|url| url.to_lowercase());
+            let same_profile = matches.iter_mut().find(|(_, other, m)| {
+                m.site == result.site && profile.is_some() && *other == profile
+            });
+            match same_profile {
+                Some(entry) if rank < entry.0 => *entry = (rank, profile, found),
+                Some(_) => {}
+                None => matches.push((rank, profile, found)),
+            }
+        }
+        matches.sort_by(|(a_rank, _, a), (b_rank, _, b)| {
+            a.site
+                .to_lowercase()
+                .cmp(&b.site.to_lowercase())
+                .then(a_rank.cmp(b_rank))
+        });
+
+        Self {
+            seed: seed.to_string(),
+            variants,
+            results,
+            matches: matches.into_iter().map(|(_, _, found)| found).collect(),
+        }
+    }
+}
+
+/// Expand a seed into ranked, deduplicated candidates, capped at `options.max_variants`
+pub fn generate_variants(seed: &str, options: &VariantOptions) -> Vec<Variant> {
+    let mut variants = Vec::new();
+    let seed = seed.trim();
+
+    if seed.contains(PLACEHOLDER) {
+        for username in expand_placeholders(seed) {
+            push(&mut variants, username, VariantKind::Separator, 90);
+        }
+    } else {
+        push(&mut variants, seed.to_string(), VariantKind::Seed, 100);
+    }
+    if let Some(full_name) = &options.full_name {
+        for (username, score) in name_combinations(full_name) {
+            push(&mut variants, username, VariantKind::Name, score);
+        }
+    }
+
+    // Suffixes and leetspeak apply to the likely bases only
+    let bases: Vec<Variant> = variants.iter().filter(|v| v.score >= 70).cloned().collect();
+    for base in &bases {
+        if let Some(year) = options.birth_year {
+            for suffix in [year.to_string(), format!("{:02}", year % 100)] {
+                let username = format!("{}{}", base.username, suffix);
+                push(
+                    &mut variants,
+                    username,
+                    VariantKind::Suffix,
+                    base.score - 20,
+                );
+            }
+        }
+        if options.suffixes {
+            for suffix in DIGIT_SUFFIXES {
+                let username = format!("{}{}", base.username, suffix);
+                push(
+                    &mut variants,
+                    username,
+                    VariantKind::Suffix,
+                    base.score - 30,
+                );
+            }
+        }
+        if options.leetspeak {
+            let username = leetspeak(&base.username);
+            push(
+                &mut variants,
+                username,
+                VariantKind::Leetspeak,
+                base.score - 40,
+            );
+        }
+    }
+
+    // Stable: equal scores keep the order they were generated in
+    variants.sort_by_key(|v| std::cmp::Reverse(v.score));
+    variants.truncate(options.max_variants);
+    variants
+}
+
+/// Add a variant unless it is empty or already present (case-insensitively)
+fn push(variants: &mut Vec<Variant>, username: String, kind: VariantKind, score: u8) {
+    let known = variants
+        .iter()
+        .any(|v| v.username.eq_ignore_ascii_case(&username));
+    if !username.is_empty() && !known {
+        variants.push(Variant {
+            username,
+            kind,
+            score,
+        });
+    }
+}
+
+/// Every way to fill the `{?}` placeholders of a seed
+fn expand_placeholders(seed: &str) -> Vec<String> {
+    let mut expanded = vec![String::new()];
+    let mut parts = seed.split(PLACEHOLDER).peekable();
+    while let Some(part) = parts.next() {
+        for prefix in expanded.iter_mut() {
+            prefix.push_str(part);
+        }
+        if parts.peek().is_some() {
+            expanded = expanded
+                .iter()
+                .flat_map(|prefix| {
+                    PLACEHOLDER_SEPARATORS
+                        .iter()
+                        .map(move |separator| format!("{}{}", prefix, separator))
+                })
+                .collect();
+        }
+    }
+    expanded
+}
+
+/// Handles built from the first and last names of a full name, with scores
+fn name_combinations(full_name: &str) -> Vec<(String, u8)> {
+    let names: Vec<String> = full_name
+        .split_whitespace()
+        .map(|name| {
+            name.chars()
+                .filter(char::is_ascii_alphanumeric)
+                .collect::<String>()
+                .to_lowercase()
+        })
+        .filter(|name| !name.is_empty())
+        .collect();
+    let (first, last) = match names.as_slice() {
+        [] => return vec![],
+        [only] => return vec![(only.clone(), 60)],
+        [first, .., last] => (first, last),
+    };
+    let initial = |name: &str| name.chars().next().map(String::from).unwrap_or_default();
+
+    vec![
+        (format!("{}{}", first, last), 85),
+        (format!("{}.{}", first, last), 80),
+        (format!("{}_{}", first, last), 80),
+        (format!("{}-{}", first, last), 75),
+        (format!("{}{}", initial(first), last), 75),
+        (format!("{}{}", first, initial(last)), 65),
+        (format!("{}{}", last, first), 60),
+        (format!("{}.{}", last, first), 55),
+        (first.clone(), 45),
+        (last.clone(), 40),
+    ]
+}
+
+/// Replace the letters with common look-alike digits
+fn leetspeak(username: &str) -> String {
+    username
+        .chars()
+        .map(|c| match c.to_ascii_lowercase() {
+            'a' => '4',
+            'e' => '3',
+            'i' => '1',
+            'o' => '0',
+            's' => '5',
+            't' => '7',
+            _ => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(variants: &[Variant]) -> Vec<&str> {
+        variants.iter().map(|v| v.username.as_str()).collect()
+    }
+
+    #[test]
+    fn test_expand_placeholders() {
+        assert_eq!(
+            expand_placeholders("jane{?}doe"),
+            vec!["jane_doe", "jane-doe", "jane.doe"]
+        );
+        assert_eq!(expand_placeholders("a{?}b{?}c").len(), 9);
+        assert_eq!(expand_placeholders("jane"), vec!["jane"]);
+    }
+
+    #[test]
+    fn test_generate_variants_seed_first() {
+        let options = VariantOptions {
+            birth_year: Some(1990),
+            ..VariantOptions::default()
+        };
+        let variants = generate_variants("blue", &options);
+        assert_eq!(variants[0].kind, VariantKind::Seed);
+        assert_eq!(
+            names(&variants),
+            vec!["blue", "blue1990", "blue90", "blue1", "blue123", "blue01", "blue2", "blu3"]
+        );
+        assert!(variants.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+
+    #[test]
+    fn test_generate_variants_placeholders_and_names() {
+        let options = VariantOptions {
+            full_name: Some("Jane Q. Doe".to_string()),
+            suffixes: false,
+            leetspeak: false,
+            ..VariantOptions::default()
+        };
+        let variants = generate_variants("jane{?}doe", &options);
+        let usernames = names(&variants);
+        assert_eq!(
+            &usernames[..4],
+            &["jane_doe", "jane-doe", "jane.doe", "janedoe"]
+        );
+        assert!(usernames.contains(&"jdoe"));
+        assert!(usernames.contains(&"doejane"));
+        // "jane_doe" from the full name repeats a placeholder variant
+        assert_eq!(usernames.iter().filter(|u| **u == "jane_doe").count(), 1);
+        assert_eq!(variants[3].kind, VariantKind::Name);
+    }
+
+    #[test]
+    fn test_generate_variants_capped() {
+        let options = VariantOptions {
+            max_variants: 3,
+            full_name: Some("Jane Doe".to_string()),
+            ..VariantOptions::default()
+        };
+        assert_eq!(
+            names(&generate_variants("jd", &options)),
+            vec!["jd", "janedoe", "jane.doe"]
+        );
+        assert!(name_combinations("  ").is_empty());
+        assert_eq!(name_combinations("Cher"), vec![("cher".to_string(), 60)]);
+    }
+
+    #[test]
+    fn test_variant_report_matches() {
+        let variants = generate_variants(
+            "jane{?}doe",
+            &VariantOptions {
+                suffixes: false,
+                leetspeak: false,
+                ..VariantOptions::default()
+            },
+        );
+        let found = |site: &str, username: &str, url: &str| {
+            SearchResult::found(site.to_string(), username.to_string(), url.to_string())
+        };
+        let results = vec![
+            // Case-insensitive site: two variants, one profile
+            found("Code", "jane-doe", "https://code.example/Jane-Doe"),
+            found("Code", "jane_doe", "https://code.example/jane-doe"),
+            found("Blog", "jane.doe", "https://blog.example/jane.doe"),
+            SearchResult::not_found("Blog".to_string(), "jane_doe".to_string()),
+        ];
+        let report = VariantReport::new("jane{?}doe", variants, results);
+
+        assert_eq!(report.results.len(), 4);
+        let matches: Vec<_> = report
+            .matches
+            .iter()
+            .map(|m| (m.site.as_str(), m.username.as_str(), m.url.as_deref()))
+            .collect();
+        assert_eq!(
+            matches,
+            vec![
+                ("Blog", "jane.doe", Some("https://blog.example/jane.doe")),
+                ("Code", "jane_doe", Some("https://code.example/jane-doe")),
+            ]
+        );
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["matches"][1]["kind"], "separator");
+        assert_eq!(json["variants"][0]["score"], 90);
+    }
+}
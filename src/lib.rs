@@ -15,7 +15,7 @@ pub mod utils;
 pub use core::{scan_username, Engine, SearchResult};
 pub use data::site_info::{SiteInfo, SiteType};
 pub use request::{create_request, Request, RequestResponse, RequestType};
-pub use sites::{Site, SiteRegistry, SiteStatistics};
+pub use sites::{DeclarativeSite, Site, SiteDefinition, SiteRegistry, SiteStatistics};
 pub use utils::error::{Result, SleuthError};
 
 #[cfg(test)]
@@ -1,17 +1,102 @@
 //! Sleuth CLI entry point
 
 use clap::Parser;
-use sleuth::cli::{print_results, Args};
-use sleuth::core::Engine;
+use futures::StreamExt;
+use sleuth::cli::{
+    print_pivot_report, print_pivot_reports, print_self_test, print_variant_report,
+    print_variant_reports, Args, BatchPrinter, Command, ResultPrinter, SitesCommand,
+};
+use sleuth::core::scanner::default_request;
+use sleuth::core::{test_sites, Engine, PivotOptions, RetryPolicy, ScanOptions};
+use sleuth::request::{FixtureRequest, RecordingRequest, Request};
+use sleuth::sites::SiteRegistry;
+use std::sync::Arc;
+use std::time::Duration;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
 
-    let engine = Engine::new();
+    let mut registry = SiteRegistry::new();
+    for path in &args.sites_files {
+        registry.load_manifest(path)?;
+    }
+    if let Some(path) = &args.sherlock_data {
+        let import = registry.load_sherlock(path)?;
+        if !import.skipped.is_empty() {
+            eprintln!(
+                "Skipped {} unsupported sherlock entries",
+                import.skipped.len()
+            );
+        }
+        if !import.notes.is_empty() {
+            eprintln!(
+                "Imported {} sherlock entries without their username pattern",
+                import.notes.len()
+            );
+        }
+    }
+    if let Some(path) = &args.wmn_data {
+        let import = registry.load_wmn(path)?;
+        if !import.skipped.is_empty() {
+            eprintln!(
+                "Skipped {} unsupported WhatsMyName entries",
+                import.skipped.len()
+            );
+        }
+    }
+    let engine = Engine::with_registry(registry)
+        .with_scan_options(ScanOptions {
+            calibrate: args.calibrate,
+            retry: RetryPolicy::new(args.retries),
+            timeout: Duration::from_secs(args.timeout),
+            deadline: args.deadline.map(Duration::from_secs),
+            ..ScanOptions::default()
+        })
+        .with_concurrency(args.concurrency, args.per_host);
     let site_types = args.parsed_site_types();
 
-    println!("Searching for username: {}", args.username);
+    if let Some(Command::Sites {
+        command: SitesCommand::Test { fixtures, record },
+    }) = &args.command
+    {
+        let sites = engine.registry().filter(&site_types, &args.sites);
+        let options = engine.scan_options();
+
+        let live = || default_request(&sites, options);
+        let recorder = match record {
+            Some(_) => Some(Arc::new(RecordingRequest::new(live()?))),
+            None => None,
+        };
+        let request: Arc<dyn Request> = match (fixtures, &recorder) {
+            (Some(path), _) => Arc::new(FixtureRequest::load(path)?),
+            (None, Some(recorder)) => recorder.clone(),
+            (None, None) => live()?,
+        };
+
+        let summary = test_sites(sites, request, options).await;
+        if let (Some(path), Some(recorder)) = (record, recorder) {
+            recorder.fixtures().save(path)?;
+        }
+
+        print_self_test(&summary, &args.output_format);
+        if summary.failed > 0 {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let usernames = args.load_usernames()?;
+    if usernames.is_empty() {
+        eprintln!("No usernames to search");
+        std::process::exit(2);
+    }
+
+    if args.is_batch() {
+        println!("Searching for {} usernames", usernames.len());
+    } else {
+        println!("Searching for username: {}", usernames[0]);
+    }
     if !site_types.is_empty() {
         println!("Filtering by types: {:?}", site_types);
     }
@@ -19,11 +104,72 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("Filtering by sites: {:?}", args.sites);
     }
 
-    let results = engine
-        .search(&args.username, &site_types, &args.sites, None, args.verify)
-        .await?;
+    // Each username is a seed; its variants are searched as a batch
+    if args.wants_variants(&usernames) {
+        let options = args.variant_options();
+        let mut reports = Vec::new();
+        for seed in &usernames {
+            let report = engine
+                .search_variants(seed, &options, &site_types, &args.sites, None, args.verify)
+                .await?;
+            reports.push(report);
+        }
+        if args.is_batch() {
+            print_variant_reports(&reports, &args.output_format);
+        } else {
+            print_variant_report(&reports[0], &args.output_format);
+        }
+        return Ok(());
+    }
+
+    // Pivoting needs every result of a username before scanning the next
+    if args.pivot_depth > 0 {
+        let pivot = PivotOptions {
+            max_depth: args.pivot_depth,
+            max_scans: args.pivot_budget,
+        };
+        let mut reports = Vec::new();
+        for username in &usernames {
+            let report = engine
+                .search_recursive(
+                    username,
+                    &site_types,
+                    &args.sites,
+                    None,
+                    args.verify,
+                    &pivot,
+                )
+                .await?;
+            reports.push(report);
+        }
+        if args.is_batch() {
+            print_pivot_reports(&reports, &args.output_format);
+        } else {
+            print_pivot_report(&reports[0], &args.output_format);
+        }
+        return Ok(());
+    }
+
+    // Group the output per username, printing each as its search completes
+    if args.is_batch() {
+        let mut batch =
+            engine.search_batch(&usernames, &site_types, &args.sites, None, args.verify)?;
+        let mut printer = BatchPrinter::new(&args.output_format);
+        while let Some(group) = batch.next().await {
+            printer.print(group?);
+        }
+        printer.finish();
+        return Ok(());
+    }
 
-    print_results(&results, &args.output_format);
+    // Print each result as soon as its site answers
+    let mut stream =
+        engine.search_stream(&usernames[0], &site_types, &args.sites, None, args.verify)?;
+    let mut printer = ResultPrinter::new(&args.output_format);
+    while let Some(result) = stream.next().await {
+        printer.print(result);
+    }
+    printer.finish();
 
     Ok(())
 }
@@ -0,0 +1,299 @@
+//! Block page, WAF and CAPTCHA detection
+//!
+//! Bot protection often answers instead of the site: a challenge page, a block
+//! page or a bare status such as LinkedIn's 999. [`detect_block`] recognises
+//! the common ones by status, headers and body markers so that the scanner can
+//! report them as blocked instead of reading them as a profile or a 404.
+
+use crate::request::RequestResponse;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Bot protection that answered instead of the site
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Protection {
+    Cloudflare,
+    Akamai,
+    DataDome,
+    PerimeterX,
+    Imperva,
+    AwsWaf,
+    /// LinkedIn's own bot wall (status 999)
+    LinkedIn,
+    /// A CAPTCHA from an unidentified protection
+    Captcha,
+}
+
+impl Protection {
+    /// Human-readable name
+    pub fn label(&self) -> &'static str {
+        match self {
+            Protection::Cloudflare => "Cloudflare",
+            Protection::Akamai => "Akamai",
+            Protection::DataDome => "DataDome",
+            Protection::PerimeterX => "PerimeterX",
+            Protection::Imperva => "Imperva",
+            Protection::AwsWaf => "AWS WAF",
+            Protection::LinkedIn => "LinkedIn",
+            Protection::Captcha => "CAPTCHA",
+        }
+    }
+}
+
+impl fmt::Display for Protection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// A response recognised as a block or challenge page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub protection: Protection,
+    /// The status, header or body marker that gave it away
+    pub indicator: String,
+}
+
+impl fmt::Display for Block {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.protection, self.indicator)
+    }
+}
+
+/// Where a signature looks
+#[derive(Debug, Clone, Copy)]
+enum Marker {
+    /// Exact status code
+    Status(u16),
+    /// Header present, whatever its value
+    Header(&'static str),
+    /// Header containing a value (case-insensitive)
+    HeaderValue(&'static str, &'static str),
+    /// Body containing a text (case-insensitive)
+    Body(&'static str),
+}
+
+/// Statuses a signature applies to
+#[derive(Debug, Clone, Copy)]
+enum Statuses {
+    Any,
+    /// 4xx/5xx only; the marker also appears on normal pages
+    Error,
+    /// Challenge statuses only (403, 429, 503); the marker is a widget that
+    /// normal pages embed too
+    Challenge,
+}
+
+impl Statuses {
+    fn allow(self, status: u16) -> bool {
+        match self {
+            Statuses::Any => true,
+            Statuses::Error => status >= 400,
+            Statuses::Challenge => matches!(status, 403 | 429 | 503),
+        }
+    }
+}
+
+/// A marker of a protection, optionally only on some statuses
+struct Signature {
+    protection: Protection,
+    marker: Marker,
+    statuses: Statuses,
+}
+
+const fn signature(protection: Protection, marker: Marker) -> Signature {
+    Signature {
+        protection,
+        marker,
+        statuses: Statuses::Any,
+    }
+}
+
+const fn on_error(protection: Protection, marker: Marker) -> Signature {
+    Signature {
+        protection,
+        marker,
+        statuses: Statuses::Error,
+    }
+}
+
+const fn on_challenge(protection: Protection, marker: Marker) -> Signature {
+    Signature {
+        protection,
+        marker,
+        statuses: Statuses::Challenge,
+    }
+}
+
+/// Known block and challenge signatures, most specific first
+const SIGNATURES: &[Signature] = &[
+    signature(Protection::LinkedIn, Marker::Status(999)),
+    signature(
+        Protection::Cloudflare,
+        Marker::HeaderValue("cf-mitigated", "challenge"),
+    ),
+    signature(Protection::Cloudflare, Marker::Body("window._cf_chl_opt")),
+    signature(
+        Protection::Cloudflare,
+        Marker::Body("<title>Just a moment...</title>"),
+    ),
+    signature(
+        Protection::Cloudflare,
+        Marker::Body("<title>Attention Required! | Cloudflare</title>"),
+    ),
+    on_error(Protection::Cloudflare, Marker::Body("cf-error-details")),
+    signature(Protection::DataDome, Marker::Body("captcha-delivery.com")),
+    on_error(Protection::DataDome, Marker::Header("x-datadome")),
+    on_error(Protection::DataDome, Marker::Header("x-dd-b")),
+    signature(Protection::PerimeterX, Marker::Body("_pxCaptcha")),
+    on_error(Protection::PerimeterX, Marker::Body("px-captcha")),
+    signature(Protection::Imperva, Marker::Body("_Incapsula_Resource")),
+    signature(Protection::Imperva, Marker::Body("Incapsula incident ID")),
+    signature(
+        Protection::AwsWaf,
+        Marker::HeaderValue("x-amzn-waf-action", ""),
+    ),
+    on_error(Protection::AwsWaf, Marker::Body("awswaf")),
+    on_error(Protection::Akamai, Marker::Body("errors.edgesuite.net")),
+    on_error(
+        Protection::Akamai,
+        Marker::HeaderValue("server", "AkamaiGHost"),
+    ),
+    on_challenge(
+        Protection::Captcha,
+        Marker::Body("challenges.cloudflare.com/turnstile"),
+    ),
+    on_error(Protection::Captcha, Marker::Body("g-recaptcha")),
+    on_error(Protection::Captcha, Marker::Body("h-captcha")),
+];
+
+/// Recognise a block, challenge or CAPTCHA page
+///
+/// Runs before site detection: a recognised page says nothing about the
+/// username, whatever its status.
+pub fn detect_block(response: &RequestResponse) -> Option<Block> {
+    let body = response.body.as_deref().map(str::to_lowercase);
+
+    SIGNATURES
+        .iter()
+        .filter(|signature| signature.statuses.allow(response.status_code))
+        .find_map(|signature| {
+            let indicator = match signature.marker {
+                Marker::Status(status) => {
+                    (response.status_code == status).then(|| format!("HTTP {}", status))
+                }
+                Marker::Header(name) => response.header(name).map(|_| format!("{} header", name)),
+                Marker::HeaderValue(name, value) => response
+                    .header(name)
+                    .filter(|actual| actual.to_lowercase().contains(&value.to_lowercase()))
+                    .map(|actual| format!("{}: {}", name, actual)),
+                Marker::Body(marker) => body
+                    .as_deref()
+                    .filter(|body| body.contains(&marker.to_lowercase()))
+                    .map(|_| format!("'{}' in body", marker)),
+            }?;
+            Some(Block {
+                protection: signature.protection,
+                indicator,
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_header(status: u16, name: &str, value: &str) -> RequestResponse {
+        RequestResponse {
+            headers: vec![(name.to_string(), value.to_string())],
+            ..RequestResponse::new(status)
+        }
+    }
+
+    #[test]
+    fn test_detect_block_status_and_headers() {
+        let block = detect_block(&RequestResponse::new(999)).unwrap();
+        assert_eq!(block.protection, Protection::LinkedIn);
+        assert_eq!(block.to_string(), "LinkedIn (HTTP 999)");
+
+        let block = detect_block(&with_header(403, "CF-Mitigated", "challenge")).unwrap();
+        assert_eq!(block.protection, Protection::Cloudflare);
+        assert_eq!(block.indicator, "cf-mitigated: challenge");
+
+        let block = detect_block(&with_header(405, "x-amzn-waf-action", "captcha")).unwrap();
+        assert_eq!(block.protection, Protection::AwsWaf);
+
+        // DataDome sets its header on every response; only errors are blocks
+        assert!(detect_block(&with_header(200, "X-DataDome", "protected")).is_none());
+        let block = detect_block(&with_header(403, "X-DataDome", "protected")).unwrap();
+        assert_eq!(block.protection, Protection::DataDome);
+    }
+
+    #[test]
+    fn test_detect_block_body_markers() {
+        let challenge = RequestResponse::with_body(
+            503,
+            "<html><head><title>Just a moment...</title></head></html>".to_string(),
+        );
+        assert_eq!(
+            detect_block(&challenge).unwrap().protection,
+            Protection::Cloudflare
+        );
+
+        // A browser renders challenges with a synthetic 200
+        let datadome = RequestResponse::with_body(
+            200,
+            r#"<script src="https://ct.captcha-delivery.com/c.js"></script>"#.to_string(),
+        );
+        assert_eq!(
+            detect_block(&datadome).unwrap().protection,
+            Protection::DataDome
+        );
+
+        let akamai = RequestResponse {
+            headers: vec![("Server".to_string(), "AkamaiGHost".to_string())],
+            ..RequestResponse::with_body(403, "<H1>Access Denied</H1>".to_string())
+        };
+        assert_eq!(
+            detect_block(&akamai).unwrap().protection,
+            Protection::Akamai
+        );
+    }
+
+    #[test]
+    fn test_detect_block_ignores_normal_pages() {
+        assert!(detect_block(&RequestResponse::new(200)).is_none());
+        assert!(detect_block(&RequestResponse::new(404)).is_none());
+        assert!(detect_block(&RequestResponse::new(403)).is_none());
+        // Origin responses through a CDN, and sign-up forms with a CAPTCHA
+        assert!(detect_block(&with_header(403, "Server", "cloudflare")).is_none());
+        let signup = RequestResponse::with_body(
+            200,
+            r#"<form><div class="g-recaptcha"></div></form>"#.to_string(),
+        );
+        assert!(detect_block(&signup).is_none());
+    }
+
+    #[test]
+    fn test_detect_block_turnstile_only_on_challenge_statuses() {
+        let page = r#"<form><script src="https://challenges.cloudflare.com/turnstile/v0/api.js"></script></form>"#;
+        // A normal page embedding the widget, e.g. on a login form
+        assert!(detect_block(&RequestResponse::with_body(200, page.to_string())).is_none());
+        assert!(detect_block(&RequestResponse::with_body(404, page.to_string())).is_none());
+        for status in [403, 429, 503] {
+            let block =
+                detect_block(&RequestResponse::with_body(status, page.to_string())).unwrap();
+            assert_eq!(block.protection, Protection::Captcha);
+        }
+    }
+
+    #[test]
+    fn test_protection_names() {
+        assert_eq!(Protection::AwsWaf.to_string(), "AWS WAF");
+        assert_eq!(
+            serde_json::to_string(&Protection::DataDome).unwrap(),
+            "\"data_dome\""
+        );
+    }
+}
@@ -1,8 +1,12 @@
 //! Browser request implementation using headless Chrome
 
-use crate::request::{Request, RequestResponse};
+use crate::request::{Request, RequestResponse, RequestSpec, RequestType};
 use crate::utils::error::{Result, SleuthError};
-use headless_chrome::Browser;
+use headless_chrome::protocol::{network, Method};
+use headless_chrome::{Browser, Tab};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
 use std::time::Duration;
 
 pub struct BrowserRequest {
@@ -22,6 +26,170 @@ impl BrowserRequest {
     }
 }
 
+/// `Network.setExtraHTTPHeaders`, which headless_chrome does not wrap
+#[derive(Serialize, Debug)]
+struct SetExtraHttpHeaders {
+    headers: HashMap<String, String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct SetExtraHttpHeadersReturnObject {}
+
+impl Method for SetExtraHttpHeaders {
+    const NAME: &'static str = "Network.setExtraHTTPHeaders";
+    type ReturnObject = SetExtraHttpHeadersReturnObject;
+}
+
+/// Send the spec's headers with every request the tab makes
+fn set_headers(tab: &Tab, spec: &RequestSpec) -> Result<()> {
+    let failed = |e| SleuthError::Unknown(format!("Failed to set headers: {}", e));
+    tab.call_method(network::methods::Enable {})
+        .map_err(failed)?;
+    tab.call_method(SetExtraHttpHeaders {
+        headers: spec.headers.iter().cloned().collect(),
+    })
+    .map_err(failed)?;
+    Ok(())
+}
+
+/// Navigate to a page, wait for JavaScript to render it and read the HTML
+fn render(tab: &Tab, spec: &RequestSpec) -> Result<RequestResponse> {
+    if !spec.headers.is_empty() {
+        set_headers(tab, spec)?;
+    }
+    if !spec.cookies.is_empty() {
+        // Cookies are set from the site's origin before the page itself is loaded
+        navigate(tab, &origin(&spec.url)?)?;
+        evaluate(tab, &set_cookies_script(spec), false)?;
+    }
+    navigate(tab, &spec.url)?;
+
+    // Wait a bit for JavaScript to render (configurable)
+    // For sites like OnlyFans, we need to wait longer for content to load
+    std::thread::sleep(Duration::from_millis(2000));
+
+    // Get the rendered HTML using evaluate_expression
+    let body_str = evaluate(tab, "document.documentElement.outerHTML", false)?;
+
+    // Debug: Save HTML to file for analysis (if SLEUTH_DEBUG_HTML env var is set)
+    if let Ok(debug_file) = std::env::var("SLEUTH_DEBUG_HTML") {
+        if !debug_file.is_empty() {
+            let _ = std::fs::write(&debug_file, &body_str);
+        }
+    }
+
+    // Get status code (browser doesn't expose this directly, so we'll use 200 as default)
+    // In a real browser, we'd check the network response
+    let status_code = 200;
+
+    // Get headers (limited in headless_chrome)
+    let headers = vec![];
+
+    Ok(RequestResponse {
+        status_code,
+        body: Some(body_str),
+        headers,
+        final_url: Some(tab.get_url()),
+    })
+}
+
+/// Response of an in-page `fetch()`, as serialized by [`fetch_script`]
+#[derive(Deserialize)]
+struct Fetched {
+    status: u16,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Option<String>,
+}
+
+/// Send a request with `fetch()` from the site's origin, so that its method,
+/// headers, cookies, body and redirect policy are used as given
+fn fetch(tab: &Tab, spec: &RequestSpec) -> Result<RequestResponse> {
+    navigate(tab, &origin(&spec.url)?)?;
+    let fetched = evaluate(tab, &fetch_script(spec), true)?;
+    let fetched: Fetched = serde_json::from_str(&fetched)
+        .map_err(|e| SleuthError::Unknown(format!("Failed to read fetch response: {}", e)))?;
+    Ok(RequestResponse {
+        status_code: fetched.status,
+        body: fetched.body,
+        headers: fetched.headers,
+        final_url: Some(fetched.url),
+    })
+}
+
+fn navigate(tab: &Tab, url: &str) -> Result<()> {
+    tab.navigate_to(url)
+        .map_err(|e| SleuthError::Unknown(format!("Failed to navigate: {}", e)))?;
+
+    // Wait for network to be idle (page loaded)
+    tab.wait_until_navigated()
+        .map_err(|e| SleuthError::Unknown(format!("Failed to wait for navigation: {}", e)))?;
+    Ok(())
+}
+
+/// Evaluate a script and return its string result
+fn evaluate(tab: &Tab, script: &str, await_promise: bool) -> Result<String> {
+    let result = tab
+        .evaluate(script, await_promise)
+        .map_err(|e| SleuthError::Unknown(format!("Failed to evaluate script: {}", e)))?;
+    Ok(result
+        .value
+        .map(|v| match v {
+            serde_json::Value::String(s) => s,
+            other => other.to_string(),
+        })
+        .unwrap_or_default())
+}
+
+/// Origin of a URL (`https://host:port/`), where pages can fetch it from
+fn origin(url: &str) -> Result<String> {
+    let url = reqwest::Url::parse(url)
+        .map_err(|e| SleuthError::Unknown(format!("Invalid URL '{}': {}", url, e)))?;
+    Ok(format!("{}/", url.origin().ascii_serialization()))
+}
+
+fn set_cookies_script(spec: &RequestSpec) -> String {
+    spec.cookies
+        .iter()
+        .map(|(name, value)| {
+            format!(
+                "document.cookie = {};",
+                json!(format!("{}={}; path=/", name, value))
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Script running `fetch()` for the spec, resolving to the response as JSON
+fn fetch_script(spec: &RequestSpec) -> String {
+    let init = json!({
+        "method": spec.method,
+        "headers": spec.headers,
+        "body": spec.body,
+        "credentials": "include",
+        "redirect": if spec.follow_redirects { "follow" } else { "manual" },
+    });
+    format!(
+        r#"(async () => {{
+{cookies}
+const response = await fetch({url}, {init});
+// Browsers hide an unfollowed redirect's status and target: report a plain 302
+if (response.type === "opaqueredirect") {{
+  return JSON.stringify({{ status: 302, url: {url}, headers: [], body: null }});
+}}
+const headers = [];
+response.headers.forEach((value, name) => headers.push([name, value]));
+const body = {reads_body} ? await response.text() : null;
+return JSON.stringify({{ status: response.status, url: response.url, headers, body }});
+}})()"#,
+        cookies = set_cookies_script(spec),
+        url = json!(spec.url),
+        init = init,
+        reads_body = spec.reads_body(),
+    )
+}
+
 #[async_trait::async_trait]
 impl Request for BrowserRequest {
     async fn head(&self, url: &str) -> Result<RequestResponse> {
@@ -31,65 +199,43 @@ impl Request for BrowserRequest {
     }
 
     async fn get(&self, url: &str) -> Result<RequestResponse> {
+        self.send(&RequestSpec::get(url)).await
+    }
+
+    /// HEAD and GET requests that follow redirects load and render the page,
+    /// with the spec's headers and cookies; other methods, bodies and
+    /// unfollowed redirects are sent with an in-page `fetch()` from the site's
+    /// origin, which does not run the page's scripts.
+    async fn send(&self, spec: &RequestSpec) -> Result<RequestResponse> {
         // Launch browser in a blocking way (headless_chrome is not async)
         // We'll use tokio::task::spawn_blocking to run it in a thread pool
-        let url = url.to_string();
-        let timeout = self.timeout;
+        let spec = spec.clone();
+        let timeout = spec.timeout.unwrap_or(self.timeout);
 
         tokio::task::spawn_blocking(move || {
             let browser = BrowserRequest::new(timeout.as_secs())?.launch_browser()?;
             let tab = browser
                 .new_tab()
                 .map_err(|e| SleuthError::Unknown(format!("Failed to create tab: {}", e)))?;
+            tab.set_default_timeout(timeout);
 
-            // Navigate to URL
-            tab.navigate_to(&url)
-                .map_err(|e| SleuthError::Unknown(format!("Failed to navigate: {}", e)))?;
-
-            // Wait for network to be idle (page loaded)
-            tab.wait_until_navigated().map_err(|e| {
-                SleuthError::Unknown(format!("Failed to wait for navigation: {}", e))
-            })?;
-
-            // Wait a bit for JavaScript to render (configurable)
-            // For sites like OnlyFans, we need to wait longer for content to load
-            std::thread::sleep(Duration::from_millis(2000));
-
-            // Get the rendered HTML using evaluate_expression
-            let body = tab
-                .evaluate("document.documentElement.outerHTML", false)
-                .map_err(|e| SleuthError::Unknown(format!("Failed to get content: {}", e)))?;
-
-            let body_str = body
-                .value
-                .and_then(|v| v.as_str().map(|s| s.to_string()))
-                .ok_or_else(|| {
-                    SleuthError::Unknown("Failed to extract HTML content".to_string())
-                })?;
-
-            // Debug: Save HTML to file for analysis (if SLEUTH_DEBUG_HTML env var is set)
-            if let Ok(debug_file) = std::env::var("SLEUTH_DEBUG_HTML") {
-                if !debug_file.is_empty() {
-                    let _ = std::fs::write(&debug_file, &body_str);
-                }
+            // Headers and cookies do not need fetch(): they are set before rendering
+            let renders = matches!(spec.method.as_str(), "HEAD" | "GET")
+                && spec.body.is_none()
+                && spec.follow_redirects;
+            if renders {
+                render(&tab, &spec)
+            } else {
+                fetch(&tab, &spec)
             }
-
-            // Get status code (browser doesn't expose this directly, so we'll use 200 as default)
-            // In a real browser, we'd check the network response
-            let status_code = 200;
-
-            // Get headers (limited in headless_chrome)
-            let headers = vec![];
-
-            Ok(RequestResponse {
-                status_code,
-                body: Some(body_str),
-                headers,
-            })
         })
         .await
         .map_err(|e| SleuthError::Unknown(format!("Browser task failed: {}", e)))?
     }
+
+    fn request_type(&self) -> RequestType {
+        RequestType::Browser
+    }
 }
 
 #[cfg(test)]
@@ -102,6 +248,27 @@ mod tests {
         assert!(request.is_ok());
     }
 
+    #[test]
+    fn test_fetch_script() {
+        let spec = RequestSpec::new("POST", "https://api.example/check")
+            .with_header("Content-Type", "application/json")
+            .with_cookie("session", "a\"b")
+            .with_body(r#"{"username":"blue"}"#);
+        let script = fetch_script(&spec);
+        assert!(script.contains(r#"document.cookie = "session=a\"b; path=/";"#));
+        assert!(script.contains(r#"fetch("https://api.example/check", "#));
+        assert!(script.contains(r#""method":"POST""#));
+        assert!(script.contains(r#""body":"{\"username\":\"blue\"}""#));
+        assert!(script.contains(r#""redirect":"follow""#));
+        assert!(script.contains("const body = true ?"));
+        let script = fetch_script(&spec.with_follow_redirects(false));
+        assert!(script.contains(r#""redirect":"manual""#));
+        assert_eq!(
+            origin("https://api.example:8443/check?x=1").unwrap(),
+            "https://api.example:8443/"
+        );
+    }
+
     #[test]
     fn test_browser_request_new_zero_timeout() {
         let request = BrowserRequest::new(0);
@@ -0,0 +1,291 @@
+//! Recorded responses for offline replay
+//!
+//! A fixture file is a JSON list of responses keyed by HTTP method, URL and
+//! request body.
+//! [`RecordingRequest`] captures live responses into a [`FixtureSet`];
+//! [`FixtureRequest`] replays them without touching the network.
+
+use crate::request::{Request, RequestResponse, RequestSpec, RequestType};
+use crate::utils::error::{Result, SleuthError};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// A recorded response for one request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fixture {
+    /// HTTP method
+    pub method: String,
+    /// Requested URL
+    pub url: String,
+    /// Body sent with the request, for POST checks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<String>,
+    /// Recorded response
+    #[serde(flatten)]
+    pub response: RequestResponse,
+}
+
+/// A set of recorded responses
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FixtureSet {
+    pub fixtures: Vec<Fixture>,
+}
+
+impl FixtureSet {
+    /// Parse fixtures from JSON
+    pub fn from_json(content: &str) -> Result<Self> {
+        serde_json::from_str(content)
+            .map_err(|e| SleuthError::Config(format!("Invalid fixture file: {}", e)))
+    }
+
+    /// Load fixtures from a JSON file
+    pub fn load(path: &Path) -> Result<Self> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Write fixtures to a JSON file
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| SleuthError::Unknown(format!("Failed to serialize fixtures: {}", e)))?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Find the recorded response for a request without a body
+    pub fn find(&self, method: &str, url: &str) -> Option<&RequestResponse> {
+        self.find_request(&RequestSpec::new(method, url))
+    }
+
+    /// Find the recorded response for a request, matched on method, URL and body
+    pub fn find_request(&self, spec: &RequestSpec) -> Option<&RequestResponse> {
+        self.fixtures
+            .iter()
+            .find(|f| f.matches(spec))
+            .map(|f| &f.response)
+    }
+
+    /// Record a response to a request without a body, replacing any earlier one
+    pub fn insert(&mut self, method: &str, url: &str, response: RequestResponse) {
+        self.insert_request(&RequestSpec::new(method, url), response);
+    }
+
+    /// Record a response, replacing any earlier one for the same request
+    pub fn insert_request(&mut self, spec: &RequestSpec, response: RequestResponse) {
+        self.fixtures.retain(|f| !f.matches(spec));
+        self.fixtures.push(Fixture {
+            method: spec.method.clone(),
+            url: spec.url.clone(),
+            request_body: spec.body.clone(),
+            response,
+        });
+    }
+}
+
+impl Fixture {
+    fn matches(&self, spec: &RequestSpec) -> bool {
+        self.method.eq_ignore_ascii_case(&spec.method)
+            && self.url == spec.url
+            && self.request_body == spec.body
+    }
+}
+
+/// Request implementation that replays recorded responses
+pub struct FixtureRequest {
+    fixtures: FixtureSet,
+}
+
+impl FixtureRequest {
+    pub fn new(fixtures: FixtureSet) -> Self {
+        Self { fixtures }
+    }
+
+    /// Load fixtures from a JSON file
+    pub fn load(path: &Path) -> Result<Self> {
+        Ok(Self::new(FixtureSet::load(path)?))
+    }
+
+    fn replay(&self, spec: &RequestSpec) -> Result<RequestResponse> {
+        self.fixtures.find_request(spec).cloned().ok_or_else(|| {
+            SleuthError::Unknown(format!(
+                "No recorded response for {} {}",
+                spec.method, spec.url
+            ))
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Request for FixtureRequest {
+    async fn head(&self, url: &str) -> Result<RequestResponse> {
+        self.replay(&RequestSpec::head(url))
+    }
+
+    async fn get(&self, url: &str) -> Result<RequestResponse> {
+        self.replay(&RequestSpec::get(url))
+    }
+
+    /// Replays by method, URL and body; headers and cookies are not recorded
+    async fn send(&self, spec: &RequestSpec) -> Result<RequestResponse> {
+        self.replay(spec)
+    }
+}
+
+/// Request implementation that forwards to another transport and records
+/// every response
+pub struct RecordingRequest {
+    inner: Arc<dyn Request>,
+    recorded: Mutex<FixtureSet>,
+}
+
+impl RecordingRequest {
+    pub fn new(inner: Arc<dyn Request>) -> Self {
+        Self {
+            inner,
+            recorded: Mutex::new(FixtureSet::default()),
+        }
+    }
+
+    /// Get the responses recorded so far
+    pub fn fixtures(&self) -> FixtureSet {
+        self.recorded.lock().expect("fixture lock poisoned").clone()
+    }
+
+    fn record(&self, spec: &RequestSpec, response: &RequestResponse) {
+        self.recorded
+            .lock()
+            .expect("fixture lock poisoned")
+            .insert_request(spec, response.clone());
+    }
+}
+
+#[async_trait::async_trait]
+impl Request for RecordingRequest {
+    async fn head(&self, url: &str) -> Result<RequestResponse> {
+        let response = self.inner.head(url).await?;
+        self.record(&RequestSpec::head(url), &response);
+        Ok(response)
+    }
+
+    async fn get(&self, url: &str) -> Result<RequestResponse> {
+        let response = self.inner.get(url).await?;
+        self.record(&RequestSpec::get(url), &response);
+        Ok(response)
+    }
+
+    async fn request_no_redirect(&self, method: &str, url: &str) -> Result<RequestResponse> {
+        let response = self.inner.request_no_redirect(method, url).await?;
+        self.record(&RequestSpec::new(method, url), &response);
+        Ok(response)
+    }
+
+    async fn send(&self, spec: &RequestSpec) -> Result<RequestResponse> {
+        let response = self.inner.send(spec).await?;
+        self.record(spec, &response);
+        Ok(response)
+    }
+
+    fn request_type(&self) -> RequestType {
+        self.inner.request_type()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> FixtureSet {
+        let mut fixtures = FixtureSet::default();
+        fixtures.insert(
+            "get",
+            "https://example.com/blue",
+            RequestResponse::with_body(200, "profile".to_string()),
+        );
+        fixtures.insert(
+            "HEAD",
+            "https://example.com/nobody",
+            RequestResponse::new(404),
+        );
+        fixtures
+    }
+
+    #[tokio::test]
+    async fn test_fixture_request_replay() {
+        let request = FixtureRequest::new(sample());
+        let response = request.get("https://example.com/blue").await.unwrap();
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.body.as_deref(), Some("profile"));
+
+        let response = request.head("https://example.com/nobody").await.unwrap();
+        assert_eq!(response.status_code, 404);
+    }
+
+    #[tokio::test]
+    async fn test_fixture_request_missing() {
+        let request = FixtureRequest::new(sample());
+        let err = request.head("https://example.com/blue").await.unwrap_err();
+        assert!(err.to_string().contains("No recorded response"));
+    }
+
+    #[tokio::test]
+    async fn test_recording_request() {
+        let recorder = RecordingRequest::new(Arc::new(FixtureRequest::new(sample())));
+        recorder.get("https://example.com/blue").await.unwrap();
+        recorder.get("https://example.com/blue").await.unwrap();
+        assert!(recorder.get("https://example.com/other").await.is_err());
+
+        let recorded = recorder.fixtures();
+        assert_eq!(recorded.fixtures.len(), 1);
+        assert_eq!(recorded.fixtures[0].method, "GET");
+    }
+
+    #[tokio::test]
+    async fn test_fixtures_keyed_by_body() {
+        let recorder = RecordingRequest::new(Arc::new(FixtureRequest::new(sample())));
+        let check = |username: &str| {
+            RequestSpec::new("POST", "https://api.example/check")
+                .with_body(format!(r#"{{"username":"{}"}}"#, username))
+        };
+        // Nothing recorded for POST yet
+        assert!(recorder.send(&check("blue")).await.is_err());
+
+        let mut fixtures = sample();
+        fixtures.insert_request(&check("blue"), RequestResponse::new(200));
+        fixtures.insert_request(&check("nobody"), RequestResponse::new(404));
+        let request = FixtureRequest::new(fixtures.clone());
+        assert_eq!(request.send(&check("blue")).await.unwrap().status_code, 200);
+        assert_eq!(
+            request.send(&check("nobody")).await.unwrap().status_code,
+            404
+        );
+        assert!(request
+            .send(&RequestSpec::new("POST", "https://api.example/check"))
+            .await
+            .is_err());
+        // Plain requests still replay through send()
+        let spec = RequestSpec::get("https://example.com/blue").with_header("Accept", "*/*");
+        assert_eq!(request.send(&spec).await.unwrap().status_code, 200);
+
+        let json = serde_json::to_value(&fixtures).unwrap();
+        assert_eq!(
+            json["fixtures"][2]["request_body"],
+            r#"{"username":"blue"}"#
+        );
+        assert!(json["fixtures"][0].get("request_body").is_none());
+    }
+
+    #[test]
+    fn test_fixture_set_roundtrip() {
+        let path =
+            std::env::temp_dir().join(format!("sleuth-fixtures-{}.json", std::process::id()));
+        sample().save(&path).unwrap();
+        let loaded = FixtureSet::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, sample());
+    }
+
+    #[test]
+    fn test_fixture_set_invalid() {
+        assert!(FixtureSet::from_json("not json").is_err());
+    }
+}
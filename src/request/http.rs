@@ -1,90 +1,119 @@
 //! HTTP request implementation using reqwest
 
-use crate::request::{Request, RequestResponse};
+use crate::request::{spec, Request, RequestResponse, RequestSpec};
 use crate::utils::error::{Result, SleuthError};
-use reqwest::Client;
+use reqwest::header::COOKIE;
+use reqwest::redirect::Policy;
+use reqwest::{Client, Method};
 use std::time::Duration;
 
 pub struct HttpRequest {
     client: Client,
+    /// Client that returns 3xx responses as-is instead of following them
+    no_redirect_client: Client,
     #[allow(dead_code)]
     timeout: Duration,
 }
 
 impl HttpRequest {
     pub fn new(timeout_secs: u64) -> Result<Self> {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(timeout_secs))
-            .user_agent("sleuth/0.0.1")
-            .build()
-            .map_err(SleuthError::Http)?;
+        Self::with_user_agent(timeout_secs, "sleuth/0.0.1")
+    }
 
+    pub fn with_user_agent(timeout_secs: u64, user_agent: &str) -> Result<Self> {
+        let timeout = Duration::from_secs(timeout_secs);
         Ok(Self {
-            client,
-            timeout: Duration::from_secs(timeout_secs),
+            client: build_client(timeout, user_agent, Policy::default())?,
+            no_redirect_client: build_client(timeout, user_agent, Policy::none())?,
+            timeout,
         })
     }
+}
 
-    pub fn with_user_agent(timeout_secs: u64, user_agent: &str) -> Result<Self> {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(timeout_secs))
-            .user_agent(user_agent)
-            .build()
-            .map_err(SleuthError::Http)?;
+fn build_client(timeout: Duration, user_agent: &str, redirect: Policy) -> Result<Client> {
+    Client::builder()
+        .timeout(timeout)
+        .user_agent(user_agent)
+        .redirect(redirect)
+        .build()
+        .map_err(SleuthError::Http)
+}
 
-        Ok(Self {
-            client,
-            timeout: Duration::from_secs(timeout_secs),
-        })
+/// Send a request and collect status, headers, final URL and (except for HEAD) the body
+async fn send(client: &Client, spec: &RequestSpec) -> Result<RequestResponse> {
+    let mut request = client.request(parse_method(&spec.method)?, &spec.url);
+    for (name, value) in &spec.headers {
+        request = request.header(name.as_str(), value.as_str());
+    }
+    if let Some(cookies) = spec.cookie_header() {
+        request = request.header(COOKIE, cookies);
+    }
+    if let Some(body) = &spec.body {
+        request = request.body(body.clone());
     }
+    if let Some(timeout) = spec.timeout {
+        request = request.timeout(timeout);
+    }
+    let response = request.send().await.map_err(SleuthError::Http)?;
+
+    let status_code = response.status().as_u16();
+    let final_url = Some(response.url().to_string());
+    let headers: Vec<(String, String)> = response
+        .headers()
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
+        .collect();
+
+    let body = if spec.reads_body() {
+        response.text().await.ok()
+    } else {
+        None
+    };
+
+    Ok(RequestResponse {
+        status_code,
+        body,
+        headers,
+        final_url,
+    })
+}
+
+fn parse_method(method: &str) -> Result<Method> {
+    // Method::from_bytes accepts any token, so keep to the methods sites use
+    let method = spec::supported_method(method).ok_or_else(|| unsupported(method))?;
+    Method::from_bytes(method.as_bytes()).map_err(|_| unsupported(method))
+}
+
+fn unsupported(method: &str) -> SleuthError {
+    SleuthError::Unknown(format!("Unsupported HTTP method: {}", method))
 }
 
 #[async_trait::async_trait]
 impl Request for HttpRequest {
     async fn head(&self, url: &str) -> Result<RequestResponse> {
-        let response = self
-            .client
-            .head(url)
-            .send()
-            .await
-            .map_err(SleuthError::Http)?;
-
-        let status_code = response.status().as_u16();
-        let headers: Vec<(String, String)> = response
-            .headers()
-            .iter()
-            .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
-            .collect();
-
-        Ok(RequestResponse {
-            status_code,
-            body: None,
-            headers,
-        })
+        self.send(&RequestSpec::head(url)).await
     }
 
     async fn get(&self, url: &str) -> Result<RequestResponse> {
-        let response = self
-            .client
-            .get(url)
-            .send()
-            .await
-            .map_err(SleuthError::Http)?;
+        self.send(&RequestSpec::get(url)).await
+    }
 
-        let status_code = response.status().as_u16();
-        let headers: Vec<(String, String)> = response
-            .headers()
-            .iter()
-            .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
-            .collect();
+    async fn request(&self, method: &str, url: &str) -> Result<RequestResponse> {
+        self.send(&RequestSpec::new(method, url)).await
+    }
 
-        let body = response.text().await.ok();
+    async fn request_no_redirect(&self, method: &str, url: &str) -> Result<RequestResponse> {
+        self.send(&RequestSpec::new(method, url).with_follow_redirects(false))
+            .await
+    }
 
-        Ok(RequestResponse {
-            status_code,
-            body,
-            headers,
-        })
+    async fn send(&self, spec: &RequestSpec) -> Result<RequestResponse> {
+        let client = if spec.follow_redirects {
+            &self.client
+        } else {
+            &self.no_redirect_client
+        };
+        send(client, spec).await
     }
 }
 
@@ -199,9 +228,78 @@ mod tests {
     #[tokio::test]
     async fn test_http_request_unsupported_method() {
         let request = HttpRequest::new(10).unwrap();
-        let response = request.request("POST", "https://github.com").await;
+        let response = request.request("BREW", "https://github.com").await;
         assert!(response.is_err());
         let err = response.unwrap_err();
         assert!(err.to_string().contains("Unsupported HTTP method"));
     }
+
+    /// Serve one connection on localhost, answering with the raw request it received
+    async fn echo_server() -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut received = Vec::new();
+            let mut buffer = [0; 4096];
+            // Read the head, then as much body as Content-Length announces
+            loop {
+                let read = socket.read(&mut buffer).await.unwrap();
+                received.extend_from_slice(&buffer[..read]);
+                let text = String::from_utf8_lossy(&received).to_string();
+                if let Some(end) = text.find("\r\n\r\n") {
+                    let length = text
+                        .lines()
+                        .find_map(|l| {
+                            l.to_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().parse::<usize>().unwrap())
+                        })
+                        .unwrap_or(0);
+                    if received.len() >= end + 4 + length || read == 0 {
+                        break;
+                    }
+                }
+            }
+            let response = format!(
+                "HTTP/1.1 201 Created\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                received.len(),
+                String::from_utf8_lossy(&received)
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+        format!("http://{}/check", address)
+    }
+
+    #[tokio::test]
+    async fn test_http_request_send_full_spec() {
+        let url = echo_server().await;
+        let request = HttpRequest::new(10).unwrap();
+        let spec = RequestSpec::new("POST", &url)
+            .with_header("Content-Type", "application/json")
+            .with_cookie("session", "abc")
+            .with_body(r#"{"username":"blue"}"#)
+            .with_timeout(Duration::from_secs(5));
+        let response = request.send(&spec).await.unwrap();
+        assert_eq!(response.status_code, 201);
+        let echoed = response.body.unwrap();
+        assert!(echoed.starts_with("POST /check HTTP/1.1"), "{}", echoed);
+        assert!(
+            echoed.contains("content-type: application/json"),
+            "{}",
+            echoed
+        );
+        assert!(echoed.contains("cookie: session=abc"), "{}", echoed);
+        assert!(echoed.ends_with(r#"{"username":"blue"}"#), "{}", echoed);
+    }
+
+    #[test]
+    fn test_parse_method() {
+        assert_eq!(parse_method("head").unwrap(), Method::HEAD);
+        assert_eq!(parse_method("GET").unwrap(), Method::GET);
+        assert_eq!(parse_method("post").unwrap(), Method::POST);
+        assert!(parse_method("BREW").is_err());
+        assert!(parse_method("").is_err());
+    }
 }
@@ -1,23 +1,41 @@
 //! Request abstraction for making HTTP requests
 
+pub mod block;
 pub mod browser;
+pub mod fixture;
 pub mod http;
+pub mod rate_limit;
+pub mod spec;
 pub mod tor;
 pub mod trait_impl;
 
+pub use block::{detect_block, Block, Protection};
+pub use fixture::{FixtureRequest, FixtureSet, RecordingRequest};
+pub use rate_limit::{RateLimit, RateLimiter};
+pub use spec::RequestSpec;
 pub use trait_impl::{Request, RequestResponse};
 
 use crate::utils::error::Result;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-/// Request type enum
-#[derive(Debug, Clone, Copy)]
+/// Request type enum, also reported as the transport of a check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum RequestType {
     Http,
     Browser,
     Tor,
 }
 
+/// Host a URL is sent to, lowercased (the whole URL if it cannot be parsed)
+pub fn host_key(url: &str) -> String {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_lowercase))
+        .unwrap_or_else(|| url.to_string())
+}
+
 /// Factory for creating request implementations
 pub fn create_request(request_type: RequestType, timeout_secs: u64) -> Result<Arc<dyn Request>> {
     match request_type {
@@ -36,6 +54,13 @@ pub fn create_request(request_type: RequestType, timeout_secs: u64) -> Result<Ar
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_host_key() {
+        assert_eq!(host_key("https://GitHub.com/user"), "github.com");
+        assert_eq!(host_key("https://api.github.com/users/x"), "api.github.com");
+        assert_eq!(host_key("not a url"), "not a url");
+    }
+
     #[test]
     fn test_create_http_request() {
         let request = create_request(RequestType::Http, 10);
@@ -0,0 +1,323 @@
+//! Per-host rate limiting
+
+use crate::request::{host_key, RequestResponse};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tokio::time::Instant;
+
+/// Longest a host is paused for, whatever its headers ask
+pub const MAX_PAUSE: Duration = Duration::from_secs(300);
+
+/// A request budget: at most `requests` every `per_secs` seconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimit {
+    /// Requests allowed per period (also the burst size)
+    pub requests: u32,
+    /// Length of the period in seconds
+    pub per_secs: u64,
+}
+
+impl RateLimit {
+    /// At most `requests` per second
+    pub fn per_second(requests: u32) -> Self {
+        Self {
+            requests,
+            per_secs: 1,
+        }
+    }
+
+    /// At most `requests` per minute
+    pub fn per_minute(requests: u32) -> Self {
+        Self {
+            requests,
+            per_secs: 60,
+        }
+    }
+
+    /// Tokens added per second
+    fn rate(&self) -> f64 {
+        f64::from(self.requests.max(1)) / self.per_secs.max(1) as f64
+    }
+}
+
+/// Token bucket and pause state of one host
+#[derive(Debug)]
+struct HostState {
+    budget: Option<RateLimit>,
+    tokens: f64,
+    refilled: Instant,
+    paused_until: Option<Instant>,
+}
+
+impl HostState {
+    fn new(budget: Option<RateLimit>, now: Instant) -> Self {
+        Self {
+            budget,
+            tokens: budget.map_or(0.0, |b| f64::from(b.requests.max(1))),
+            refilled: now,
+            paused_until: None,
+        }
+    }
+
+    /// Take a token, or return how long to wait before trying again
+    fn try_take(&mut self, now: Instant) -> Option<Duration> {
+        if let Some(until) = self.paused_until {
+            if until > now {
+                return Some(until - now);
+            }
+            self.paused_until = None;
+        }
+
+        let budget = self.budget?;
+        let elapsed = now.duration_since(self.refilled).as_secs_f64();
+        self.tokens =
+            (self.tokens + elapsed * budget.rate()).min(f64::from(budget.requests.max(1)));
+        self.refilled = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64((1.0 - self.tokens) / budget.rate()))
+        }
+    }
+}
+
+/// Per-host token-bucket rate limiter
+///
+/// Hosts get the budget of the sites that use them (the strictest one if
+/// several sites share a host) and are paused when a response asks for it
+/// via `Retry-After` or exhausted `X-RateLimit-*` headers. Clones share the
+/// same state, so an engine applies it across all its scans.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimiter {
+    hosts: Arc<Mutex<HashMap<String, HostState>>>,
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wait until a request to `url` fits in its host's budget and pause
+    pub async fn acquire(&self, url: &str, budget: Option<RateLimit>) {
+        let host = host_key(url);
+        loop {
+            let wait = {
+                let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+                let now = Instant::now();
+                let state = hosts
+                    .entry(host.clone())
+                    .or_insert_with(|| HostState::new(budget, now));
+                if let Some(budget) = budget {
+                    if state.budget.is_none_or(|b| budget.rate() < b.rate()) {
+                        state.budget = Some(budget);
+                        state.tokens = state.tokens.min(f64::from(budget.requests.max(1)));
+                    }
+                }
+                state.try_take(now)
+            };
+
+            match wait {
+                Some(wait) => tokio::time::sleep(wait).await,
+                None => return,
+            }
+        }
+    }
+
+    /// Pause the host of `url` if the response asks to slow down
+    pub fn observe(&self, url: &str, response: &RequestResponse) {
+        let Some(pause) = requested_pause(response, SystemTime::now()) else {
+            return;
+        };
+        let until = Instant::now() + pause.min(MAX_PAUSE);
+
+        let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        let state = hosts
+            .entry(host_key(url))
+            .or_insert_with(|| HostState::new(None, Instant::now()));
+        if state.paused_until.is_none_or(|current| until > current) {
+            state.paused_until = Some(until);
+        }
+    }
+
+    /// How much longer the host of `url` is paused, if at all
+    pub fn paused_for(&self, url: &str) -> Option<Duration> {
+        let hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        let until = hosts.get(&host_key(url))?.paused_until?;
+        until.checked_duration_since(Instant::now())
+    }
+}
+
+/// How long a response asks the client to wait before the next request
+///
+/// Reads `Retry-After` (seconds or HTTP date) on 429/503 responses, and
+/// `X-RateLimit-Reset` / `RateLimit-Reset` once the matching `Remaining`
+/// header reaches zero. Reset values that look like Unix timestamps are
+/// treated as such, smaller ones as seconds from now.
+pub fn requested_pause(response: &RequestResponse, now: SystemTime) -> Option<Duration> {
+    if matches!(response.status_code, 429 | 503) {
+        if let Some(value) = response.header("retry-after") {
+            let value = value.trim();
+            if let Ok(secs) = value.parse::<u64>() {
+                return Some(Duration::from_secs(secs));
+            }
+            if let Ok(date) = httpdate::parse_http_date(value) {
+                return Some(date.duration_since(now).unwrap_or_default());
+            }
+        }
+    }
+
+    for prefix in ["x-ratelimit", "ratelimit"] {
+        let remaining = response
+            .header(&format!("{}-remaining", prefix))
+            .and_then(|v| v.trim().parse::<u64>().ok());
+        if remaining != Some(0) {
+            continue;
+        }
+        if let Some(reset) = response
+            .header(&format!("{}-reset", prefix))
+            .and_then(|v| v.trim().parse::<u64>().ok())
+        {
+            return Some(reset_delay(reset, now));
+        }
+    }
+
+    None
+}
+
+/// Delay until a rate limit reset given either as a Unix timestamp or in seconds
+fn reset_delay(reset: u64, now: SystemTime) -> Duration {
+    // Anything past 2001-09-09 is a timestamp, not a delay
+    if reset >= 1_000_000_000 {
+        let reset = SystemTime::UNIX_EPOCH + Duration::from_secs(reset);
+        reset.duration_since(now).unwrap_or_default()
+    } else {
+        Duration::from_secs(reset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(status_code: u16, headers: &[(&str, &str)]) -> RequestResponse {
+        RequestResponse {
+            headers: headers
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            ..RequestResponse::new(status_code)
+        }
+    }
+
+    #[test]
+    fn test_requested_pause_retry_after() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let seconds = response(429, &[("Retry-After", "30")]);
+        assert_eq!(
+            requested_pause(&seconds, now),
+            Some(Duration::from_secs(30))
+        );
+
+        let date = httpdate::fmt_http_date(now + Duration::from_secs(90));
+        let dated = response(503, &[("Retry-After", &date)]);
+        assert_eq!(requested_pause(&dated, now), Some(Duration::from_secs(90)));
+
+        // Retry-After on a success is not a throttle
+        let ok = response(200, &[("Retry-After", "30")]);
+        assert_eq!(requested_pause(&ok, now), None);
+    }
+
+    #[test]
+    fn test_requested_pause_rate_limit_headers() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let exhausted = response(
+            200,
+            &[
+                ("X-RateLimit-Remaining", "0"),
+                ("X-RateLimit-Reset", "1700000045"),
+            ],
+        );
+        assert_eq!(
+            requested_pause(&exhausted, now),
+            Some(Duration::from_secs(45))
+        );
+
+        let delta = response(
+            200,
+            &[("RateLimit-Remaining", "0"), ("RateLimit-Reset", "12")],
+        );
+        assert_eq!(requested_pause(&delta, now), Some(Duration::from_secs(12)));
+
+        let remaining = response(
+            200,
+            &[
+                ("X-RateLimit-Remaining", "7"),
+                ("X-RateLimit-Reset", "1700000045"),
+            ],
+        );
+        assert_eq!(requested_pause(&remaining, now), None);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate_limiter_token_bucket() {
+        let limiter = RateLimiter::new();
+        let budget = Some(RateLimit::per_second(20));
+        let started = Instant::now();
+
+        // The burst goes through, the next request waits for a refill
+        for _ in 0..20 {
+            limiter.acquire("https://example.com/a", budget).await;
+        }
+        assert_eq!(started.elapsed(), Duration::ZERO);
+        limiter.acquire("https://example.com/b", budget).await;
+        assert!(started.elapsed() >= Duration::from_millis(50));
+
+        // Other hosts have their own bucket
+        let other = Instant::now();
+        limiter.acquire("https://example.org/a", budget).await;
+        assert_eq!(other.elapsed(), Duration::ZERO);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate_limiter_pauses_host() {
+        let limiter = RateLimiter::new();
+        limiter.observe(
+            "https://example.com/a",
+            &response(429, &[("Retry-After", "5")]),
+        );
+        assert!(limiter.paused_for("https://example.com/b").is_some());
+        assert!(limiter.paused_for("https://example.org/b").is_none());
+
+        let blocked = tokio::time::timeout(
+            Duration::from_millis(50),
+            limiter.acquire("https://example.com/b", None),
+        )
+        .await;
+        assert!(blocked.is_err());
+        let other = tokio::time::timeout(
+            Duration::from_millis(50),
+            limiter.acquire("https://example.org/b", None),
+        )
+        .await;
+        assert!(other.is_ok());
+
+        // The host is free again once the pause is over
+        let started = Instant::now();
+        limiter.acquire("https://example.com/b", None).await;
+        assert!(started.elapsed() >= Duration::from_secs(4));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate_limiter_unlimited_without_budget() {
+        let limiter = RateLimiter::new();
+        let started = Instant::now();
+        for _ in 0..100 {
+            limiter.acquire("https://example.com/a", None).await;
+        }
+        assert_eq!(started.elapsed(), Duration::ZERO);
+    }
+}
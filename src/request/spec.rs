@@ -0,0 +1,161 @@
+//! Full description of a request: method, URL, headers, cookies, body,
+//! redirect policy and timeout
+
+use std::time::Duration;
+
+/// Methods a request can be sent with
+pub const METHODS: [&str; 5] = ["HEAD", "GET", "POST", "PUT", "PATCH"];
+
+/// The supported method matching `method`, whatever its case
+pub fn supported_method(method: &str) -> Option<&'static str> {
+    METHODS
+        .iter()
+        .copied()
+        .find(|supported| supported.eq_ignore_ascii_case(method))
+}
+
+/// Everything a transport needs to send one request
+///
+/// Sites describe their probe with `Site::build_request()`; every [`Request`]
+/// implementation sends it through [`Request::send`].
+///
+/// [`Request`]: crate::request::Request
+/// [`Request::send`]: crate::request::Request::send
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestSpec {
+    /// HTTP method, uppercase (e.g. HEAD, GET, POST)
+    pub method: String,
+    pub url: String,
+    /// Extra headers, sent in order
+    pub headers: Vec<(String, String)>,
+    /// Cookies, sent as one `Cookie` header
+    pub cookies: Vec<(String, String)>,
+    /// Request body (e.g. a JSON document for a POST)
+    pub body: Option<String>,
+    /// Whether to follow redirects; when false, 3xx responses are returned as-is
+    pub follow_redirects: bool,
+    /// Time allowed for this request (None: the transport's own timeout)
+    pub timeout: Option<Duration>,
+}
+
+impl RequestSpec {
+    /// Request `url` with `method`, following redirects and nothing else set
+    pub fn new(method: &str, url: &str) -> Self {
+        Self {
+            method: method.to_uppercase(),
+            url: url.to_string(),
+            headers: vec![],
+            cookies: vec![],
+            body: None,
+            follow_redirects: true,
+            timeout: None,
+        }
+    }
+
+    /// A GET request for `url`
+    pub fn get(url: &str) -> Self {
+        Self::new("GET", url)
+    }
+
+    /// A HEAD request for `url`
+    pub fn head(url: &str) -> Self {
+        Self::new("HEAD", url)
+    }
+
+    /// Add a header
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Add a cookie
+    pub fn with_cookie(mut self, name: &str, value: &str) -> Self {
+        self.cookies.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Set the body
+    pub fn with_body(mut self, body: impl Into<String>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// Set whether redirects are followed
+    pub fn with_follow_redirects(mut self, follow_redirects: bool) -> Self {
+        self.follow_redirects = follow_redirects;
+        self
+    }
+
+    /// Set the time allowed for the request
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Look up a header value (case-insensitive name)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Value of the `Cookie` header for the cookies, if there are any
+    pub fn cookie_header(&self) -> Option<String> {
+        if self.cookies.is_empty() {
+            return None;
+        }
+        let pairs: Vec<String> = self
+            .cookies
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect();
+        Some(pairs.join("; "))
+    }
+
+    /// Whether the response body is wanted (every method but HEAD)
+    pub fn reads_body(&self) -> bool {
+        self.method != "HEAD"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_spec_builder() {
+        let spec = RequestSpec::new("post", "https://api.example/check")
+            .with_header("Content-Type", "application/json")
+            .with_cookie("session", "abc")
+            .with_cookie("lang", "en")
+            .with_body(r#"{"username":"blue"}"#)
+            .with_follow_redirects(false)
+            .with_timeout(Duration::from_secs(5));
+        assert_eq!(spec.method, "POST");
+        assert_eq!(spec.header("content-type"), Some("application/json"));
+        assert_eq!(
+            spec.cookie_header().as_deref(),
+            Some("session=abc; lang=en")
+        );
+        assert_eq!(spec.body.as_deref(), Some(r#"{"username":"blue"}"#));
+        assert!(!spec.follow_redirects);
+        assert_eq!(spec.timeout, Some(Duration::from_secs(5)));
+        assert!(spec.reads_body());
+    }
+
+    #[test]
+    fn test_request_spec_defaults() {
+        let spec = RequestSpec::head("https://example.com/blue");
+        assert!(spec.follow_redirects);
+        assert!(spec.cookie_header().is_none());
+        assert!(!spec.reads_body());
+    }
+
+    #[test]
+    fn test_supported_method() {
+        assert_eq!(supported_method("patch"), Some("PATCH"));
+        assert_eq!(supported_method("DELETE"), None);
+        assert_eq!(supported_method("OPTIONS"), None);
+    }
+}
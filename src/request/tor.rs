@@ -1,6 +1,6 @@
 //! Tor request implementation (future)
 
-use crate::request::{Request, RequestResponse};
+use crate::request::{Request, RequestResponse, RequestSpec, RequestType};
 use crate::utils::error::{Result, SleuthError};
 use async_trait::async_trait;
 
@@ -33,6 +33,17 @@ impl Request for TorRequest {
             "Tor support not yet implemented".to_string(),
         ))
     }
+
+    async fn send(&self, _spec: &RequestSpec) -> Result<RequestResponse> {
+        // TODO: Send the full spec through Tor
+        Err(SleuthError::Unknown(
+            "Tor support not yet implemented".to_string(),
+        ))
+    }
+
+    fn request_type(&self) -> RequestType {
+        RequestType::Tor
+    }
 }
 
 #[cfg(test)]
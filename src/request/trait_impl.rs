@@ -1,14 +1,21 @@
 //! Request trait definition
 
+use crate::request::{RequestSpec, RequestType};
 use crate::utils::error::Result;
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 
 /// Response from a request
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RequestResponse {
     pub status_code: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub body: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub headers: Vec<(String, String)>,
+    /// URL of the final response, after any redirects
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub final_url: Option<String>,
 }
 
 impl RequestResponse {
@@ -17,6 +24,7 @@ impl RequestResponse {
             status_code,
             body: None,
             headers: vec![],
+            final_url: None,
         }
     }
 
@@ -25,6 +33,24 @@ impl RequestResponse {
             status_code,
             body: Some(body),
             headers: vec![],
+            final_url: None,
+        }
+    }
+
+    /// Look up a header value (case-insensitive name)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Redirect target of a 3xx response (the `Location` header)
+    pub fn location(&self) -> Option<&str> {
+        if (300..=399).contains(&self.status_code) {
+            self.header("Location")
+        } else {
+            None
         }
     }
 }
@@ -50,6 +76,46 @@ pub trait Request: Send + Sync {
             ))),
         }
     }
+
+    /// Make a request without following redirects, so 3xx responses and their
+    /// `Location` header are returned as-is
+    /// Transports that cannot control redirects (default) follow them
+    async fn request_no_redirect(&self, method: &str, url: &str) -> Result<RequestResponse> {
+        self.request(method, url).await
+    }
+
+    /// Send a fully specified request: method, headers, cookies, body,
+    /// redirect policy and timeout
+    /// The default sends the method and URL through [`Request::request`] or
+    /// [`Request::request_no_redirect`] and fails on headers, cookies or a
+    /// body, which it cannot send; transports override it to honour the whole spec
+    async fn send(&self, spec: &RequestSpec) -> Result<RequestResponse> {
+        let unsupported = if spec.body.is_some() {
+            Some("Request bodies")
+        } else if !spec.headers.is_empty() {
+            Some("Request headers")
+        } else if !spec.cookies.is_empty() {
+            Some("Cookies")
+        } else {
+            None
+        };
+        if let Some(unsupported) = unsupported {
+            return Err(crate::utils::error::SleuthError::Unknown(format!(
+                "{} are not supported by this transport ({} {})",
+                unsupported, spec.method, spec.url
+            )));
+        }
+        if spec.follow_redirects {
+            self.request(&spec.method, &spec.url).await
+        } else {
+            self.request_no_redirect(&spec.method, &spec.url).await
+        }
+    }
+
+    /// Transport the requests go through (default: plain HTTP)
+    fn request_type(&self) -> RequestType {
+        RequestType::Http
+    }
 }
 
 #[cfg(test)]
@@ -62,6 +128,7 @@ mod tests {
         assert_eq!(response.status_code, 200);
         assert!(response.body.is_none());
         assert!(response.headers.is_empty());
+        assert!(response.final_url.is_none());
     }
 
     #[test]
@@ -72,6 +139,50 @@ mod tests {
         assert!(response.headers.is_empty());
     }
 
+    #[test]
+    fn test_request_response_location() {
+        let mut response = RequestResponse::new(302);
+        response.headers = vec![("location".to_string(), "/login".to_string())];
+        assert_eq!(response.header("Location"), Some("/login"));
+        assert_eq!(response.location(), Some("/login"));
+
+        response.status_code = 200;
+        assert_eq!(response.location(), None);
+    }
+
+    /// Transport that only knows HEAD and GET
+    struct PlainRequest;
+
+    #[async_trait]
+    impl Request for PlainRequest {
+        async fn head(&self, _url: &str) -> Result<RequestResponse> {
+            Ok(RequestResponse::new(204))
+        }
+
+        async fn get(&self, _url: &str) -> Result<RequestResponse> {
+            Ok(RequestResponse::new(200))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_send_default() {
+        let spec = RequestSpec::head("https://example.com/blue");
+        assert_eq!(PlainRequest.send(&spec).await.unwrap().status_code, 204);
+        let spec = RequestSpec::get("https://example.com/blue").with_follow_redirects(false);
+        assert_eq!(PlainRequest.send(&spec).await.unwrap().status_code, 200);
+
+        // Other methods, headers, cookies and bodies cannot be sent
+        let spec = RequestSpec::new("POST", "https://example.com/check");
+        assert!(PlainRequest.send(&spec).await.is_err());
+        let spec = RequestSpec::get("https://example.com/check").with_body("x");
+        assert!(PlainRequest.send(&spec).await.is_err());
+        let spec = RequestSpec::head("https://example.com/blue").with_header("Accept", "*/*");
+        let err = PlainRequest.send(&spec).await.unwrap_err();
+        assert!(err.to_string().contains("headers"));
+        let spec = RequestSpec::head("https://example.com/blue").with_cookie("session", "abc");
+        assert!(PlainRequest.send(&spec).await.is_err());
+    }
+
     #[test]
     fn test_request_response_different_status_codes() {
         let response_404 = RequestResponse::new(404);
@@ -0,0 +1,647 @@
+//! Data-driven site checkers built from manifest entries
+
+use crate::data::site_info::SiteType;
+use crate::request::{spec, RateLimit};
+use crate::sites::detection::RuleSet;
+use crate::sites::Site;
+use crate::utils::error::{Result, SleuthError};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// A site definition as it appears in a manifest file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SiteDefinition {
+    /// Site name
+    pub name: String,
+    /// Site type/category
+    #[serde(rename = "type", default = "default_site_type")]
+    pub site_type: SiteType,
+    /// Profile URL pattern with `{}` as the username placeholder
+    pub url_pattern: String,
+    /// URL pattern to request instead of `url_pattern` (e.g. an API endpoint)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub probe_url: Option<String>,
+    /// HTTP method to use (HEAD, GET, POST, PUT or PATCH)
+    #[serde(default = "default_method")]
+    pub method: String,
+    /// Extra headers to send with the request
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// Cookies to send with the request
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub cookies: BTreeMap<String, String>,
+    /// Body to send with POST, PUT or PATCH requests, with `{}` as the username
+    /// placeholder
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<RequestBody>,
+    /// Whether the site needs browser rendering to be verified
+    #[serde(default)]
+    pub requires_browser: bool,
+    /// Whether the probe returns JSON (enables JSON conditions, forces GET)
+    #[serde(default)]
+    pub expects_json: bool,
+    /// Whether to follow redirects; when false, 3xx responses reach the rules
+    #[serde(default = "default_follow_redirects")]
+    pub follow_redirects: bool,
+    /// Request timeout in seconds, overriding the scan timeout
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+    /// Request budget for the site's host
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RateLimit>,
+    /// Detection rules, evaluated in order; the first one that fires decides
+    #[serde(default, skip_serializing_if = "RuleSet::is_empty")]
+    pub rules: RuleSet,
+    /// Regex a username must match to be possible on this site
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username_pattern: Option<String>,
+    /// Free-form tags (e.g. source categories that have no `SiteType`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Usernames known to exist on the site, for self-testing
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub known_usernames: Vec<String>,
+    /// A username known not to exist on the site, for self-testing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unclaimed_username: Option<String>,
+}
+
+impl SiteDefinition {
+    /// Create a definition with default settings for everything but the
+    /// name, type and URL pattern
+    pub fn new(name: &str, site_type: SiteType, url_pattern: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            site_type,
+            url_pattern: url_pattern.to_string(),
+            probe_url: None,
+            method: default_method(),
+            headers: BTreeMap::new(),
+            cookies: BTreeMap::new(),
+            body: None,
+            requires_browser: false,
+            expects_json: false,
+            follow_redirects: default_follow_redirects(),
+            timeout: None,
+            rate_limit: None,
+            rules: RuleSet::default(),
+            username_pattern: None,
+            tags: vec![],
+            known_usernames: vec![],
+            unclaimed_username: None,
+        }
+    }
+}
+
+/// Body of a manifest request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RequestBody {
+    /// Raw text, sent as written; the username is form-encoded for
+    /// `application/x-www-form-urlencoded` bodies and escaped for JSON ones
+    Text(String),
+    /// A JSON document, sent with `Content-Type: application/json` unless the
+    /// site sets its own; the username is escaped wherever a string holds `{}`
+    Json(serde_json::Value),
+}
+
+impl RequestBody {
+    /// Whether the body carries the username (has a `{}` placeholder)
+    pub fn has_placeholder(&self) -> bool {
+        self.render("", None) != self.render("_", None)
+    }
+
+    /// The body for a username, sent with the given `Content-Type`
+    pub fn render(&self, username: &str, content_type: Option<&str>) -> String {
+        match self {
+            RequestBody::Text(text) => {
+                let media_type = content_type
+                    .and_then(|value| value.split(';').next())
+                    .map(|value| value.trim().to_ascii_lowercase())
+                    .unwrap_or_default();
+                let username = if media_type == "application/x-www-form-urlencoded" {
+                    form_encode(username)
+                } else if media_type == "application/json" || media_type.ends_with("+json") {
+                    let quoted = serde_json::Value::from(username).to_string();
+                    quoted[1..quoted.len() - 1].to_string()
+                } else {
+                    username.to_string()
+                };
+                text.replace("{}", &username)
+            }
+            RequestBody::Json(value) => fill_json(value, username).to_string(),
+        }
+    }
+}
+
+/// Encode a value for an `application/x-www-form-urlencoded` body
+fn form_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'*' | b'-' | b'.' | b'_' => {
+                encoded.push(byte as char)
+            }
+            b' ' => encoded.push('+'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Replace `{}` with the username in every string of a JSON value
+fn fill_json(value: &serde_json::Value, username: &str) -> serde_json::Value {
+    use serde_json::Value;
+    match value {
+        Value::String(s) => Value::String(s.replace("{}", username)),
+        Value::Array(items) => Value::Array(items.iter().map(|v| fill_json(v, username)).collect()),
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(k, v)| (k.clone(), fill_json(v, username)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+fn default_site_type() -> SiteType {
+    SiteType::Other
+}
+
+fn default_method() -> String {
+    "HEAD".to_string()
+}
+
+fn default_follow_redirects() -> bool {
+    true
+}
+
+/// Site checker driven entirely by a [`SiteDefinition`]
+#[derive(Debug, Clone)]
+pub struct DeclarativeSite {
+    definition: SiteDefinition,
+    method: &'static str,
+    username_regex: Option<Regex>,
+}
+
+impl DeclarativeSite {
+    /// Create a site from its definition, validating the entry
+    pub fn new(definition: SiteDefinition) -> Result<Self> {
+        if definition.name.trim().is_empty() {
+            return Err(SleuthError::Config(
+                "Site definition is missing a name".to_string(),
+            ));
+        }
+
+        if !definition.url_pattern.contains("{}") {
+            return Err(SleuthError::Config(format!(
+                "Site '{}': url_pattern must contain a '{{}}' placeholder",
+                definition.name
+            )));
+        }
+
+        if definition.timeout == Some(0) {
+            return Err(SleuthError::Config(format!(
+                "Site '{}': timeout must be at least 1 second",
+                definition.name
+            )));
+        }
+
+        if let Some(rate_limit) = &definition.rate_limit {
+            if rate_limit.requests == 0 || rate_limit.per_secs == 0 {
+                return Err(SleuthError::Config(format!(
+                    "Site '{}': rate_limit requests and per_secs must be at least 1",
+                    definition.name
+                )));
+            }
+        }
+
+        if let Some(rule) = definition
+            .rules
+            .rules()
+            .iter()
+            .find(|rule| rule.confidence.is_some_and(|c| c > 100))
+        {
+            return Err(SleuthError::Config(format!(
+                "Site '{}': rule '{}' confidence must be at most 100",
+                definition.name, rule.name
+            )));
+        }
+
+        // POST checks may send the username in the body instead
+        let body_placeholder = definition
+            .body
+            .as_ref()
+            .is_some_and(RequestBody::has_placeholder);
+        if let Some(probe_url) = &definition.probe_url {
+            if !probe_url.contains("{}") && !body_placeholder {
+                return Err(SleuthError::Config(format!(
+                    "Site '{}': probe_url must contain a '{{}}' placeholder, or the body one",
+                    definition.name
+                )));
+            }
+        }
+
+        let method = spec::supported_method(&definition.method).ok_or_else(|| {
+            SleuthError::Config(format!(
+                "Site '{}': unsupported HTTP method: {}",
+                definition.name,
+                definition.method.to_uppercase()
+            ))
+        })?;
+
+        if definition.body.is_some() && matches!(method, "HEAD" | "GET") {
+            return Err(SleuthError::Config(format!(
+                "Site '{}': a body needs a POST, PUT or PATCH method",
+                definition.name
+            )));
+        }
+
+        let username_regex = match &definition.username_pattern {
+            Some(pattern) => Some(Regex::new(pattern).map_err(|e| {
+                SleuthError::Config(format!(
+                    "Site '{}': invalid username_pattern: {}",
+                    definition.name, e
+                ))
+            })?),
+            None => None,
+        };
+
+        Ok(Self {
+            definition,
+            method,
+            username_regex,
+        })
+    }
+
+    /// Get the definition this site was built from
+    pub fn definition(&self) -> &SiteDefinition {
+        &self.definition
+    }
+}
+
+impl Site for DeclarativeSite {
+    fn name(&self) -> &str {
+        &self.definition.name
+    }
+
+    fn url_pattern(&self) -> &str {
+        &self.definition.url_pattern
+    }
+
+    fn site_type(&self) -> SiteType {
+        self.definition.site_type
+    }
+
+    fn probe_url_pattern(&self) -> &str {
+        self.definition
+            .probe_url
+            .as_deref()
+            .unwrap_or(&self.definition.url_pattern)
+    }
+
+    fn username_pattern(&self) -> Option<&Regex> {
+        self.username_regex.as_ref()
+    }
+
+    fn detection_rules(&self) -> Option<&RuleSet> {
+        // Without rules, fall back to the default status-code detection
+        if self.definition.rules.is_empty() {
+            None
+        } else {
+            Some(&self.definition.rules)
+        }
+    }
+
+    fn http_method(&self) -> &'static str {
+        self.method
+    }
+
+    fn headers(&self) -> Vec<(&str, &str)> {
+        let mut headers: Vec<(&str, &str)> = self
+            .definition
+            .headers
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        let json_body = matches!(self.definition.body, Some(RequestBody::Json(_)));
+        if json_body
+            && !headers
+                .iter()
+                .any(|(k, _)| k.eq_ignore_ascii_case("Content-Type"))
+        {
+            headers.push(("Content-Type", "application/json"));
+        }
+        headers
+    }
+
+    fn cookies(&self) -> Vec<(&str, &str)> {
+        self.definition
+            .cookies
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect()
+    }
+
+    fn request_body(&self, username: &str) -> Option<String> {
+        let body = self.definition.body.as_ref()?;
+        let headers = self.headers();
+        let content_type = headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case("Content-Type"))
+            .map(|(_, v)| *v);
+        Some(body.render(username, content_type))
+    }
+
+    fn requires_browser(&self) -> bool {
+        self.definition.requires_browser
+    }
+
+    fn expects_json(&self) -> bool {
+        self.definition.expects_json
+    }
+
+    fn follow_redirects(&self) -> bool {
+        self.definition.follow_redirects
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.definition.timeout.map(Duration::from_secs)
+    }
+
+    fn rate_limit(&self) -> Option<RateLimit> {
+        self.definition.rate_limit
+    }
+
+    fn tags(&self) -> &[String] {
+        &self.definition.tags
+    }
+
+    fn claimed_username(&self) -> Option<&str> {
+        self.definition.known_usernames.first().map(String::as_str)
+    }
+
+    fn unclaimed_username(&self) -> Option<&str> {
+        self.definition.unclaimed_username.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::RequestResponse;
+    use crate::sites::detection::{Condition, Rule, Verdict};
+
+    fn definition(rules: Vec<Rule>) -> SiteDefinition {
+        SiteDefinition {
+            method: "GET".to_string(),
+            rules: RuleSet::new(rules),
+            ..SiteDefinition::new("Example", SiteType::Dev, "https://example.com/{}")
+        }
+    }
+
+    #[test]
+    fn test_declarative_site_basic_fields() {
+        let site = DeclarativeSite::new(definition(vec![])).unwrap();
+        assert_eq!(site.name(), "Example");
+        assert_eq!(site.site_type(), SiteType::Dev);
+        assert_eq!(site.http_method(), "GET");
+        assert_eq!(site.build_url("user"), "https://example.com/user");
+        assert!(!site.requires_browser());
+        assert!(!site.expects_json());
+        assert!(site.follow_redirects());
+        assert!(site.claimed_username().is_none());
+        assert!(site.tags().is_empty());
+        assert!(site.timeout().is_none());
+    }
+
+    #[test]
+    fn test_declarative_site_default_detection() {
+        let site = DeclarativeSite::new(definition(vec![])).unwrap();
+        assert_eq!(site.parse_response("user", 200, None), Some(true));
+        assert_eq!(site.parse_response("user", 404, None), Some(false));
+        assert_eq!(site.parse_response("user", 500, None), None);
+    }
+
+    #[test]
+    fn test_declarative_site_rules_in_order() {
+        let site = DeclarativeSite::new(definition(vec![
+            Rule::new(
+                "error-page",
+                Verdict::NotFound,
+                vec![
+                    Condition::status(200),
+                    Condition::body_contains("User not found"),
+                ],
+            ),
+            Rule::new("ok", Verdict::Found, vec![Condition::status(200)]),
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            site.parse_response("user", 200, Some("<h1>User not found</h1>")),
+            Some(false)
+        );
+        assert_eq!(
+            site.parse_response("user", 200, Some("<h1>user</h1>")),
+            Some(true)
+        );
+        // No rule fires: uncertain
+        assert_eq!(site.parse_response("user", 500, None), None);
+    }
+
+    #[test]
+    fn test_declarative_site_detect_reports_rule() {
+        let site = DeclarativeSite::new(definition(vec![Rule::new(
+            "missing",
+            Verdict::NotFound,
+            vec![Condition::status(404)],
+        )]))
+        .unwrap();
+
+        let detection = site.detect("user", &RequestResponse::new(404));
+        assert_eq!(detection.exists, Some(false));
+        assert_eq!(detection.rule.as_deref(), Some("missing"));
+    }
+
+    #[test]
+    fn test_declarative_site_probe_url() {
+        let site = DeclarativeSite::new(definition(vec![])).unwrap();
+        assert_eq!(site.build_probe_url("user"), "https://example.com/user");
+
+        let mut def = definition(vec![]);
+        def.probe_url = Some("https://api.example.com/users/{}".to_string());
+        let site = DeclarativeSite::new(def).unwrap();
+        assert_eq!(site.build_url("user"), "https://example.com/user");
+        assert_eq!(
+            site.build_probe_url("user"),
+            "https://api.example.com/users/user"
+        );
+    }
+
+    #[test]
+    fn test_declarative_site_headers() {
+        let mut def = definition(vec![]);
+        def.headers
+            .insert("Accept".to_string(), "text/html".to_string());
+        let site = DeclarativeSite::new(def).unwrap();
+        assert_eq!(site.headers(), vec![("Accept", "text/html")]);
+    }
+
+    #[test]
+    fn test_declarative_site_post_body() {
+        let manifest = r#"{
+            "name": "Signup",
+            "url_pattern": "https://example.com/{}",
+            "probe_url": "https://api.example.com/check?u={}",
+            "method": "post",
+            "cookies": { "consent": "yes" },
+            "body": { "query": "user(\"{}\")", "limit": 1 }
+        }"#;
+        let def: SiteDefinition = serde_json::from_str(manifest).unwrap();
+        let site = DeclarativeSite::new(def).unwrap();
+        assert_eq!(site.http_method(), "POST");
+        assert_eq!(site.headers(), vec![("Content-Type", "application/json")]);
+        assert_eq!(site.cookies(), vec![("consent", "yes")]);
+
+        let spec = site.build_request("a\"b");
+        assert_eq!(spec.url, "https://api.example.com/check?u=a\"b");
+        let body: serde_json::Value = serde_json::from_str(spec.body.as_deref().unwrap()).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({ "query": "user(\"a\"b\")", "limit": 1 })
+        );
+        assert_eq!(spec.cookie_header().as_deref(), Some("consent=yes"));
+
+        // Text bodies are sent as written, with the site's own content type
+        let mut def = definition(vec![]);
+        def.method = "POST".to_string();
+        def.headers.insert(
+            "Content-Type".to_string(),
+            "application/x-www-form-urlencoded".to_string(),
+        );
+        def.body = Some(RequestBody::Text("username={}".to_string()));
+        let site = DeclarativeSite::new(def).unwrap();
+        assert_eq!(site.request_body("blue").as_deref(), Some("username=blue"));
+        assert_eq!(
+            site.build_request("blue").header("content-type"),
+            Some("application/x-www-form-urlencoded")
+        );
+    }
+
+    #[test]
+    fn test_declarative_site_form_encodes_text_body() {
+        let mut def = definition(vec![]);
+        def.method = "POST".to_string();
+        def.headers.insert(
+            "Content-Type".to_string(),
+            "application/x-www-form-urlencoded; charset=UTF-8".to_string(),
+        );
+        def.body = Some(RequestBody::Text("username={}&check=1".to_string()));
+        let site = DeclarativeSite::new(def).unwrap();
+        assert_eq!(
+            site.request_body("a&b=c d/é").as_deref(),
+            Some("username=a%26b%3Dc+d%2F%C3%A9&check=1")
+        );
+        assert_eq!(
+            site.request_body("blue.sky_1").as_deref(),
+            Some("username=blue.sky_1&check=1")
+        );
+    }
+
+    #[test]
+    fn test_declarative_site_escapes_json_text_body() {
+        let mut def = definition(vec![]);
+        def.method = "POST".to_string();
+        def.headers
+            .insert("content-type".to_string(), "application/json".to_string());
+        def.body = Some(RequestBody::Text(r#"{"username": "{}"}"#.to_string()));
+        let site = DeclarativeSite::new(def).unwrap();
+        let body = site.request_body(r#"a"b\c"#).unwrap();
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(body, serde_json::json!({ "username": r#"a"b\c"# }));
+
+        // Other content types get the username as it is
+        let text = RequestBody::Text("user={}".to_string());
+        assert_eq!(text.render("a&b", Some("text/plain")), "user=a&b");
+        assert_eq!(text.render("a&b", None), "user=a&b");
+    }
+
+    #[test]
+    fn test_declarative_site_rejects_invalid_definitions() {
+        let mut def = definition(vec![]);
+        def.url_pattern = "https://example.com/user".to_string();
+        assert!(DeclarativeSite::new(def).is_err());
+
+        let mut def = definition(vec![]);
+        def.probe_url = Some("https://api.example.com/users".to_string());
+        assert!(DeclarativeSite::new(def).is_err());
+
+        let mut def = definition(vec![]);
+        def.method = "DELETE".to_string();
+        assert!(DeclarativeSite::new(def).is_err());
+
+        let mut def = definition(vec![]);
+        def.body = Some(RequestBody::Text("user={}".to_string()));
+        assert!(DeclarativeSite::new(def).is_err());
+
+        let mut def = definition(vec![]);
+        def.name = " ".to_string();
+        assert!(DeclarativeSite::new(def).is_err());
+
+        let mut def = definition(vec![]);
+        def.username_pattern = Some("([".to_string());
+        assert!(DeclarativeSite::new(def).is_err());
+
+        let mut def = definition(vec![]);
+        def.timeout = Some(0);
+        assert!(DeclarativeSite::new(def).is_err());
+
+        let mut def = definition(vec![]);
+        def.rate_limit = Some(RateLimit::per_minute(0));
+        assert!(DeclarativeSite::new(def).is_err());
+
+        let def = definition(vec![Rule::new(
+            "ok",
+            Verdict::Found,
+            vec![Condition::success()],
+        )
+        .with_confidence(101)]);
+        assert!(DeclarativeSite::new(def).is_err());
+    }
+
+    #[test]
+    fn test_declarative_site_rate_limit() {
+        let site = DeclarativeSite::new(definition(vec![])).unwrap();
+        assert!(site.rate_limit().is_none());
+
+        let mut def = definition(vec![]);
+        def.rate_limit = Some(RateLimit::per_minute(30));
+        let site = DeclarativeSite::new(def).unwrap();
+        assert_eq!(site.rate_limit(), Some(RateLimit::per_minute(30)));
+    }
+
+    #[test]
+    fn test_declarative_site_timeout() {
+        let mut def = definition(vec![]);
+        def.timeout = Some(25);
+        let site = DeclarativeSite::new(def).unwrap();
+        assert_eq!(site.timeout(), Some(Duration::from_secs(25)));
+    }
+
+    #[test]
+    fn test_declarative_site_username_pattern() {
+        let mut def = definition(vec![]);
+        def.username_pattern = Some("^[a-z0-9]{3,8}$".to_string());
+        let site = DeclarativeSite::new(def).unwrap();
+        assert!(site.validate_username("user123"));
+        assert!(!site.validate_username("ab"));
+        assert!(!site.validate_username("User.Name"));
+
+        let site = DeclarativeSite::new(definition(vec![])).unwrap();
+        assert!(site.validate_username("anything goes"));
+    }
+}
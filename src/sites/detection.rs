@@ -0,0 +1,677 @@
+//! Rule-based detection of whether a username exists
+//!
+//! A [`RuleSet`] is an ordered list of [`Rule`]s. Each rule has a verdict and a list
+//! of [`Condition`]s that must all hold; the first rule that fires decides the
+//! outcome and is reported by name. When no rule fires the result is uncertain.
+//!
+//! Every rule also carries a confidence: rules that look at the content of the
+//! response are trusted more than rules that only look at the status code, and
+//! catch-all rules without conditions least of all. A rule can set its own.
+
+use crate::request::RequestResponse;
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::cell::OnceCell;
+
+/// Confidence of a rule that inspects the response content (body, JSON, headers, redirects)
+pub const CONTENT_CONFIDENCE: u8 = 90;
+
+/// Confidence of a rule that only inspects the status code
+pub const STATUS_CONFIDENCE: u8 = 70;
+
+/// Confidence of a catch-all rule without conditions
+pub const FALLBACK_CONFIDENCE: u8 = 40;
+
+/// Verdict a detection rule assigns when it fires
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    /// The username exists on the site
+    Found,
+    /// The username does not exist on the site
+    NotFound,
+}
+
+/// The parts of a response that rules are evaluated against
+#[derive(Debug, Clone)]
+pub struct ResponseView<'a> {
+    pub status_code: u16,
+    pub body: Option<&'a str>,
+    pub headers: &'a [(String, String)],
+    pub final_url: Option<&'a str>,
+    /// Body parsed as JSON, on first use
+    json: OnceCell<Option<Value>>,
+}
+
+impl<'a> ResponseView<'a> {
+    /// Create a view with only a status code and body
+    pub fn new(status_code: u16, body: Option<&'a str>) -> Self {
+        Self {
+            status_code,
+            body,
+            headers: &[],
+            final_url: None,
+            json: OnceCell::new(),
+        }
+    }
+
+    /// Redirect target of a 3xx response (the `Location` header)
+    pub fn location(&self) -> Option<&'a str> {
+        if (300..=399).contains(&self.status_code) {
+            self.header("Location")
+        } else {
+            None
+        }
+    }
+
+    /// Get the body parsed as JSON (None if there is no body or it is not valid JSON)
+    pub fn json(&self) -> Option<&Value> {
+        self.json
+            .get_or_init(|| self.body.and_then(|body| serde_json::from_str(body).ok()))
+            .as_ref()
+    }
+
+    /// Look up a header value (case-insensitive name)
+    pub fn header(&self, name: &str) -> Option<&'a str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+impl<'a> From<&'a RequestResponse> for ResponseView<'a> {
+    fn from(response: &'a RequestResponse) -> Self {
+        Self {
+            status_code: response.status_code,
+            body: response.body.as_deref(),
+            headers: &response.headers,
+            final_url: response.final_url.as_deref(),
+            json: OnceCell::new(),
+        }
+    }
+}
+
+/// Look up a value in a JSON document
+///
+/// `path` is either a JSON pointer (`/data/children/0`) or a dotted path
+/// (`data.children.0`); array elements are addressed by index.
+pub fn json_lookup<'v>(value: &'v Value, path: &str) -> Option<&'v Value> {
+    if path.is_empty() || path.starts_with('/') {
+        return value.pointer(path);
+    }
+    path.split('.')
+        .try_fold(value, |current, key| match current {
+            Value::Object(map) => map.get(key),
+            Value::Array(items) => key.parse::<usize>().ok().and_then(|idx| items.get(idx)),
+            _ => None,
+        })
+}
+
+/// A compiled regular expression that (de)serializes as its source string
+#[derive(Debug, Clone)]
+pub struct Pattern(Regex);
+
+impl Pattern {
+    /// Compile a pattern
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        Regex::new(pattern).map(Self)
+    }
+
+    /// Check whether the pattern matches anywhere in `text`
+    pub fn is_match(&self, text: &str) -> bool {
+        self.0.is_match(text)
+    }
+
+    /// Get the pattern source
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Serialize for Pattern {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Pattern {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let source = String::deserialize(deserializer)?;
+        Pattern::new(&source).map_err(serde::de::Error::custom)
+    }
+}
+
+/// A condition on a response
+///
+/// Body conditions never match when no body was fetched (e.g. HEAD requests),
+/// JSON conditions never match when the body is not valid JSON, and final-URL
+/// conditions never match when the final URL is unknown.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Condition {
+    /// Status code is one of the listed codes
+    Status(Vec<u16>),
+    /// Status code is within an inclusive range
+    StatusRange(u16, u16),
+    /// Body contains the text (case-sensitive)
+    BodyContains(String),
+    /// Body is present and does not contain the text
+    BodyNotContains(String),
+    /// Body matches the regular expression
+    BodyMatches(Pattern),
+    /// Body is at least this many bytes long
+    MinLength(usize),
+    /// Body is at most this many bytes long
+    MaxLength(usize),
+    /// Header is present, optionally with an exact (case-insensitive) value
+    Header {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        value: Option<String>,
+    },
+    /// JSON body has a value at the path (see [`json_lookup`])
+    JsonExists(String),
+    /// JSON body has exactly this value at the path
+    JsonEquals { path: String, value: Value },
+    /// JSON body has a non-empty array, object or string at the path
+    JsonNonEmpty(String),
+    /// Response is a redirect whose `Location` contains the text
+    LocationContains(String),
+    /// Response is a redirect whose `Location` matches the regular expression
+    LocationMatches(Pattern),
+    /// Final URL (after redirects) contains the text
+    FinalUrlContains(String),
+    /// Final URL (after redirects) matches the regular expression
+    FinalUrlMatches(Pattern),
+    /// At least one of the conditions holds
+    Any(Vec<Condition>),
+    /// All of the conditions hold
+    All(Vec<Condition>),
+    /// The condition does not hold
+    Not(Box<Condition>),
+}
+
+impl Condition {
+    /// Status code is 2xx
+    pub fn success() -> Self {
+        Condition::StatusRange(200, 299)
+    }
+
+    /// Status code is 3xx (only seen by sites that do not follow redirects)
+    pub fn redirect() -> Self {
+        Condition::StatusRange(300, 399)
+    }
+
+    /// Status code equals `code`
+    pub fn status(code: u16) -> Self {
+        Condition::Status(vec![code])
+    }
+
+    /// Body contains `text`
+    pub fn body_contains(text: &str) -> Self {
+        Condition::BodyContains(text.to_string())
+    }
+
+    /// Body matches `pattern`
+    ///
+    /// # Panics
+    /// Panics if the pattern is invalid; meant for built-in rules
+    pub fn body_matches(pattern: &str) -> Self {
+        Condition::BodyMatches(Pattern::new(pattern).expect("invalid built-in pattern"))
+    }
+
+    /// Whether the condition looks at what the response says, beyond its
+    /// status code and size (body length alone says little about a profile)
+    pub fn inspects_content(&self) -> bool {
+        match self {
+            Condition::Status(_)
+            | Condition::StatusRange(..)
+            | Condition::MinLength(_)
+            | Condition::MaxLength(_) => false,
+            Condition::Any(conditions) | Condition::All(conditions) => {
+                conditions.iter().any(Condition::inspects_content)
+            }
+            Condition::Not(condition) => condition.inspects_content(),
+            _ => true,
+        }
+    }
+
+    /// Check the condition against a response
+    pub fn matches(&self, response: &ResponseView<'_>) -> bool {
+        match self {
+            Condition::Status(codes) => codes.contains(&response.status_code),
+            Condition::StatusRange(min, max) => (*min..=*max).contains(&response.status_code),
+            Condition::BodyContains(text) => response
+                .body
+                .is_some_and(|body| body.contains(text.as_str())),
+            Condition::BodyNotContains(text) => response
+                .body
+                .is_some_and(|body| !body.contains(text.as_str())),
+            Condition::BodyMatches(pattern) => response.body.is_some_and(|b| pattern.is_match(b)),
+            Condition::MinLength(min) => response.body.is_some_and(|body| body.len() >= *min),
+            Condition::MaxLength(max) => response.body.is_some_and(|body| body.len() <= *max),
+            Condition::Header { name, value } => match (response.header(name), value) {
+                (Some(actual), Some(expected)) => actual.eq_ignore_ascii_case(expected),
+                (Some(_), None) => true,
+                (None, _) => false,
+            },
+            Condition::JsonExists(path) => response
+                .json()
+                .is_some_and(|json| json_lookup(json, path).is_some()),
+            Condition::JsonEquals { path, value } => response
+                .json()
+                .and_then(|json| json_lookup(json, path))
+                .is_some_and(|found| found == value),
+            Condition::JsonNonEmpty(path) => response
+                .json()
+                .and_then(|json| json_lookup(json, path))
+                .is_some_and(|found| match found {
+                    Value::Array(items) => !items.is_empty(),
+                    Value::Object(map) => !map.is_empty(),
+                    Value::String(text) => !text.is_empty(),
+                    _ => false,
+                }),
+            Condition::LocationContains(text) => response
+                .location()
+                .is_some_and(|location| location.contains(text.as_str())),
+            Condition::LocationMatches(pattern) => response
+                .location()
+                .is_some_and(|location| pattern.is_match(location)),
+            Condition::FinalUrlContains(text) => response
+                .final_url
+                .is_some_and(|url| url.contains(text.as_str())),
+            Condition::FinalUrlMatches(pattern) => {
+                response.final_url.is_some_and(|url| pattern.is_match(url))
+            }
+            Condition::Any(conditions) => conditions.iter().any(|c| c.matches(response)),
+            Condition::All(conditions) => conditions.iter().all(|c| c.matches(response)),
+            Condition::Not(condition) => !condition.matches(response),
+        }
+    }
+}
+
+/// A named rule: fires when every condition in `when` holds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rule {
+    /// Rule name, reported when the rule fires
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    /// Verdict to return when the rule fires
+    pub verdict: Verdict,
+    /// Conditions that must all hold (an empty list always fires)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub when: Vec<Condition>,
+    /// Confidence (0-100) of the verdict, instead of the one derived from `when`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<u8>,
+}
+
+impl Rule {
+    /// Create a named rule
+    pub fn new(name: &str, verdict: Verdict, when: Vec<Condition>) -> Self {
+        Self {
+            name: name.to_string(),
+            verdict,
+            when,
+            confidence: None,
+        }
+    }
+
+    /// Set the confidence of the verdict explicitly
+    pub fn with_confidence(mut self, confidence: u8) -> Self {
+        self.confidence = Some(confidence);
+        self
+    }
+
+    /// Confidence (0-100) of the verdict when the rule fires
+    pub fn confidence(&self) -> u8 {
+        self.confidence.unwrap_or_else(|| {
+            if self.when.is_empty() {
+                FALLBACK_CONFIDENCE
+            } else if self.when.iter().any(Condition::inspects_content) {
+                CONTENT_CONFIDENCE
+            } else {
+                STATUS_CONFIDENCE
+            }
+        })
+    }
+
+    /// Check whether the rule fires for a response
+    pub fn matches(&self, response: &ResponseView<'_>) -> bool {
+        self.when.iter().all(|c| c.matches(response))
+    }
+}
+
+/// The rule that decided a detection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleMatch {
+    /// Verdict of the rule
+    pub verdict: Verdict,
+    /// Name of the rule (or `rule #N` for unnamed rules)
+    pub rule: String,
+    /// Confidence (0-100) of the verdict
+    pub confidence: u8,
+}
+
+/// An ordered list of rules; the first rule that fires decides
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RuleSet {
+    rules: Vec<Rule>,
+}
+
+impl RuleSet {
+    /// Create a rule set from rules in evaluation order
+    pub fn new(rules: Vec<Rule>) -> Self {
+        Self { rules }
+    }
+
+    /// Get the rules in evaluation order
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Check whether the set has no rules
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Evaluate the rules in order, returning the first one that fires
+    pub fn evaluate(&self, response: &ResponseView<'_>) -> Option<RuleMatch> {
+        self.rules
+            .iter()
+            .enumerate()
+            .find(|(_, rule)| rule.matches(response))
+            .map(|(idx, rule)| RuleMatch {
+                verdict: rule.verdict,
+                rule: if rule.name.is_empty() {
+                    format!("rule #{}", idx + 1)
+                } else {
+                    rule.name.clone()
+                },
+                confidence: rule.confidence(),
+            })
+    }
+}
+
+/// Outcome of running detection on a response
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Detection {
+    /// Some(true) if exists, Some(false) if not found, None if uncertain
+    pub exists: Option<bool>,
+    /// Rule that decided the outcome, for rule-based sites
+    pub rule: Option<String>,
+    /// Confidence (0-100) of the deciding rule, for rule-based sites
+    pub confidence: Option<u8>,
+}
+
+impl From<Option<RuleMatch>> for Detection {
+    fn from(rule_match: Option<RuleMatch>) -> Self {
+        match rule_match {
+            Some(m) => Self {
+                exists: Some(m.verdict == Verdict::Found),
+                rule: Some(m.rule),
+                confidence: Some(m.confidence),
+            },
+            None => Self::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers() -> Vec<(String, String)> {
+        vec![("Content-Type".to_string(), "application/json".to_string())]
+    }
+
+    #[test]
+    fn test_condition_status() {
+        let view = ResponseView::new(404, None);
+        assert!(Condition::status(404).matches(&view));
+        assert!(Condition::Status(vec![400, 404]).matches(&view));
+        assert!(!Condition::success().matches(&view));
+        assert!(Condition::success().matches(&ResponseView::new(204, None)));
+    }
+
+    #[test]
+    fn test_condition_body() {
+        let view = ResponseView::new(200, Some("<title>Profile</title>"));
+        assert!(Condition::body_contains("Profile").matches(&view));
+        assert!(!Condition::body_contains("profile").matches(&view));
+        assert!(Condition::body_matches("(?i)PROFILE").matches(&view));
+        assert!(Condition::BodyNotContains("Error".to_string()).matches(&view));
+        assert!(Condition::MinLength(10).matches(&view));
+        assert!(!Condition::MaxLength(10).matches(&view));
+    }
+
+    #[test]
+    fn test_condition_body_needs_body() {
+        let view = ResponseView::new(200, None);
+        assert!(!Condition::body_contains("x").matches(&view));
+        assert!(!Condition::BodyNotContains("x".to_string()).matches(&view));
+        assert!(!Condition::MinLength(0).matches(&view));
+        assert!(!Condition::MaxLength(100).matches(&view));
+    }
+
+    #[test]
+    fn test_condition_header_and_final_url() {
+        let headers = headers();
+        let view = ResponseView {
+            status_code: 200,
+            body: None,
+            headers: &headers,
+            final_url: Some("https://example.com/login?next=/user"),
+            json: OnceCell::new(),
+        };
+        assert!(Condition::Header {
+            name: "content-type".to_string(),
+            value: None
+        }
+        .matches(&view));
+        assert!(Condition::Header {
+            name: "Content-Type".to_string(),
+            value: Some("APPLICATION/JSON".to_string())
+        }
+        .matches(&view));
+        assert!(!Condition::Header {
+            name: "Location".to_string(),
+            value: None
+        }
+        .matches(&view));
+        assert!(Condition::FinalUrlContains("/login".to_string()).matches(&view));
+        assert!(Condition::FinalUrlMatches(Pattern::new(r"/login\b").unwrap()).matches(&view));
+    }
+
+    #[test]
+    fn test_json_lookup_paths() {
+        let json: Value =
+            serde_json::from_str(r#"{"data": {"children": [{"name": "blue"}]}}"#).unwrap();
+        assert_eq!(
+            json_lookup(&json, "/data/children/0/name"),
+            Some(&Value::from("blue"))
+        );
+        assert_eq!(
+            json_lookup(&json, "data.children.0.name"),
+            Some(&Value::from("blue"))
+        );
+        assert!(json_lookup(&json, "data.children.1").is_none());
+        assert!(json_lookup(&json, "data.missing").is_none());
+    }
+
+    #[test]
+    fn test_condition_json() {
+        let body =
+            r#"{"kind": "t2", "data": {"name": "blue", "is_suspended": false, "trophies": []}}"#;
+        let view = ResponseView::new(200, Some(body));
+        assert!(Condition::JsonExists("data.name".to_string()).matches(&view));
+        assert!(!Condition::JsonExists("/error".to_string()).matches(&view));
+        assert!(Condition::JsonEquals {
+            path: "/kind".to_string(),
+            value: Value::from("t2")
+        }
+        .matches(&view));
+        assert!(!Condition::JsonEquals {
+            path: "data.is_suspended".to_string(),
+            value: Value::from(true)
+        }
+        .matches(&view));
+        assert!(Condition::JsonNonEmpty("data".to_string()).matches(&view));
+        assert!(!Condition::JsonNonEmpty("data.trophies".to_string()).matches(&view));
+    }
+
+    #[test]
+    fn test_condition_json_needs_json_body() {
+        let view = ResponseView::new(200, Some("<html>Blocked</html>"));
+        assert!(!Condition::JsonExists("".to_string()).matches(&view));
+        assert!(!Condition::JsonExists("/".to_string()).matches(&ResponseView::new(200, None)));
+    }
+
+    #[test]
+    fn test_condition_location() {
+        let headers = vec![(
+            "Location".to_string(),
+            "https://example.com/login".to_string(),
+        )];
+        let mut view = ResponseView::new(302, None);
+        view.headers = &headers;
+        assert!(Condition::redirect().matches(&view));
+        assert!(Condition::LocationContains("/login".to_string()).matches(&view));
+        assert!(
+            Condition::LocationMatches(Pattern::new("^https://example").unwrap()).matches(&view)
+        );
+
+        // Only redirects have a meaningful Location
+        view.status_code = 201;
+        assert!(!Condition::LocationContains("/login".to_string()).matches(&view));
+    }
+
+    #[test]
+    fn test_condition_combinators() {
+        let view = ResponseView::new(200, Some("abc"));
+        let any = Condition::Any(vec![Condition::status(404), Condition::body_contains("b")]);
+        let all = Condition::All(vec![Condition::status(404), Condition::body_contains("b")]);
+        assert!(any.matches(&view));
+        assert!(!all.matches(&view));
+        assert!(Condition::Not(Box::new(all)).matches(&view));
+    }
+
+    #[test]
+    fn test_rule_set_first_match_wins() {
+        let rules = RuleSet::new(vec![
+            Rule::new("missing", Verdict::NotFound, vec![Condition::status(404)]),
+            Rule::new(
+                "error-page",
+                Verdict::NotFound,
+                vec![Condition::success(), Condition::body_contains("Error")],
+            ),
+            Rule::new("ok", Verdict::Found, vec![Condition::success()]),
+        ]);
+
+        let m = rules
+            .evaluate(&ResponseView::new(200, Some("Error")))
+            .unwrap();
+        assert_eq!(m.verdict, Verdict::NotFound);
+        assert_eq!(m.rule, "error-page");
+
+        let m = rules.evaluate(&ResponseView::new(200, Some("Hi"))).unwrap();
+        assert_eq!(m.verdict, Verdict::Found);
+        assert_eq!(m.rule, "ok");
+
+        assert!(rules.evaluate(&ResponseView::new(500, None)).is_none());
+    }
+
+    #[test]
+    fn test_rule_set_unnamed_rule() {
+        let rules = RuleSet::new(vec![Rule::new("", Verdict::Found, vec![])]);
+        let m = rules.evaluate(&ResponseView::new(200, None)).unwrap();
+        assert_eq!(m.rule, "rule #1");
+    }
+
+    #[test]
+    fn test_rule_set_serde_roundtrip() {
+        let json = r#"[
+            { "name": "missing", "verdict": "not_found", "when": [ { "status": [404, 410] } ] },
+            { "verdict": "not_found", "when": [
+                { "status_range": [200, 299] },
+                { "any": [ { "body_contains": "No such user" }, { "body_matches": "(?i)suspended" } ] }
+            ] },
+            { "verdict": "found", "when": [ { "header": { "name": "X-User" } } ] },
+            { "verdict": "found", "when": [ { "json_equals": { "path": "/kind", "value": "t2" } } ] }
+        ]"#;
+        let rules: RuleSet = serde_json::from_str(json).unwrap();
+        assert_eq!(rules.rules().len(), 4);
+
+        let m = rules
+            .evaluate(&ResponseView::new(200, Some("Account SUSPENDED")))
+            .unwrap();
+        assert_eq!(m.rule, "rule #2");
+
+        let roundtrip: RuleSet =
+            serde_json::from_str(&serde_json::to_string(&rules).unwrap()).unwrap();
+        assert_eq!(roundtrip, rules);
+    }
+
+    #[test]
+    fn test_rule_set_rejects_invalid_regex() {
+        let json = r#"[ { "verdict": "found", "when": [ { "body_matches": "([" } ] } ]"#;
+        assert!(serde_json::from_str::<RuleSet>(json).is_err());
+    }
+
+    #[test]
+    fn test_rule_confidence() {
+        let status = Rule::new("missing", Verdict::NotFound, vec![Condition::status(404)]);
+        assert_eq!(status.confidence(), STATUS_CONFIDENCE);
+
+        let content = Rule::new(
+            "profile",
+            Verdict::Found,
+            vec![
+                Condition::success(),
+                Condition::Not(Box::new(Condition::body_contains("not found"))),
+            ],
+        );
+        assert_eq!(content.confidence(), CONTENT_CONFIDENCE);
+
+        let length = Rule::new(
+            "large-page",
+            Verdict::Found,
+            vec![Condition::success(), Condition::MinLength(20_001)],
+        );
+        assert_eq!(length.confidence(), STATUS_CONFIDENCE);
+
+        let fallback = Rule::new("fallback", Verdict::Found, vec![]);
+        assert_eq!(fallback.confidence(), FALLBACK_CONFIDENCE);
+        assert_eq!(fallback.with_confidence(10).confidence(), 10);
+
+        let rules = RuleSet::new(vec![status]);
+        let matched = rules.evaluate(&ResponseView::new(404, None)).unwrap();
+        assert_eq!(matched.confidence, STATUS_CONFIDENCE);
+
+        let json = r#"[ { "verdict": "found", "confidence": 55 } ]"#;
+        let rules: RuleSet = serde_json::from_str(json).unwrap();
+        assert_eq!(rules.rules()[0].confidence(), 55);
+    }
+
+    #[test]
+    fn test_detection_from_rule_match() {
+        let detection = Detection::from(Some(RuleMatch {
+            verdict: Verdict::Found,
+            rule: "ok".to_string(),
+            confidence: STATUS_CONFIDENCE,
+        }));
+        assert_eq!(detection.exists, Some(true));
+        assert_eq!(detection.rule.as_deref(), Some("ok"));
+        assert_eq!(detection.confidence, Some(STATUS_CONFIDENCE));
+        assert_eq!(Detection::from(None), Detection::default());
+    }
+}
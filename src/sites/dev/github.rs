@@ -1,7 +1,11 @@
 //! GitHub site checker
 
 use crate::data::site_info::SiteType;
+use crate::request::RequestResponse;
+use crate::sites::profile::{html_capture, ProfileInfo};
 use crate::sites::Site;
+use regex::Regex;
+use std::sync::OnceLock;
 
 /// GitHub site checker
 pub struct GitHubChecker;
@@ -28,13 +32,94 @@ impl Site for GitHubChecker {
         SiteType::Dev
     }
 
+    fn claimed_username(&self) -> Option<&str> {
+        Some("torvalds")
+    }
+
+    fn validate_username(&self, username: &str) -> bool {
+        // At most 39 characters, which the pattern alone cannot express
+        username.len() <= 39
+            && self
+                .username_pattern()
+                .is_some_and(|pattern| pattern.is_match(username))
+    }
+
+    fn username_pattern(&self) -> Option<&Regex> {
+        // Alphanumerics and single inner hyphens
+        static PATTERN: OnceLock<Regex> = OnceLock::new();
+        Some(
+            PATTERN.get_or_init(|| {
+                Regex::new(r"^[A-Za-z0-9](?:-?[A-Za-z0-9])*$").expect("valid pattern")
+            }),
+        )
+    }
+
     fn url_pattern(&self) -> &str {
         "https://github.com/{}"
     }
 
-    // Uses default build_url() implementation
+    fn http_method(&self) -> &'static str {
+        // The profile page itself: the REST API allows only 60 anonymous requests an hour
+        "GET"
+    }
+
     // Uses default parse_response() implementation (200 = exists, 404 = not found)
-    // Uses default http_method() (HEAD)
+
+    fn extract_profile(&self, _username: &str, response: &RequestResponse) -> Option<ProfileInfo> {
+        static PATTERNS: OnceLock<[Regex; 8]> = OnceLock::new();
+        let [name, username, bio, avatar, location, website, followers, following] = PATTERNS
+            .get_or_init(|| {
+                [
+                    r#"itemprop="name"[^>]*>([^<]*)<"#,
+                    r#"itemprop="additionalName"[^>]*>([^<]*)<"#,
+                    r#"data-bio-text="([^"]*)""#,
+                    r#"<img[^>]*src="([^"]+)"[^>]*class="avatar avatar-user"#,
+                    r#"(?s)itemprop="homeLocation"[^>]*>.*?<span class="p-label">([^<]*)<"#,
+                    r#"(?s)itemprop="url"[^>]*>.*?href="([^"]+)""#,
+                    r#"(?s)\?tab=followers"[^>]*>.*?<span[^>]*>([0-9.,]+[km]?)</span>"#,
+                    r#"(?s)\?tab=following"[^>]*>.*?<span[^>]*>([0-9.,]+[km]?)</span>"#,
+                ]
+                .map(|pattern| Regex::new(pattern).expect("valid pattern"))
+            });
+        static SOCIAL: OnceLock<Regex> = OnceLock::new();
+        let social = SOCIAL.get_or_init(|| {
+            Regex::new(r#"<a[^>]*rel="nofollow me"[^>]*href="([^"]+)""#).expect("valid pattern")
+        });
+        let body = response.body.as_deref()?;
+
+        let mut links: Vec<String> = html_capture(website, body).into_iter().collect();
+        for link in social.captures_iter(body).filter_map(|c| c.get(1)) {
+            let link = link.as_str().to_string();
+            if !links.contains(&link) {
+                links.push(link);
+            }
+        }
+
+        Some(ProfileInfo {
+            name: html_capture(name, body),
+            username: html_capture(username, body),
+            bio: html_capture(bio, body),
+            avatar_url: html_capture(avatar, body),
+            location: html_capture(location, body),
+            followers: html_capture(followers, body).and_then(|count| parse_count(&count)),
+            following: html_capture(following, body).and_then(|count| parse_count(&count)),
+            links,
+            ..ProfileInfo::default()
+        })
+    }
+}
+
+/// Parse a count as GitHub shows it, such as `12`, `1,024` or `1.2k`
+fn parse_count(count: &str) -> Option<u64> {
+    let (number, factor) = match count.strip_suffix('k') {
+        Some(number) => (number, 1_000.0),
+        None => match count.strip_suffix('m') {
+            Some(number) => (number, 1_000_000.0),
+            None => (count, 1.0),
+        },
+    };
+    let number: f64 = number.replace(',', "").parse().ok()?;
+    Some((number * factor).round() as u64)
 }
 
 #[cfg(test)]
@@ -71,4 +156,78 @@ mod tests {
         assert_eq!(checker.parse_response("testuser", 200, None), Some(true));
         assert_eq!(checker.parse_response("testuser", 404, None), Some(false));
     }
+
+    #[test]
+    fn test_github_checker_probes_profile_page() {
+        let checker = GitHubChecker::new();
+        assert_eq!(
+            checker.build_probe_url("torvalds"),
+            "https://github.com/torvalds"
+        );
+        assert_eq!(checker.http_method(), "GET");
+        assert!(!checker.expects_json());
+    }
+
+    #[test]
+    fn test_github_checker_extract_profile() {
+        let checker = GitHubChecker::new();
+        let body = r#"<html><head>
+            <meta property="og:image" content="https://avatars.githubusercontent.com/u/1024025?v=4?s=400">
+            </head><body>
+            <img style="height:auto;" alt="View torvalds's full-sized avatar" src="https://avatars.githubusercontent.com/u/1024025?v=4" width="260" height="260" class="avatar avatar-user width-full border color-bg-default" />
+            <h1 class="vcard-names">
+              <span class="p-name vcard-fullname d-block overflow-hidden" itemprop="name">
+                Linus Torvalds
+              </span>
+              <span class="p-nickname vcard-username d-block" itemprop="additionalName">torvalds</span>
+            </h1>
+            <div class="p-note user-profile-bio mb-3 js-user-profile-bio f4" data-bio-text="" hidden></div>
+            <a class="Link--secondary no-underline no-wrap" href="https://github.com/torvalds?tab=followers">
+              <svg class="octicon octicon-people"></svg>
+              <span class="text-bold color-fg-default">231k</span> followers
+            </a> &middot; <a class="Link--secondary no-underline no-wrap" href="https://github.com/torvalds?tab=following">
+              <span class="text-bold color-fg-default">0</span> following
+            </a>
+            <li class="vcard-detail pt-1 hide-sm hide-md" itemprop="homeLocation" show_title="false" aria-label="Home location: Portland, OR">
+              <svg class="octicon octicon-location"></svg>
+              <span class="p-label">Portland, OR</span>
+            </li>
+            <li itemprop="url" data-test-selector="profile-website-url" class="vcard-detail pt-1">
+              <svg class="octicon octicon-link"></svg>
+              <a rel="nofollow me" class="Link--primary" href="http://kernel.org">kernel.org</a>
+            </li>
+            <li itemprop="social" class="vcard-detail pt-1">
+              <a rel="nofollow me" class="Link--primary" href="https://mastodon.social/@torvalds">@torvalds</a>
+            </li>
+            </body></html>"#;
+        let response = RequestResponse::with_body(200, body.to_string());
+        let profile = checker.extract_profile("torvalds", &response).unwrap();
+        assert_eq!(profile.name.as_deref(), Some("Linus Torvalds"));
+        assert_eq!(profile.username.as_deref(), Some("torvalds"));
+        assert_eq!(
+            profile.avatar_url.as_deref(),
+            Some("https://avatars.githubusercontent.com/u/1024025?v=4")
+        );
+        assert_eq!(profile.location.as_deref(), Some("Portland, OR"));
+        assert_eq!(profile.bio, None);
+        assert_eq!(profile.followers, Some(231_000));
+        assert_eq!(profile.following, Some(0));
+        assert_eq!(
+            profile.links,
+            vec!["http://kernel.org", "https://mastodon.social/@torvalds"]
+        );
+
+        assert!(checker
+            .extract_profile("torvalds", &RequestResponse::new(200))
+            .is_none());
+    }
+
+    #[test]
+    fn test_github_checker_validate_username() {
+        let checker = GitHubChecker::new();
+        assert!(checker.validate_username("torvalds"));
+        assert!(!checker.validate_username("-leading"));
+        assert!(!checker.validate_username("double--hyphen"));
+        assert!(!checker.validate_username(&"a".repeat(40)));
+    }
 }
@@ -1,12 +1,46 @@
 use crate::data::site_info::SiteType;
+use crate::request::RequestResponse;
+use crate::sites::detection::{Condition, Rule, RuleSet, Verdict};
+use crate::sites::profile::{
+    decode_entities, json_count, json_string, json_timestamp, ProfileInfo,
+};
 use crate::sites::Site;
+use regex::Regex;
+use std::sync::OnceLock;
 
 /// Reddit username checker
-pub struct RedditChecker;
+pub struct RedditChecker {
+    rules: RuleSet,
+}
 
 impl RedditChecker {
     pub fn new() -> Self {
-        Self
+        Self {
+            rules: RuleSet::new(vec![
+                // Missing, banned and shadowbanned accounts are all 404
+                Rule::new("missing", Verdict::NotFound, vec![Condition::status(404)]),
+                // Suspended accounts still hold the name, so they count as existing
+                Rule::new(
+                    "account",
+                    Verdict::Found,
+                    vec![
+                        Condition::success(),
+                        Condition::JsonEquals {
+                            path: "/kind".to_string(),
+                            value: "t2".into(),
+                        },
+                    ],
+                ),
+                // Other API errors are read as not found to avoid false positives,
+                // but they prove little
+                Rule::new(
+                    "api-error",
+                    Verdict::NotFound,
+                    vec![Condition::JsonExists("/error".to_string())],
+                )
+                .with_confidence(50),
+            ]),
+        }
     }
 }
 
@@ -25,79 +59,60 @@ impl Site for RedditChecker {
         "https://www.reddit.com/user/{}"
     }
 
+    fn probe_url_pattern(&self) -> &str {
+        // The JSON profile endpoint needs no JavaScript rendering
+        "https://www.reddit.com/user/{}/about.json"
+    }
+
     fn site_type(&self) -> SiteType {
         SiteType::Forum
     }
 
-    fn requires_browser(&self) -> bool {
-        true // Reddit renders content with JavaScript
-    }
-
-    fn parse_response(
-        &self,
-        _username: &str,
-        status_code: u16,
-        body: Option<&str>,
-    ) -> Option<bool> {
-        match status_code {
-            404 => Some(false),
-            200..=299 => {
-                if let Some(body_text) = body {
-                    let body_lc = body_text.to_ascii_lowercase();
-
-                    // Check for explicit error messages first (negative indicators)
-                    if body_lc.contains("sorry, there isn") || body_lc.contains("page not found") {
-                        return Some(false);
-                    }
-
-                    // Check for positive indicators: Reddit profile pages typically contain:
-                    // - Profile-specific elements like "shreddit-user-profile" or similar
-                    // - User activity indicators
-                    // - Profile metadata
-                    // Blocked/error pages typically don't have these
-
-                    // Check for profile-specific content that indicates a valid profile
-                    // Reddit uses specific data attributes and classes for user profiles
-                    let has_profile_indicators = body_lc.contains("shreddit-user-profile")
-                        || body_lc.contains("user-profile")
-                        || body_lc.contains("profile-overview")
-                        || body_lc.contains("user-activity")
-                        || (body_lc.contains("karma") && body_lc.contains("cake-day"))
-                        || body_lc.contains("reddit-profile");
-
-                    // Also check if the page has substantial content beyond just CSS/JS
-                    // Blocked pages are often mostly CSS/JS with minimal actual content
-                    let has_substantial_content = body_text.len() > 50000; // Valid profiles are usually > 50KB
-
-                    if has_profile_indicators && has_substantial_content {
-                        return Some(true);
-                    }
-
-                    // If body is very short, likely an error page
-                    if body_text.len() < 10000 {
-                        return Some(false);
-                    }
-
-                    // Default to false to avoid false positives
-                    // If we can't find clear positive indicators, assume it's not a valid profile
-                    Some(false)
-                } else {
-                    // No body - can't determine
-                    None
-                }
-            }
-            _ => None,
-        }
+    fn claimed_username(&self) -> Option<&str> {
+        Some("spez")
+    }
+
+    fn username_pattern(&self) -> Option<&Regex> {
+        // 3-20 letters, digits, underscores or hyphens
+        static PATTERN: OnceLock<Regex> = OnceLock::new();
+        Some(PATTERN.get_or_init(|| Regex::new(r"^[A-Za-z0-9_-]{3,20}$").expect("valid pattern")))
+    }
+
+    fn detection_rules(&self) -> Option<&RuleSet> {
+        Some(&self.rules)
+    }
+
+    fn expects_json(&self) -> bool {
+        true
     }
 
     fn http_method(&self) -> &'static str {
         "GET"
     }
+
+    fn extract_profile(&self, _username: &str, response: &RequestResponse) -> Option<ProfileInfo> {
+        let json: serde_json::Value = serde_json::from_str(response.body.as_deref()?).ok()?;
+        // Image URLs come HTML-escaped (`&amp;`)
+        let avatar_url = json_string(&json, "/data/snoovatar_img")
+            .or_else(|| json_string(&json, "/data/icon_img"))
+            .map(|url| decode_entities(&url));
+
+        Some(ProfileInfo {
+            name: json_string(&json, "/data/subreddit/title"),
+            username: json_string(&json, "/data/name"),
+            bio: json_string(&json, "/data/subreddit/public_description"),
+            avatar_url,
+            followers: json_count(&json, "/data/subreddit/subscribers"),
+            created_at: json_timestamp(&json, "/data/created_utc"),
+            ..ProfileInfo::default()
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::request::RequestResponse;
 
     #[test]
     fn test_reddit_checker_new() {
@@ -118,6 +133,10 @@ mod tests {
             checker.build_url("spez"),
             "https://www.reddit.com/user/spez"
         );
+        assert_eq!(
+            checker.build_probe_url("spez"),
+            "https://www.reddit.com/user/spez/about.json"
+        );
     }
 
     #[test]
@@ -130,56 +149,97 @@ mod tests {
     fn test_reddit_checker_http_method() {
         let checker = RedditChecker::new();
         assert_eq!(checker.http_method(), "GET");
+        assert!(checker.expects_json());
+        assert!(!checker.requires_browser());
     }
 
     #[test]
-    fn test_reddit_checker_false_positive_sorry_message() {
+    fn test_reddit_checker_existing_account() {
         let checker = RedditChecker::new();
-        let body = r#"<html><body>Sorry, there isn't anything here</body></html>"#;
-        assert_eq!(
-            checker.parse_response("testuser", 200, Some(body)),
-            Some(false)
+        let body = r#"{"kind": "t2", "data": {"name": "spez", "total_karma": 1000}}"#;
+        let detection = checker.detect("spez", &RequestResponse::with_body(200, body.to_string()));
+        assert_eq!(detection.exists, Some(true));
+        assert_eq!(detection.rule.as_deref(), Some("account"));
+    }
+
+    #[test]
+    fn test_reddit_checker_missing_account() {
+        let checker = RedditChecker::new();
+        let body = r#"{"message": "Not Found", "error": 404}"#;
+        let detection = checker.detect(
+            "testuser",
+            &RequestResponse::with_body(404, body.to_string()),
         );
+        assert_eq!(detection.exists, Some(false));
+        assert_eq!(detection.rule.as_deref(), Some("missing"));
     }
 
     #[test]
-    fn test_reddit_checker_false_positive_page_not_found() {
+    fn test_reddit_checker_extract_profile() {
         let checker = RedditChecker::new();
-        let body = r#"<html><body>page not found</body></html>"#;
+        let body = r#"{"kind": "t2", "data": {
+            "name": "spez",
+            "created_utc": 1118030400.0,
+            "icon_img": "https://styles.redditmedia.com/avatar.png?width=256&amp;s=abc",
+            "subreddit": {
+                "title": "spez",
+                "public_description": "Reddit CEO",
+                "subscribers": 1200
+            }
+        }}"#;
+        let response = RequestResponse::with_body(200, body.to_string());
+        let profile = checker.extract_profile("spez", &response).unwrap();
+        assert_eq!(profile.name.as_deref(), Some("spez"));
+        assert_eq!(profile.username.as_deref(), Some("spez"));
+        assert_eq!(profile.bio.as_deref(), Some("Reddit CEO"));
         assert_eq!(
-            checker.parse_response("testuser", 200, Some(body)),
-            Some(false)
+            profile.avatar_url.as_deref(),
+            Some("https://styles.redditmedia.com/avatar.png?width=256&s=abc")
+        );
+        assert_eq!(profile.followers, Some(1200));
+        assert_eq!(
+            profile.created_at.as_deref(),
+            Some("2005-06-06T04:00:00+00:00")
+        );
+    }
+
+    #[test]
+    fn test_reddit_checker_api_error_is_weak() {
+        let checker = RedditChecker::new();
+        let body = r#"{"message": "Forbidden", "error": 403}"#;
+        let detection = checker.detect(
+            "testuser",
+            &RequestResponse::with_body(403, body.to_string()),
         );
+        assert_eq!(detection.exists, Some(false));
+        assert_eq!(detection.rule.as_deref(), Some("api-error"));
+        assert_eq!(detection.confidence, Some(50));
     }
 
     #[test]
-    fn test_reddit_checker_false_positive_blocked() {
+    fn test_reddit_checker_blocked_page_is_uncertain() {
         let checker = RedditChecker::new();
         let body = r#"<html><body>You've been blocked by network security</body></html>"#;
-        assert_eq!(
-            checker.parse_response("testuser", 200, Some(body)),
-            Some(false)
+        let detection = checker.detect(
+            "testuser",
+            &RequestResponse::with_body(200, body.to_string()),
         );
+        assert_eq!(detection.exists, None);
     }
 
     #[test]
     fn test_reddit_checker_parse_response() {
         let checker = RedditChecker::new();
-        // With body=None (HEAD request), we can't determine - returns None
         assert_eq!(checker.parse_response("testuser", 200, None), None);
         assert_eq!(checker.parse_response("testuser", 404, None), Some(false));
         assert_eq!(checker.parse_response("testuser", 500, None), None);
+    }
 
-        // With body containing profile structure, should return true
-        // Need substantial content (> 50KB) and profile indicators
-        let mut body_with_profile = String::from(
-            r#"<html><body class="shreddit-user-profile"><div>karma</div><div>cake-day</div>"#,
-        );
-        body_with_profile.push_str(&"x".repeat(60000)); // Make it > 50KB
-        body_with_profile.push_str("</body></html>");
-        assert_eq!(
-            checker.parse_response("testuser", 200, Some(&body_with_profile)),
-            Some(true)
-        );
+    #[test]
+    fn test_reddit_checker_validate_username() {
+        let checker = RedditChecker::new();
+        assert!(checker.validate_username("spez"));
+        assert!(!checker.validate_username("ab"));
+        assert!(!checker.validate_username("user.name"));
     }
 }
@@ -1,12 +1,58 @@
 use crate::data::site_info::SiteType;
+use crate::request::RequestResponse;
+use crate::sites::detection::{Condition, Rule, RuleSet, Verdict};
+use crate::sites::profile::{html_capture, ProfileInfo};
 use crate::sites::Site;
+use regex::Regex;
+use std::sync::OnceLock;
 
 /// Steam username checker
-pub struct SteamChecker;
+pub struct SteamChecker {
+    rules: RuleSet,
+}
 
 impl SteamChecker {
     pub fn new() -> Self {
-        Self
+        Self {
+            rules: RuleSet::new(vec![
+                Rule::new("missing", Verdict::NotFound, vec![Condition::status(404)]),
+                // Error pages have specific indicators that don't depend on language
+                Rule::new(
+                    "error-page",
+                    Verdict::NotFound,
+                    vec![
+                        Condition::success(),
+                        Condition::body_matches(
+                            r#"(?i)steam community :: error|fatalerror\.css|error_ctn|class="error"#,
+                        ),
+                    ],
+                ),
+                // Valid profiles have specific CSS classes and structure
+                Rule::new(
+                    "profile-page",
+                    Verdict::Found,
+                    vec![
+                        Condition::success(),
+                        Condition::Any(vec![
+                            Condition::body_matches(
+                                "(?i)profile_page|profile_header|playeravatar|profile_badges|profile_summary",
+                            ),
+                            Condition::All(vec![
+                                Condition::body_matches("(?i)persona_name"),
+                                Condition::body_matches("(?i)profile_content"),
+                            ]),
+                        ]),
+                    ],
+                ),
+                // No clear positive indicators: assume not found to avoid false positives
+                Rule::new(
+                    "no-profile-markers",
+                    Verdict::NotFound,
+                    vec![Condition::success(), Condition::MinLength(0)],
+                )
+                .with_confidence(50),
+            ]),
+        }
     }
 }
 
@@ -29,72 +75,52 @@ impl Site for SteamChecker {
         SiteType::Gaming
     }
 
-    fn parse_response(
-        &self,
-        _username: &str,
-        status_code: u16,
-        body: Option<&str>,
-    ) -> Option<bool> {
-        match status_code {
-            404 => Some(false),
-            200..=299 => {
-                if let Some(body_text) = body {
-                    let body_lc = body_text.to_ascii_lowercase();
-
-                    // Check for error page structure (negative indicators)
-                    // Error pages have specific indicators that don't depend on language
-                    let has_error_structure = body_lc.contains("steam community :: error")
-                        || body_lc.contains("fatalerror.css")
-                        || body_lc.contains("error_ctn")
-                        || body_lc.contains("class=\"error");
-
-                    if has_error_structure {
-                        return Some(false);
-                    }
-
-                    // Check for valid profile structure (positive indicators)
-                    // Valid profiles have specific CSS classes and structure
-                    let has_profile_structure =
-                        // Profile page class (most reliable indicator)
-                        body_lc.contains("profile_page")
-                        // Profile-specific elements
-                        || (body_lc.contains("persona_name") && body_lc.contains("profile_content"))
-                        || body_lc.contains("profile_header")
-                        || body_lc.contains("playeravatar")
-                        || body_lc.contains("profile_badges")
-                        || body_lc.contains("profile_summary");
-
-                    // If we detect profile structure, it's a valid profile
-                    if has_profile_structure {
-                        return Some(true);
-                    }
-
-                    // If body is very short, likely an error page
-                    if body_text.len() < 5000 {
-                        return Some(false);
-                    }
-
-                    // Default to false to avoid false positives
-                    // If we can't find clear positive indicators, assume not found
-                    Some(false)
-                } else {
-                    // No body - can't determine
-                    None
-                }
-            }
-            _ => None,
-        }
+    fn claimed_username(&self) -> Option<&str> {
+        Some("gaben")
+    }
+
+    fn username_pattern(&self) -> Option<&Regex> {
+        // Custom profile URLs: 2-32 letters, digits, underscores or hyphens
+        static PATTERN: OnceLock<Regex> = OnceLock::new();
+        Some(PATTERN.get_or_init(|| Regex::new(r"^[A-Za-z0-9_-]{2,32}$").expect("valid pattern")))
+    }
+
+    fn detection_rules(&self) -> Option<&RuleSet> {
+        Some(&self.rules)
     }
 
     fn http_method(&self) -> &'static str {
         // Use GET instead of HEAD to get response body for parsing
         "GET"
     }
+
+    fn extract_profile(&self, _username: &str, response: &RequestResponse) -> Option<ProfileInfo> {
+        static PATTERNS: OnceLock<[Regex; 4]> = OnceLock::new();
+        let [name, avatar, bio, location] = PATTERNS.get_or_init(|| {
+            [
+                r#"class="actual_persona_name">([^<]+)<"#,
+                r#"(?s)class="playerAvatarAutoSizeInner">.*?<img src="([^"]+)""#,
+                r#"(?s)class="profile_summary[^"]*">(.*?)</div>"#,
+                r#"(?s)class="profile_flag"[^>]*>(.*?)</div>"#,
+            ]
+            .map(|pattern| Regex::new(pattern).expect("valid pattern"))
+        });
+        let body = response.body.as_deref()?;
+
+        Some(ProfileInfo {
+            name: html_capture(name, body),
+            avatar_url: html_capture(avatar, body),
+            bio: html_capture(bio, body),
+            location: html_capture(location, body),
+            ..ProfileInfo::default()
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::request::RequestResponse;
 
     #[test]
     fn test_steam_checker_new() {
@@ -168,4 +194,57 @@ mod tests {
             Some(false)
         );
     }
+
+    #[test]
+    fn test_steam_checker_reports_rule() {
+        let checker = SteamChecker::new();
+        let response = RequestResponse::with_body(200, "<html>short</html>".to_string());
+        let detection = checker.detect("testuser", &response);
+        assert_eq!(detection.exists, Some(false));
+        assert_eq!(detection.rule.as_deref(), Some("no-profile-markers"));
+        assert_eq!(detection.confidence, Some(50));
+    }
+
+    #[test]
+    fn test_steam_checker_extract_profile() {
+        let checker = SteamChecker::new();
+        let body = r#"<div class="profile_header">
+            <div class="playerAvatarAutoSizeInner"><img src="https://avatars.steamstatic.com/gaben_full.jpg"></div>
+            <span class="actual_persona_name">Rabscuttle</span>
+            <div class="header_real_name ellipsis">
+                <bdi>Gabe Newell</bdi>
+                <img class="profile_flag" src="https://community.steamstatic.com/flags/us.gif">
+                Bellevue, Washington, United States
+            </div>
+            <div class="profile_summary">
+                Hello &amp; welcome<br>to my profile
+            </div>
+        </div>"#;
+        let response = RequestResponse::with_body(200, body.to_string());
+        let profile = checker.extract_profile("gaben", &response).unwrap();
+        assert_eq!(profile.name.as_deref(), Some("Rabscuttle"));
+        assert_eq!(
+            profile.avatar_url.as_deref(),
+            Some("https://avatars.steamstatic.com/gaben_full.jpg")
+        );
+        assert_eq!(
+            profile.bio.as_deref(),
+            Some("Hello & welcome to my profile")
+        );
+        assert_eq!(
+            profile.location.as_deref(),
+            Some("Bellevue, Washington, United States")
+        );
+
+        let empty = RequestResponse::with_body(200, "<html></html>".to_string());
+        assert!(checker.extract_profile("gaben", &empty).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_steam_checker_validate_username() {
+        let checker = SteamChecker::new();
+        assert!(checker.validate_username("gaben"));
+        assert!(!checker.validate_username("a"));
+        assert!(!checker.validate_username("has space"));
+    }
 }
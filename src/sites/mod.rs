@@ -1,15 +1,22 @@
 //! Site-specific implementations
 
+pub mod declarative;
+pub mod detection;
 pub mod dev;
 pub mod forum;
 pub mod gaming;
 pub mod nsfw;
 pub mod other;
+pub mod page_profile;
 pub mod professional;
+pub mod profile;
 pub mod registry;
 pub mod site;
 pub mod social;
 
+pub use declarative::{DeclarativeSite, SiteDefinition};
+pub use detection::{Condition, Detection, Rule, RuleSet, Verdict};
+pub use profile::ProfileInfo;
 pub use registry::{SiteRegistry, SiteStatistics};
 pub use site::Site;
 
@@ -1,12 +1,60 @@
 use crate::data::site_info::SiteType;
+use crate::sites::detection::{Condition, Rule, RuleSet, Verdict};
 use crate::sites::Site;
+use std::time::Duration;
 
 /// OnlyFans username checker
-pub struct OnlyFansChecker;
+pub struct OnlyFansChecker {
+    rules: RuleSet,
+}
 
 impl OnlyFansChecker {
     pub fn new() -> Self {
-        Self
+        Self {
+            rules: RuleSet::new(vec![
+                Rule::new("missing", Verdict::NotFound, vec![Condition::status(404)]),
+                // Error pages use CSS classes like "b-404", "b-wrapper-404"
+                Rule::new(
+                    "error-page",
+                    Verdict::NotFound,
+                    vec![
+                        Condition::success(),
+                        Condition::body_matches("(?i)b-404|b-wrapper-404"),
+                    ],
+                ),
+                // Profile-specific CSS classes, avatars and IDs
+                Rule::new(
+                    "profile-page",
+                    Verdict::Found,
+                    vec![
+                        Condition::success(),
+                        Condition::body_matches(
+                            "(?i)b-profile|b-username|g-avatar|b-friend__avatar|profileposttab|icon-profile|icon-media|icon-post",
+                        ),
+                    ],
+                ),
+                // The remaining rules only go by page size
+                Rule::new(
+                    "short-page",
+                    Verdict::NotFound,
+                    vec![Condition::success(), Condition::MaxLength(2_999)],
+                )
+                .with_confidence(50),
+                // Large pages without error structure are usually valid profiles
+                Rule::new(
+                    "large-page",
+                    Verdict::Found,
+                    vec![Condition::success(), Condition::MinLength(20_001)],
+                )
+                .with_confidence(40),
+                Rule::new(
+                    "no-profile-markers",
+                    Verdict::NotFound,
+                    vec![Condition::success(), Condition::MinLength(0)],
+                )
+                .with_confidence(40),
+            ]),
+        }
     }
 }
 
@@ -29,79 +77,21 @@ impl Site for OnlyFansChecker {
         SiteType::Nsfw
     }
 
+    fn claimed_username(&self) -> Option<&str> {
+        Some("onlyfans")
+    }
+
     fn requires_browser(&self) -> bool {
         true // OnlyFans renders content with JavaScript
     }
 
-    fn parse_response(
-        &self,
-        _username: &str,
-        status_code: u16,
-        body: Option<&str>,
-    ) -> Option<bool> {
-        match status_code {
-            404 => Some(false),
-            200..=299 => {
-                if let Some(body_text) = body {
-                    let body_lc = body_text.to_ascii_lowercase();
-
-                    // Check for error page structure (negative indicators)
-                    // Error pages have specific CSS classes like "b-404", "b-wrapper-404"
-                    let has_error_structure = body_lc.contains("b-404")
-                        || body_lc.contains("b-wrapper-404")
-                        || body_lc.contains("b-404__title")
-                        || body_lc.contains("b-404__description");
-
-                    if has_error_structure {
-                        return Some(false);
-                    }
-
-                    // Check for valid profile structure (positive indicators)
-                    // Valid profiles have specific CSS classes and IDs that indicate a real profile page
-                    let has_profile_structure =
-                        // Profile-specific CSS classes
-                        body_lc.contains("b-profile")
-                        || body_lc.contains("b-profile__header")
-                        || body_lc.contains("b-profile__user")
-                        || body_lc.contains("b-profile__content")
-                        || body_lc.contains("b-profile-info-card")
-                        || body_lc.contains("b-username")
-                        || body_lc.contains("b-username-row")
-                        // Avatar/image classes
-                        || body_lc.contains("g-avatar")
-                        || body_lc.contains("b-friend__avatar")
-                        // Profile-specific IDs
-                        || body_lc.contains("profileposttab")
-                        || body_lc.contains("icon-profile")
-                        || body_lc.contains("icon-media")
-                        || body_lc.contains("icon-post");
-
-                    // If we detect profile structure, it's a valid profile
-                    if has_profile_structure {
-                        return Some(true);
-                    }
-
-                    // If body is very short, likely an error page
-                    if body_text.len() < 3000 {
-                        return Some(false);
-                    }
-
-                    // If content is substantial (> 20KB) and no error structure, assume valid
-                    // (large pages are usually valid profiles with lots of content)
-                    if body_text.len() > 20000 {
-                        return Some(true);
-                    }
-
-                    // Default to false to avoid false positives
-                    // If we can't find clear positive indicators, assume not found
-                    Some(false)
-                } else {
-                    // No body - can't determine
-                    None
-                }
-            }
-            _ => None,
-        }
+    fn timeout(&self) -> Option<Duration> {
+        // Rendering the profile page is slow
+        Some(Duration::from_secs(20))
+    }
+
+    fn detection_rules(&self) -> Option<&RuleSet> {
+        Some(&self.rules)
     }
 
     fn http_method(&self) -> &'static str {
@@ -126,6 +116,12 @@ mod tests {
         assert_eq!(checker.http_method(), "GET");
     }
 
+    #[test]
+    fn test_onlyfans_checker_timeout() {
+        let checker = OnlyFansChecker::new();
+        assert_eq!(checker.timeout(), Some(Duration::from_secs(20)));
+    }
+
     #[test]
     fn test_onlyfans_checker_url_pattern() {
         let checker = OnlyFansChecker::new();
@@ -183,4 +179,17 @@ mod tests {
             Some(true)
         );
     }
+
+    #[test]
+    fn test_onlyfans_checker_page_size_is_low_confidence() {
+        let checker = OnlyFansChecker::new();
+        let large = crate::request::RequestResponse::with_body(200, "x".repeat(20_001));
+        let detection = checker.detect("testuser", &large);
+        assert_eq!(detection.exists, Some(true));
+        assert_eq!(detection.rule.as_deref(), Some("large-page"));
+        assert_eq!(detection.confidence, Some(40));
+
+        let short = crate::request::RequestResponse::with_body(200, "x".repeat(100));
+        assert_eq!(checker.detect("testuser", &short).confidence, Some(50));
+    }
 }
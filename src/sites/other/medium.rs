@@ -1,12 +1,32 @@
 use crate::data::site_info::SiteType;
+use crate::sites::detection::{Condition, Rule, RuleSet, Verdict};
 use crate::sites::Site;
 
 /// Medium username checker
-pub struct MediumChecker;
+pub struct MediumChecker {
+    rules: RuleSet,
+}
 
 impl MediumChecker {
     pub fn new() -> Self {
-        Self
+        Self {
+            rules: RuleSet::new(vec![
+                Rule::new("missing", Verdict::NotFound, vec![Condition::status(404)]),
+                // Medium returns 200 even for non-existent profiles
+                Rule::new(
+                    "error-page",
+                    Verdict::NotFound,
+                    vec![
+                        Condition::success(),
+                        Condition::Any(vec![
+                            Condition::body_contains("PAGE NOT FOUND"),
+                            Condition::body_contains("Out of nothing, something."),
+                        ]),
+                    ],
+                ),
+                Rule::new("ok", Verdict::Found, vec![Condition::success()]),
+            ]),
+        }
     }
 }
 
@@ -29,31 +49,12 @@ impl Site for MediumChecker {
         SiteType::Other
     }
 
-    fn parse_response(
-        &self,
-        _username: &str,
-        status_code: u16,
-        body: Option<&str>,
-    ) -> Option<bool> {
-        match status_code {
-            404 => Some(false),
-            200..=299 => {
-                // Medium returns 200 even for non-existent profiles
-                // Check response body for error indicators
-                if let Some(body_text) = body {
-                    // Check for "PAGE NOT FOUND" text
-                    if body_text.contains("PAGE NOT FOUND") {
-                        return Some(false);
-                    }
-                    // Check for the specific error message
-                    if body_text.contains("Out of nothing, something.") {
-                        return Some(false);
-                    }
-                }
-                Some(true)
-            }
-            _ => None,
-        }
+    fn claimed_username(&self) -> Option<&str> {
+        Some("ev")
+    }
+
+    fn detection_rules(&self) -> Option<&RuleSet> {
+        Some(&self.rules)
     }
 
     fn http_method(&self) -> &'static str {
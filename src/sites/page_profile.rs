@@ -0,0 +1,296 @@
+//! Generic profile parser for OpenGraph, meta tags and JSON-LD
+//!
+//! Most profile pages describe their owner for link previews and search engines:
+//! `og:*` / `twitter:*` / `profile:*` meta tags, `<link rel="me">` links and a
+//! schema.org `Person` in JSON-LD. [`page_profile`] reads all of them from any HTML
+//! body, so sites get profile details without an extractor of their own.
+
+use crate::sites::profile::{decode_entities, html_text, ProfileInfo};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Read a best-effort profile from the meta tags and JSON-LD of an HTML page
+///
+/// JSON-LD wins over meta tags when both describe the same field.
+pub fn page_profile(body: &str) -> ProfileInfo {
+    let person = json_ld_person(body).unwrap_or_default();
+    let meta = meta_tags(body);
+    let meta_value = |keys: &[&str]| keys.iter().find_map(|key| meta.get(*key).cloned());
+
+    let mut links = person.links;
+    links.extend(rel_me_links(body));
+    dedup(&mut links);
+
+    ProfileInfo {
+        name: person
+            .name
+            .or_else(|| meta_value(&["og:title", "twitter:title"])),
+        username: person
+            .username
+            .or_else(|| meta_value(&["profile:username"])),
+        bio: person
+            .bio
+            .or_else(|| meta_value(&["og:description", "twitter:description", "description"])),
+        avatar_url: person
+            .avatar_url
+            .or_else(|| meta_value(&["og:image", "twitter:image"])),
+        location: person.location,
+        followers: person.followers,
+        links,
+        ..ProfileInfo::default()
+    }
+}
+
+/// Meta tag contents keyed by their lowercase `property` or `name`
+fn meta_tags(body: &str) -> HashMap<String, String> {
+    static META: OnceLock<Regex> = OnceLock::new();
+    let meta = META.get_or_init(|| Regex::new(r"(?i)<meta\s[^>]*>").expect("valid pattern"));
+
+    let mut tags = HashMap::new();
+    for tag in meta.find_iter(body) {
+        let attributes = attributes(tag.as_str());
+        let key = attributes
+            .get("property")
+            .or_else(|| attributes.get("name"))
+            .map(|key| key.to_lowercase());
+        let content = attributes
+            .get("content")
+            .map(|content| content.trim())
+            .filter(|content| !content.is_empty());
+        if let (Some(key), Some(content)) = (key, content) {
+            // The first tag for a key wins, as with most consumers
+            tags.entry(key).or_insert_with(|| content.to_string());
+        }
+    }
+    tags
+}
+
+/// `href`s of `<link rel="me">` tags
+fn rel_me_links(body: &str) -> Vec<String> {
+    static LINK: OnceLock<Regex> = OnceLock::new();
+    let link = LINK.get_or_init(|| Regex::new(r"(?i)<link\s[^>]*>").expect("valid pattern"));
+
+    link.find_iter(body)
+        .map(|tag| attributes(tag.as_str()))
+        .filter(|attributes| {
+            attributes
+                .get("rel")
+                .is_some_and(|rel| rel.split_whitespace().any(|r| r.eq_ignore_ascii_case("me")))
+        })
+        .filter_map(|mut attributes| attributes.remove("href"))
+        .filter(|href| href.starts_with("http"))
+        .collect()
+}
+
+/// Attributes of an HTML tag, with lowercase names and decoded values
+fn attributes(tag: &str) -> HashMap<String, String> {
+    static ATTRIBUTE: OnceLock<Regex> = OnceLock::new();
+    let attribute = ATTRIBUTE.get_or_init(|| {
+        Regex::new(r#"([a-zA-Z_:-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).expect("valid pattern")
+    });
+
+    attribute
+        .captures_iter(tag)
+        .map(|captures| {
+            let value = captures
+                .get(2)
+                .or_else(|| captures.get(3))
+                .map_or("", |m| m.as_str());
+            (captures[1].to_lowercase(), decode_entities(value))
+        })
+        .collect()
+}
+
+/// Profile of the first schema.org `Person` in the page's JSON-LD
+fn json_ld_person(body: &str) -> Option<ProfileInfo> {
+    static SCRIPT: OnceLock<Regex> = OnceLock::new();
+    let script = SCRIPT.get_or_init(|| {
+        Regex::new(
+            r#"(?is)<script[^>]*type\s*=\s*["']application/ld\+json["'][^>]*>(.*?)</script>"#,
+        )
+        .expect("valid pattern")
+    });
+
+    script
+        .captures_iter(body)
+        .filter_map(|captures| serde_json::from_str::<Value>(captures[1].trim()).ok())
+        .find_map(|json| find_person(&json).map(person_profile))
+}
+
+/// Find a `Person` node: the document itself, an element of an array or `@graph`,
+/// or the `mainEntity` of a `ProfilePage`
+fn find_person(json: &Value) -> Option<&Value> {
+    match json {
+        Value::Array(items) => items.iter().find_map(find_person),
+        Value::Object(map) => {
+            if has_type(json, "Person") {
+                return Some(json);
+            }
+            ["@graph", "mainEntity", "author"]
+                .iter()
+                .filter_map(|key| map.get(*key))
+                .find_map(find_person)
+        }
+        _ => None,
+    }
+}
+
+fn has_type(json: &Value, wanted: &str) -> bool {
+    match json.get("@type") {
+        Some(Value::String(kind)) => kind == wanted,
+        Some(Value::Array(kinds)) => kinds.iter().any(|kind| kind.as_str() == Some(wanted)),
+        _ => false,
+    }
+}
+
+fn person_profile(person: &Value) -> ProfileInfo {
+    let text = |key: &str| {
+        person
+            .get(key)
+            .and_then(Value::as_str)
+            .map(html_text)
+            .filter(|text| !text.is_empty())
+    };
+    // Images and places are either plain strings or objects
+    let image = match person.get("image") {
+        Some(Value::String(url)) => Some(url.clone()),
+        Some(image) => image
+            .get("url")
+            .or_else(|| image.get("contentUrl"))
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        None => None,
+    };
+    let location = match person.get("homeLocation").or_else(|| person.get("address")) {
+        Some(Value::String(place)) => Some(place.clone()),
+        Some(place) => place
+            .get("name")
+            .or_else(|| place.get("addressLocality"))
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        None => None,
+    };
+    let links = match person.get("sameAs") {
+        Some(Value::String(url)) => vec![url.clone()],
+        Some(Value::Array(urls)) => urls
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        _ => vec![],
+    };
+
+    ProfileInfo {
+        name: text("name"),
+        username: text("alternateName"),
+        bio: text("description"),
+        avatar_url: image,
+        location,
+        followers: follower_count(person),
+        links,
+        ..ProfileInfo::default()
+    }
+}
+
+/// `userInteractionCount` of a `FollowAction` interaction statistic
+fn follower_count(person: &Value) -> Option<u64> {
+    let statistics = match person.get("interactionStatistic")? {
+        Value::Array(items) => items.iter().collect::<Vec<_>>(),
+        single => vec![single],
+    };
+    statistics
+        .into_iter()
+        .filter(|stat| {
+            stat.get("interactionType")
+                .and_then(|kind| kind.as_str().or_else(|| kind.get("@type")?.as_str()))
+                .is_some_and(|kind| kind.ends_with("FollowAction"))
+        })
+        .find_map(|stat| stat.get("userInteractionCount")?.as_u64())
+}
+
+fn dedup(links: &mut Vec<String>) {
+    let mut seen = std::collections::HashSet::new();
+    links.retain(|link| seen.insert(link.clone()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_profile_meta_tags() {
+        let body = r#"<html><head>
+            <meta property="og:title" content="Jane Doe &amp; Co">
+            <meta name="description" content="Plain description">
+            <meta property="og:description" content='Writer, "reader"'>
+            <meta content="https://cdn.example/jane.png" property="og:image" />
+            <meta property="profile:username" content="jane">
+            <link rel="me" href="https://mastodon.example/@jane">
+            <link rel="stylesheet" href="https://cdn.example/site.css">
+        </head></html>"#;
+        let profile = page_profile(body);
+        assert_eq!(profile.name.as_deref(), Some("Jane Doe & Co"));
+        assert_eq!(profile.username.as_deref(), Some("jane"));
+        assert_eq!(profile.bio.as_deref(), Some(r#"Writer, "reader""#));
+        assert_eq!(
+            profile.avatar_url.as_deref(),
+            Some("https://cdn.example/jane.png")
+        );
+        assert_eq!(profile.links, vec!["https://mastodon.example/@jane"]);
+    }
+
+    #[test]
+    fn test_page_profile_json_ld() {
+        let body = r#"<meta property="og:title" content="Jane on Example">
+            <script type="application/ld+json">
+            {"@context": "https://schema.org", "@type": "ProfilePage",
+             "mainEntity": {
+                "@type": "Person",
+                "name": "Jane Doe",
+                "alternateName": "jane",
+                "image": {"@type": "ImageObject", "url": "https://cdn.example/jane.png"},
+                "homeLocation": {"@type": "Place", "name": "Lisbon"},
+                "sameAs": ["https://github.com/jane", "https://jane.example"],
+                "interactionStatistic": [{
+                    "@type": "InteractionCounter",
+                    "interactionType": "https://schema.org/FollowAction",
+                    "userInteractionCount": 321
+                }]
+             }}
+            </script>"#;
+        let profile = page_profile(body);
+        assert_eq!(profile.name.as_deref(), Some("Jane Doe"));
+        assert_eq!(profile.username.as_deref(), Some("jane"));
+        assert_eq!(profile.location.as_deref(), Some("Lisbon"));
+        assert_eq!(profile.followers, Some(321));
+        assert_eq!(
+            profile.links,
+            vec!["https://github.com/jane", "https://jane.example"]
+        );
+    }
+
+    #[test]
+    fn test_page_profile_json_ld_graph() {
+        let body = r#"<script type="application/ld+json">{"@graph": [
+            {"@type": "WebSite", "name": "Example"},
+            {"@type": ["Person"], "name": "Jane", "image": "https://cdn.example/j.png",
+             "sameAs": "https://jane.example"}
+        ]}</script>"#;
+        let profile = page_profile(body);
+        assert_eq!(profile.name.as_deref(), Some("Jane"));
+        assert_eq!(
+            profile.avatar_url.as_deref(),
+            Some("https://cdn.example/j.png")
+        );
+        assert_eq!(profile.links, vec!["https://jane.example"]);
+    }
+
+    #[test]
+    fn test_page_profile_nothing_to_read() {
+        assert!(page_profile("").is_empty());
+        assert!(page_profile(r#"{"login": "jane"}"#).is_empty());
+        assert!(page_profile(r#"<script type="application/ld+json">not json</script>"#).is_empty());
+    }
+}
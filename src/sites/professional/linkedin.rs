@@ -28,6 +28,10 @@ impl Site for LinkedInChecker {
     fn site_type(&self) -> SiteType {
         SiteType::Professional
     }
+
+    fn claimed_username(&self) -> Option<&str> {
+        Some("williamhgates")
+    }
 }
 
 #[cfg(test)]
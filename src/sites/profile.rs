@@ -0,0 +1,229 @@
+//! Profile details extracted from the response of a found account
+
+use crate::sites::detection::json_lookup;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::OnceLock;
+
+/// What a profile page says about its owner; every field is best effort
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileInfo {
+    /// Display name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Username or handle shown on the profile
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// Bio or profile description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bio: Option<String>,
+    /// URL of the avatar image
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
+    /// Location, as written by the owner
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    /// Number of followers (or subscribers)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub followers: Option<u64>,
+    /// Number of accounts followed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub following: Option<u64>,
+    /// When the account was created (RFC 3339, UTC)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    /// Other URLs the profile links to (website, other accounts)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<String>,
+}
+
+impl ProfileInfo {
+    /// Check whether nothing was extracted
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// `Some(self)` unless nothing was extracted
+    pub fn non_empty(self) -> Option<Self> {
+        (!self.is_empty()).then_some(self)
+    }
+
+    /// Fill the fields this profile lacks from `other`, and add its links
+    pub fn merge(self, other: ProfileInfo) -> Self {
+        let mut links = self.links;
+        for link in other.links {
+            if !links.contains(&link) {
+                links.push(link);
+            }
+        }
+        Self {
+            name: self.name.or(other.name),
+            username: self.username.or(other.username),
+            bio: self.bio.or(other.bio),
+            avatar_url: self.avatar_url.or(other.avatar_url),
+            location: self.location.or(other.location),
+            followers: self.followers.or(other.followers),
+            following: self.following.or(other.following),
+            created_at: self.created_at.or(other.created_at),
+            links,
+        }
+    }
+}
+
+/// Non-empty string at `path` in a JSON document, trimmed
+pub fn json_string(json: &Value, path: &str) -> Option<String> {
+    json_lookup(json, path)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(str::to_string)
+}
+
+/// Unsigned integer at `path` in a JSON document
+pub fn json_count(json: &Value, path: &str) -> Option<u64> {
+    let value = json_lookup(json, path)?;
+    value
+        .as_u64()
+        .or_else(|| value.as_f64().filter(|n| *n >= 0.0).map(|n| n as u64))
+}
+
+/// Timestamp at `path` in a JSON document: a string as-is, or Unix seconds as RFC 3339
+pub fn json_timestamp(json: &Value, path: &str) -> Option<String> {
+    match json_lookup(json, path)? {
+        Value::Number(secs) => {
+            chrono::DateTime::from_timestamp(secs.as_f64()? as i64, 0).map(|time| time.to_rfc3339())
+        }
+        _ => json_string(json, path),
+    }
+}
+
+/// First capture group of `pattern` in `body`, as plain text
+pub fn html_capture(pattern: &Regex, body: &str) -> Option<String> {
+    pattern
+        .captures(body)
+        .and_then(|captures| captures.get(1))
+        .map(|m| html_text(m.as_str()))
+        .filter(|text| !text.is_empty())
+}
+
+/// Plain text of an HTML fragment: tags removed, entities decoded, whitespace collapsed
+pub fn html_text(fragment: &str) -> String {
+    static TAG: OnceLock<Regex> = OnceLock::new();
+    let tag = TAG.get_or_init(|| Regex::new(r"<[^>]*>").expect("valid pattern"));
+    let text = decode_entities(&tag.replace_all(fragment, " "));
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Decode the common named and numeric HTML entities
+pub fn decode_entities(text: &str) -> String {
+    static ENTITY: OnceLock<Regex> = OnceLock::new();
+    let entity = ENTITY.get_or_init(|| {
+        Regex::new(r"&(#[0-9]+|#[xX][0-9a-fA-F]+|[a-zA-Z]+);").expect("valid pattern")
+    });
+    entity
+        .replace_all(text, |captures: &regex::Captures<'_>| {
+            let name = &captures[1];
+            let decoded = match name {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => name
+                    .strip_prefix("#x")
+                    .or_else(|| name.strip_prefix("#X"))
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| name.strip_prefix('#').map(str::parse::<u32>))
+                    .and_then(|code| code.ok())
+                    .and_then(char::from_u32),
+            };
+            decoded.map_or_else(|| captures[0].to_string(), String::from)
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_info_non_empty() {
+        assert!(ProfileInfo::default().non_empty().is_none());
+        let profile = ProfileInfo {
+            name: Some("Blue".to_string()),
+            ..ProfileInfo::default()
+        };
+        assert_eq!(profile.clone().non_empty(), Some(profile.clone()));
+
+        let json = serde_json::to_string(&profile).unwrap();
+        assert_eq!(json, r#"{"name":"Blue"}"#);
+    }
+
+    #[test]
+    fn test_profile_info_merge() {
+        let specific = ProfileInfo {
+            name: Some("Blue".to_string()),
+            links: vec!["https://blue.example".to_string()],
+            ..ProfileInfo::default()
+        };
+        let generic = ProfileInfo {
+            name: Some("Blue on Example".to_string()),
+            bio: Some("Hello".to_string()),
+            links: vec![
+                "https://blue.example".to_string(),
+                "https://other.example/blue".to_string(),
+            ],
+            ..ProfileInfo::default()
+        };
+        let merged = specific.merge(generic);
+        assert_eq!(merged.name.as_deref(), Some("Blue"));
+        assert_eq!(merged.bio.as_deref(), Some("Hello"));
+        assert_eq!(
+            merged.links,
+            vec!["https://blue.example", "https://other.example/blue"]
+        );
+    }
+
+    #[test]
+    fn test_json_helpers() {
+        let json: Value = serde_json::json!({
+            "name": "  Blue  ",
+            "empty": "",
+            "followers": 42,
+            "karma": 12.0,
+            "created": 1_300_000_000.0
+        });
+        assert_eq!(json_string(&json, "name").as_deref(), Some("Blue"));
+        assert_eq!(json_string(&json, "empty"), None);
+        assert_eq!(json_count(&json, "followers"), Some(42));
+        assert_eq!(json_count(&json, "karma"), Some(12));
+        assert_eq!(json_count(&json, "name"), None);
+        assert_eq!(
+            json_timestamp(&json, "created").as_deref(),
+            Some("2011-03-13T07:06:40+00:00")
+        );
+        assert_eq!(json_timestamp(&json, "name").as_deref(), Some("Blue"));
+        assert_eq!(json_timestamp(&json, "missing"), None);
+    }
+
+    #[test]
+    fn test_html_text() {
+        assert_eq!(
+            html_text("  <b>Tom &amp; Jerry</b>\n<br>&#39;s &#x41; page&nbsp;"),
+            "Tom & Jerry 's A page"
+        );
+        assert_eq!(decode_entities("&unknown; &lt;3"), "&unknown; <3");
+
+        let pattern = Regex::new(r#"<span class="name">([^<]*)</span>"#).unwrap();
+        assert_eq!(
+            html_capture(&pattern, r#"<span class="name"> Blue </span>"#).as_deref(),
+            Some("Blue")
+        );
+        assert_eq!(
+            html_capture(&pattern, r#"<span class="name"></span>"#),
+            None
+        );
+    }
+}
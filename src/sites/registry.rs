@@ -1,6 +1,11 @@
+use crate::config::sherlock::load_sherlock;
+use crate::config::sites::{load_sites, SiteImport};
+use crate::config::wmn::load_wmn;
 use crate::data::site_info::SiteType;
 use crate::sites::Site;
+use crate::utils::error::Result;
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 
 /// Registry for managing all available sites
@@ -23,6 +28,50 @@ impl SiteRegistry {
         Self { all_sites: sites }
     }
 
+    /// Create a registry from the sites declared in a manifest file
+    pub fn from_manifest(path: &Path) -> Result<Self> {
+        Ok(Self::from_sites(load_sites(path)?))
+    }
+
+    /// Add sites to the registry
+    ///
+    /// Sites whose name is already registered (case-insensitive) are ignored,
+    /// so earlier definitions such as the built-in checkers take precedence.
+    /// Returns the number of sites actually added.
+    pub fn extend(&mut self, sites: Vec<Arc<dyn Site>>) -> usize {
+        let mut added = 0;
+        for site in sites {
+            if self.by_name(site.name()).is_none() {
+                self.all_sites.push(site);
+                added += 1;
+            }
+        }
+        added
+    }
+
+    /// Add the sites declared in a manifest file to the registry
+    pub fn load_manifest(&mut self, path: &Path) -> Result<usize> {
+        Ok(self.extend(load_sites(path)?))
+    }
+
+    /// Add the sites from sherlock's `data.json` to the registry
+    ///
+    /// Returns the import so callers can report skipped entries.
+    pub fn load_sherlock(&mut self, path: &Path) -> Result<SiteImport> {
+        let import = load_sherlock(path)?;
+        self.extend(import.clone().into_sites());
+        Ok(import)
+    }
+
+    /// Add the sites from WhatsMyName's `wmn-data.json` to the registry
+    ///
+    /// Returns the import so callers can report skipped entries.
+    pub fn load_wmn(&mut self, path: &Path) -> Result<SiteImport> {
+        let import = load_wmn(path)?;
+        self.extend(import.clone().into_sites());
+        Ok(import)
+    }
+
     /// Get all sites
     pub fn all(&self) -> &[Arc<dyn Site>] {
         &self.all_sites
@@ -51,6 +100,15 @@ impl SiteRegistry {
             .collect()
     }
 
+    /// Get sites carrying a tag (case-insensitive)
+    pub fn by_tag(&self, tag: &str) -> Vec<Arc<dyn Site>> {
+        self.all_sites
+            .iter()
+            .filter(|site| site.tags().iter().any(|t| t.eq_ignore_ascii_case(tag)))
+            .cloned()
+            .collect()
+    }
+
     /// Find a site by name (case-insensitive)
     pub fn by_name(&self, name: &str) -> Option<Arc<dyn Site>> {
         let name_lower = name.to_lowercase();
@@ -226,6 +284,70 @@ mod tests {
         assert_eq!(filtered[0].name(), "GitHub");
     }
 
+    #[test]
+    fn test_registry_extend() {
+        let mut registry = SiteRegistry::from_sites(vec![Arc::new(MockSite {
+            name: "GitHub".to_string(),
+            site_type: SiteType::Dev,
+        })]);
+        let added = registry.extend(vec![
+            Arc::new(MockSite {
+                name: "Twitter".to_string(),
+                site_type: SiteType::Social,
+            }),
+            Arc::new(MockSite {
+                name: "github".to_string(),
+                site_type: SiteType::Other,
+            }),
+        ]);
+        assert_eq!(added, 1);
+        assert_eq!(registry.count(), 2);
+        assert!(registry.by_name("twitter").is_some());
+        // The existing GitHub entry wins over the duplicate
+        assert_eq!(
+            registry.by_name("github").unwrap().site_type(),
+            SiteType::Dev
+        );
+    }
+
+    #[test]
+    fn test_registry_from_manifest() {
+        let path = std::env::temp_dir().join(format!(
+            "sleuth-registry-manifest-{}.json",
+            std::process::id()
+        ));
+        std::fs::write(
+            &path,
+            r#"{ "sites": [ { "name": "Declared", "type": "forum", "url_pattern": "https://forum.example/u/{}" } ] }"#,
+        )
+        .unwrap();
+
+        let registry = SiteRegistry::from_manifest(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(registry.count(), 1);
+        assert_eq!(registry.by_type(SiteType::Forum).len(), 1);
+    }
+
+    #[test]
+    fn test_registry_by_tag() {
+        use crate::sites::declarative::{DeclarativeSite, SiteDefinition};
+
+        let definition = SiteDefinition {
+            tags: vec!["hobby".to_string()],
+            ..SiteDefinition::new("Knitting", SiteType::Other, "https://knit.example/{}")
+        };
+        let registry = SiteRegistry::from_sites(vec![
+            Arc::new(DeclarativeSite::new(definition).unwrap()),
+            Arc::new(MockSite {
+                name: "GitHub".to_string(),
+                site_type: SiteType::Dev,
+            }),
+        ]);
+        let hobby = registry.by_tag("Hobby");
+        assert_eq!(hobby.len(), 1);
+        assert_eq!(hobby[0].name(), "Knitting");
+    }
+
     #[test]
     fn test_registry_statistics() {
         let sites: Vec<Arc<dyn Site>> = vec![
@@ -1,6 +1,38 @@
 //! Site trait for username checking
 
 use crate::data::site_info::SiteType;
+use crate::request::{RateLimit, RequestResponse, RequestSpec};
+use crate::sites::detection::{Detection, ResponseView, RuleSet};
+use crate::sites::profile::ProfileInfo;
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+/// Regex matching the profile URLs of a URL pattern, compiled once per pattern
+fn profile_url_regex(url_pattern: &str) -> Option<Regex> {
+    static CACHE: OnceLock<Mutex<HashMap<String, Option<Regex>>>> = OnceLock::new();
+    let mut cache = CACHE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    cache
+        .entry(url_pattern.to_string())
+        .or_insert_with(|| {
+            let pattern = url_pattern
+                .split_once("://")
+                .map_or(url_pattern, |(_, rest)| rest);
+            let pattern = pattern.strip_prefix("www.").unwrap_or(pattern);
+            let (prefix, suffix) = pattern.split_once("{}")?;
+            Regex::new(&format!(
+                r"(?i)^https?://(?:www\.)?{}([^/?#]+){}/?(?:[?#].*)?$",
+                regex::escape(prefix),
+                regex::escape(suffix)
+            ))
+            .ok()
+        })
+        .clone()
+}
 
 /// Trait that all site checkers must implement
 /// Sites are responsible for URL construction and response interpretation,
@@ -16,18 +48,63 @@ pub trait Site: Send + Sync {
     fn site_type(&self) -> SiteType;
 
     /// Build the full URL to check for a username
+    /// This is the human-facing profile URL reported in results
     fn build_url(&self, username: &str) -> String {
         self.url_pattern().replace("{}", username)
     }
 
+    /// URL pattern that is actually requested (default: `url_pattern()`)
+    /// Override to probe an API or mobile endpoint instead of the profile page
+    fn probe_url_pattern(&self) -> &str {
+        self.url_pattern()
+    }
+
+    /// Build the URL to request when checking a username
+    fn build_probe_url(&self, username: &str) -> String {
+        self.probe_url_pattern().replace("{}", username)
+    }
+
+    /// Username of a profile URL on this site, if `url` is one
+    /// Defaults to matching `url_pattern()` (any scheme, optional `www.`, trailing slash)
+    fn username_from_url(&self, url: &str) -> Option<String> {
+        let regex = profile_url_regex(self.url_pattern())?;
+        let username = regex.captures(url.trim())?.get(1)?.as_str();
+        self.validate_username(username)
+            .then(|| username.to_string())
+    }
+
+    /// Regex a username must match to be possible on this site (default: none)
+    fn username_pattern(&self) -> Option<&Regex> {
+        None
+    }
+
+    /// Check whether a username is possible on this site
+    /// Defaults to `username_pattern()`; override for rules a regex cannot express
+    /// Usernames that fail are reported as invalid without making a request
+    fn validate_username(&self, username: &str) -> bool {
+        self.username_pattern()
+            .map(|pattern| pattern.is_match(username))
+            .unwrap_or(true)
+    }
+
+    /// Detection rules for rule-based sites (default: none)
+    /// When present, they replace the status-code defaults of `parse_response()`
+    fn detection_rules(&self) -> Option<&RuleSet> {
+        None
+    }
+
     /// Determine if username exists based on HTTP response
     /// Returns Some(true) if exists, Some(false) if not found, None if uncertain
     fn parse_response(
         &self,
         _username: &str,
         status_code: u16,
-        _body: Option<&str>,
+        body: Option<&str>,
     ) -> Option<bool> {
+        if let Some(rules) = self.detection_rules() {
+            return Detection::from(rules.evaluate(&ResponseView::new(status_code, body))).exists;
+        }
+
         // Default implementation: 200 = exists, 404 = not found
         match status_code {
             200..=299 => Some(true),
@@ -36,21 +113,121 @@ pub trait Site: Send + Sync {
         }
     }
 
+    /// Whether the probe should follow redirects (default: true)
+    /// Sites that signal a missing user by redirecting (e.g. to a login page)
+    /// return false and detect on the 3xx status and `Location` header
+    fn follow_redirects(&self) -> bool {
+        true
+    }
+
+    /// Whether the probe returns JSON (default: false)
+    /// JSON sites are always fetched with GET and can use JSON conditions in their rules
+    fn expects_json(&self) -> bool {
+        false
+    }
+
+    /// Run detection on a full response (status, body, headers, final URL)
+    /// Rule-based sites report which rule decided; others defer to `parse_response()`
+    fn detect(&self, username: &str, response: &RequestResponse) -> Detection {
+        let view = ResponseView::from(response);
+
+        // A successful response that is not JSON (e.g. a block page) proves nothing
+        if self.expects_json() && (200..=299).contains(&view.status_code) && view.json().is_none() {
+            return Detection::default();
+        }
+
+        match self.detection_rules() {
+            Some(rules) => Detection::from(rules.evaluate(&view)),
+            None => Detection {
+                exists: self.parse_response(
+                    username,
+                    response.status_code,
+                    response.body.as_deref(),
+                ),
+                rule: None,
+                confidence: None,
+            },
+        }
+    }
+
     /// Get HTTP method to use (default: HEAD for efficiency)
     fn http_method(&self) -> &'static str {
         "HEAD"
     }
 
     /// Get custom headers if needed (default: none)
-    fn headers(&self) -> Vec<(&'static str, &'static str)> {
+    fn headers(&self) -> Vec<(&str, &str)> {
+        vec![]
+    }
+
+    /// Cookies to send with the probe (default: none)
+    fn cookies(&self) -> Vec<(&str, &str)> {
         vec![]
     }
 
+    /// Body to send with the probe for a username, for POST checks (default: none)
+    fn request_body(&self, _username: &str) -> Option<String> {
+        None
+    }
+
+    /// Build the probe request for a username: method, probe URL, headers,
+    /// cookies, body, redirect policy and the site's timeout
+    /// JSON probes need the body, so a HEAD method is sent as GET
+    fn build_request(&self, username: &str) -> RequestSpec {
+        let method = match self.http_method() {
+            "HEAD" if self.expects_json() => "GET",
+            method => method,
+        };
+        let mut spec = RequestSpec::new(method, &self.build_probe_url(username))
+            .with_follow_redirects(self.follow_redirects());
+        for (name, value) in self.headers() {
+            spec = spec.with_header(name, value);
+        }
+        for (name, value) in self.cookies() {
+            spec = spec.with_cookie(name, value);
+        }
+        spec.body = self.request_body(username);
+        spec.timeout = self.timeout();
+        spec
+    }
+
     /// Whether this site requires browser rendering (JavaScript execution)
     /// Sites that render content dynamically should return true
     fn requires_browser(&self) -> bool {
         false // Default: no browser needed
     }
+
+    /// Time allowed for a request to this site (default: none, use the scan timeout)
+    /// Slow, JavaScript-heavy sites can ask for more time
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Request budget for the site's host (default: none, only server-requested pauses apply)
+    fn rate_limit(&self) -> Option<RateLimit> {
+        None
+    }
+
+    /// Read profile details from the response of a found account (default: none)
+    /// Only called for found results; the body is there if the site is fetched with GET
+    fn extract_profile(&self, _username: &str, _response: &RequestResponse) -> Option<ProfileInfo> {
+        None
+    }
+
+    /// A username known to exist on the site, for self-testing (default: none)
+    fn claimed_username(&self) -> Option<&str> {
+        None
+    }
+
+    /// A username known not to exist on the site, for self-testing (default: none)
+    fn unclaimed_username(&self) -> Option<&str> {
+        None
+    }
+
+    /// Free-form tags describing the site beyond its `SiteType` (default: none)
+    fn tags(&self) -> &[String] {
+        &[]
+    }
 }
 
 #[cfg(test)]
@@ -88,12 +265,52 @@ mod tests {
         assert_eq!(site.parse_response("testuser", 500, None), None);
     }
 
+    #[test]
+    fn test_site_username_from_url() {
+        let site = TestSite;
+        for url in [
+            "https://test.com/blue",
+            "http://www.test.com/blue/",
+            "HTTPS://TEST.COM/blue?tab=posts",
+        ] {
+            assert_eq!(
+                site.username_from_url(url).as_deref(),
+                Some("blue"),
+                "{url}"
+            );
+        }
+        assert_eq!(site.username_from_url("https://test.com/blue/posts"), None);
+        assert_eq!(site.username_from_url("https://other.com/blue"), None);
+        assert_eq!(site.username_from_url("https://test.com/"), None);
+    }
+
+    #[test]
+    fn test_profile_url_regex_cached() {
+        let regex = profile_url_regex("https://cache.example/u/{}").unwrap();
+        assert!(regex.is_match("https://cache.example/u/blue"));
+        assert_eq!(
+            profile_url_regex("https://cache.example/u/{}")
+                .unwrap()
+                .as_str(),
+            regex.as_str()
+        );
+        // Patterns without a placeholder have no profile URLs
+        assert!(profile_url_regex("https://cache.example/").is_none());
+    }
+
     #[test]
     fn test_site_http_method() {
         let site = TestSite;
         assert_eq!(site.http_method(), "HEAD");
     }
 
+    #[test]
+    fn test_site_timeout_default() {
+        let site = TestSite;
+        assert!(site.timeout().is_none());
+        assert!(site.rate_limit().is_none());
+    }
+
     #[test]
     fn test_site_headers() {
         let site = TestSite;
@@ -101,6 +318,204 @@ mod tests {
         assert!(headers.is_empty());
     }
 
+    #[test]
+    fn test_site_build_request() {
+        let spec = TestSite.build_request("blue");
+        assert_eq!(spec, RequestSpec::head("https://test.com/blue"));
+
+        struct ApiSite;
+
+        impl Site for ApiSite {
+            fn name(&self) -> &str {
+                "Api"
+            }
+            fn url_pattern(&self) -> &str {
+                "https://api.example/users/{}"
+            }
+            fn site_type(&self) -> SiteType {
+                SiteType::Other
+            }
+            fn expects_json(&self) -> bool {
+                true
+            }
+            fn follow_redirects(&self) -> bool {
+                false
+            }
+            fn headers(&self) -> Vec<(&str, &str)> {
+                vec![("Accept", "application/json")]
+            }
+            fn cookies(&self) -> Vec<(&str, &str)> {
+                vec![("consent", "yes")]
+            }
+            fn timeout(&self) -> Option<Duration> {
+                Some(Duration::from_secs(20))
+            }
+        }
+
+        let spec = ApiSite.build_request("blue");
+        assert_eq!(spec.method, "GET");
+        assert_eq!(spec.url, "https://api.example/users/blue");
+        assert_eq!(spec.header("accept"), Some("application/json"));
+        assert_eq!(spec.cookie_header().as_deref(), Some("consent=yes"));
+        assert!(spec.body.is_none());
+        assert!(!spec.follow_redirects);
+        assert_eq!(spec.timeout, Some(Duration::from_secs(20)));
+    }
+
+    #[test]
+    fn test_site_detect_without_rules() {
+        let site = TestSite;
+        assert!(site.detection_rules().is_none());
+        let detection = site.detect("testuser", &RequestResponse::new(404));
+        assert_eq!(detection.exists, Some(false));
+        assert!(detection.rule.is_none());
+    }
+
+    #[test]
+    fn test_rule_based_site() {
+        use crate::sites::detection::{Condition, Rule, Verdict};
+
+        struct RuleSite {
+            rules: RuleSet,
+        }
+
+        impl Site for RuleSite {
+            fn name(&self) -> &str {
+                "RuleSite"
+            }
+
+            fn url_pattern(&self) -> &str {
+                "https://rules.example/{}"
+            }
+
+            fn site_type(&self) -> SiteType {
+                SiteType::Other
+            }
+
+            fn detection_rules(&self) -> Option<&RuleSet> {
+                Some(&self.rules)
+            }
+        }
+
+        let site = RuleSite {
+            rules: RuleSet::new(vec![
+                Rule::new(
+                    "login-redirect",
+                    Verdict::NotFound,
+                    vec![Condition::FinalUrlContains("/login".to_string())],
+                ),
+                Rule::new("ok", Verdict::Found, vec![Condition::success()]),
+            ]),
+        };
+
+        // parse_response() goes through the rules too
+        assert_eq!(site.parse_response("testuser", 200, None), Some(true));
+        assert_eq!(site.parse_response("testuser", 404, None), None);
+
+        let mut response = RequestResponse::new(200);
+        response.final_url = Some("https://rules.example/login".to_string());
+        let detection = site.detect("testuser", &response);
+        assert_eq!(detection.exists, Some(false));
+        assert_eq!(detection.rule.as_deref(), Some("login-redirect"));
+    }
+
+    #[test]
+    fn test_json_site_detect() {
+        use crate::sites::detection::{Condition, Rule, Verdict};
+
+        struct JsonSite {
+            rules: RuleSet,
+        }
+
+        impl Site for JsonSite {
+            fn name(&self) -> &str {
+                "JsonSite"
+            }
+            fn url_pattern(&self) -> &str {
+                "https://json.example/api/{}"
+            }
+            fn site_type(&self) -> SiteType {
+                SiteType::Other
+            }
+            fn detection_rules(&self) -> Option<&RuleSet> {
+                Some(&self.rules)
+            }
+            fn expects_json(&self) -> bool {
+                true
+            }
+        }
+
+        let site = JsonSite {
+            rules: RuleSet::new(vec![
+                Rule::new(
+                    "has-id",
+                    Verdict::Found,
+                    vec![Condition::JsonExists("/id".to_string())],
+                ),
+                Rule::new("ok", Verdict::NotFound, vec![Condition::success()]),
+            ]),
+        };
+        assert!(!TestSite.expects_json());
+        assert!(TestSite.follow_redirects());
+        assert!(TestSite.claimed_username().is_none());
+        assert!(TestSite.unclaimed_username().is_none());
+
+        let found = RequestResponse::with_body(200, r#"{"id": 7}"#.to_string());
+        assert_eq!(site.detect("testuser", &found).exists, Some(true));
+
+        let missing = RequestResponse::with_body(200, "{}".to_string());
+        assert_eq!(site.detect("testuser", &missing).exists, Some(false));
+
+        // An HTML block page is not evidence either way
+        let blocked = RequestResponse::with_body(200, "<html>Blocked</html>".to_string());
+        assert_eq!(site.detect("testuser", &blocked), Detection::default());
+    }
+
+    #[test]
+    fn test_site_probe_url_defaults_to_url_pattern() {
+        let site = TestSite;
+        assert_eq!(site.probe_url_pattern(), site.url_pattern());
+        assert_eq!(site.build_probe_url("user"), "https://test.com/user");
+    }
+
+    #[test]
+    fn test_site_validate_username() {
+        struct StrictSite {
+            pattern: Regex,
+        }
+
+        impl Site for StrictSite {
+            fn name(&self) -> &str {
+                "StrictSite"
+            }
+            fn url_pattern(&self) -> &str {
+                "https://strict.example/{}"
+            }
+            fn site_type(&self) -> SiteType {
+                SiteType::Other
+            }
+            fn username_pattern(&self) -> Option<&Regex> {
+                Some(&self.pattern)
+            }
+        }
+
+        // No pattern: anything goes
+        assert!(TestSite.validate_username("any.name-here"));
+
+        let site = StrictSite {
+            pattern: Regex::new("^[a-z][a-z0-9]{2,7}$").unwrap(),
+        };
+        assert!(site.validate_username("user123"));
+        assert!(!site.validate_username("1user"));
+        assert!(!site.validate_username("ab"));
+    }
+
+    #[test]
+    fn test_site_tags() {
+        let site = TestSite;
+        assert!(site.tags().is_empty());
+    }
+
     #[test]
     fn test_site_build_url_with_special_chars() {
         let site = TestSite;
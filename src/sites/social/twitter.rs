@@ -1,5 +1,7 @@
 use crate::data::site_info::SiteType;
 use crate::sites::Site;
+use regex::Regex;
+use std::sync::OnceLock;
 
 /// Twitter/X username checker
 pub struct TwitterChecker;
@@ -28,6 +30,16 @@ impl Site for TwitterChecker {
     fn site_type(&self) -> SiteType {
         SiteType::Social
     }
+
+    fn claimed_username(&self) -> Option<&str> {
+        Some("jack")
+    }
+
+    fn username_pattern(&self) -> Option<&Regex> {
+        // Letters, digits and underscores, at most 15 characters
+        static PATTERN: OnceLock<Regex> = OnceLock::new();
+        Some(PATTERN.get_or_init(|| Regex::new(r"^[A-Za-z0-9_]{1,15}$").expect("valid pattern")))
+    }
 }
 
 #[cfg(test)]
@@ -71,4 +83,12 @@ mod tests {
         assert_eq!(checker.parse_response("testuser", 404, None), Some(false));
         assert_eq!(checker.parse_response("testuser", 500, None), None);
     }
+
+    #[test]
+    fn test_twitter_checker_validate_username() {
+        let checker = TwitterChecker::new();
+        assert!(checker.validate_username("jack"));
+        assert!(!checker.validate_username("has.dot"));
+        assert!(!checker.validate_username("a_very_long_username"));
+    }
 }
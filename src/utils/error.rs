@@ -17,6 +17,9 @@ pub enum SleuthError {
     #[error("Site error: {0}")]
     Site(String),
 
+    #[error("Timeout: {0}")]
+    Timeout(String),
+
     #[error("Validation error: {0}")]
     Validation(String),
 
@@ -24,6 +27,36 @@ pub enum SleuthError {
     Unknown(String),
 }
 
+impl SleuthError {
+    /// Whether the error is transient and the request worth retrying
+    /// (timeouts, connection failures and resets)
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            SleuthError::Timeout(_) => true,
+            SleuthError::Http(e) => e.is_timeout() || e.is_connect() || e.is_request(),
+            SleuthError::Io(e) => matches!(
+                e.kind(),
+                std::io::ErrorKind::TimedOut
+                    | std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::Interrupted
+                    | std::io::ErrorKind::UnexpectedEof
+            ),
+            _ => false,
+        }
+    }
+
+    /// Whether the request did not complete in time
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            SleuthError::Timeout(_) => true,
+            SleuthError::Http(e) => e.is_timeout(),
+            SleuthError::Io(e) => e.kind() == std::io::ErrorKind::TimedOut,
+            _ => false,
+        }
+    }
+}
+
 /// Result type alias for sleuth operations
 pub type Result<T> = std::result::Result<T, SleuthError>;
 
@@ -64,6 +97,28 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_error_is_retryable() {
+        let reset = std::io::Error::from(std::io::ErrorKind::ConnectionReset);
+        assert!(SleuthError::Io(reset).is_retryable());
+
+        let missing = std::io::Error::from(std::io::ErrorKind::NotFound);
+        assert!(!SleuthError::Io(missing).is_retryable());
+        assert!(!SleuthError::Config("bad".to_string()).is_retryable());
+    }
+
+    #[test]
+    fn test_error_is_timeout() {
+        let err = SleuthError::Timeout("GitHub did not answer".to_string());
+        assert!(err.is_timeout());
+        assert!(err.is_retryable());
+        assert!(err.to_string().contains("Timeout"));
+
+        let timed_out = std::io::Error::from(std::io::ErrorKind::TimedOut);
+        assert!(SleuthError::Io(timed_out).is_timeout());
+        assert!(!SleuthError::Site("site".to_string()).is_timeout());
+    }
+
     #[test]
     fn test_error_debug() {
         let err = SleuthError::Config("test".to_string());